@@ -0,0 +1,31 @@
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+///
+/// The source of the time for the cycles, timestamps and timeouts
+/// - [RealClock] - the system time, used by default
+/// - [VirtualClock] - manually advanced time, used in the deterministic tests
+pub trait Clock: Send + Sync {
+    ///
+    /// Returns the current monotonic time
+    fn now(&self) -> Instant;
+    ///
+    /// Returns the current wall-clock time
+    fn utc(&self) -> DateTime<Utc>;
+    ///
+    /// Blocks the current thread for the `duration` of the time of this clock
+    fn sleep(&self, duration: Duration);
+    ///
+    /// Blocks the current thread for the `duration` with the better precision
+    /// - `spin` - the last stretch of the `duration` to be spinned instead of sleeping
+    ///
+    /// By default the same as `sleep`
+    fn sleep_precise(&self, duration: Duration, spin: Duration) {
+        let _ = spin;
+        self.sleep(duration);
+    }
+    ///
+    /// Returns the time elapsed since the `instant`
+    fn elapsed(&self, instant: Instant) -> Duration {
+        self.now().saturating_duration_since(instant)
+    }
+}
@@ -0,0 +1,82 @@
+use std::{cell::RefCell, sync::{Arc, RwLock}, time::Instant};
+use chrono::{DateTime, Utc};
+use super::{Clock, RealClock};
+///
+/// The process-wide clock, `None` means [RealClock]
+static GLOBAL: RwLock<Option<Arc<dyn Clock>>> = RwLock::new(None);
+thread_local! {
+    ///
+    /// The clock of the current thread, overrides the process-wide one
+    static LOCAL: RefCell<Option<Arc<dyn Clock>>> = const { RefCell::new(None) };
+}
+///
+/// ### Access to the [Clock] used by the components not owning their own one
+/// - The clock of the current thread, installed by `local`, is used if present
+/// - Otherwise the process-wide clock, installed by `set`, [RealClock] by default
+///
+/// Components like `ServiceCycle` and `Services` capture the clock on creation,
+/// point constructors read it on each call
+pub struct GlobalClock;
+//
+//
+impl GlobalClock {
+    ///
+    /// Returns the current clock
+    pub fn get() -> Arc<dyn Clock> {
+        LOCAL.with(|local| local.borrow().clone())
+            .or_else(|| GLOBAL.read().unwrap_or_else(|err| err.into_inner()).clone())
+            .unwrap_or_else(|| Arc::new(RealClock))
+    }
+    ///
+    /// Installs the process-wide `clock`
+    pub fn set(clock: Arc<dyn Clock>) {
+        *GLOBAL.write().unwrap_or_else(|err| err.into_inner()) = Some(clock);
+    }
+    ///
+    /// Restores the process-wide [RealClock]
+    pub fn reset() {
+        *GLOBAL.write().unwrap_or_else(|err| err.into_inner()) = None;
+    }
+    ///
+    /// Installs the `clock` for the current thread until returned guard is dropped
+    /// - Safe for the tests running in parallel
+    pub fn local(clock: Arc<dyn Clock>) -> LocalClockGuard {
+        let prev = LOCAL.with(|local| local.borrow_mut().replace(clock));
+        LocalClockGuard { prev }
+    }
+    ///
+    /// Returns the current monotonic time of the current clock
+    pub fn now() -> Instant {
+        Self::with(|clock| clock.now(), Instant::now)
+    }
+    ///
+    /// Returns the current wall-clock time of the current clock
+    pub fn utc() -> DateTime<Utc> {
+        Self::with(|clock| clock.utc(), Utc::now)
+    }
+    ///
+    /// Calls `f` with the installed clock, or `real` if nothing installed, avoiding the allocation
+    fn with<T>(f: impl Fn(&dyn Clock) -> T, real: impl FnOnce() -> T) -> T {
+        if let Some(value) = LOCAL.with(|local| local.borrow().as_deref().map(&f)) {
+            return value;
+        }
+        match GLOBAL.read().unwrap_or_else(|err| err.into_inner()).as_deref() {
+            Some(clock) => f(clock),
+            None => real(),
+        }
+    }
+}
+///
+/// Restores the previous clock of the current thread on drop
+#[must_use]
+pub struct LocalClockGuard {
+    prev: Option<Arc<dyn Clock>>,
+}
+//
+//
+impl Drop for LocalClockGuard {
+    fn drop(&mut self) {
+        let prev = self.prev.take();
+        LOCAL.with(|local| *local.borrow_mut() = prev);
+    }
+}
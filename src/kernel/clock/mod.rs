@@ -0,0 +1,9 @@
+mod clock;
+mod global_clock;
+mod real_clock;
+mod virtual_clock;
+
+pub use clock::*;
+pub use global_clock::*;
+pub use real_clock::*;
+pub use virtual_clock::*;
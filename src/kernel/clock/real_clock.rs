@@ -0,0 +1,54 @@
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use super::Clock;
+///
+/// The system time, the default [Clock]
+/// - `sleep_precise` - hybrid sleep: the thread sleeps coarsely,
+///   then yields and spins for the last stretch, because `thread::sleep` overshoots by 50..100 us on the stock Linux
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealClock;
+//
+//
+impl RealClock {
+    ///
+    /// Remaining time of the `sleep_precise`, above which the thread yields instead of spinning
+    const YIELD_THRESHOLD: Duration = Duration::from_micros(50);
+}
+//
+//
+impl Clock for RealClock {
+    //
+    //
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+    //
+    //
+    fn utc(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+    //
+    //
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+    //
+    //
+    fn sleep_precise(&self, duration: Duration, spin: Duration) {
+        let deadline = Instant::now() + duration;
+        if duration > spin {
+            std::thread::sleep(duration - spin);
+        }
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            if deadline - now > Self::YIELD_THRESHOLD {
+                std::thread::yield_now();
+            } else {
+                std::hint::spin_loop();
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use parking_lot::Condvar;
+use crate::sync::Mutex;
+use super::Clock;
+///
+/// The state of the [VirtualClock]
+#[derive(Debug, Default)]
+struct VirtualTime {
+    elapsed: Duration,
+    sleeping: usize,
+}
+///
+/// ### Manually advanced [Clock] for the deterministic tests
+/// - The time is started from the `origin` and changed only by `advance`
+/// - `sleep` blocks until the time is advanced enough by the another thread
+/// - In the `auto` mode `sleep` advances the time itself and returns immediately,
+///   so hours of the cyclic operation are simulated in milliseconds
+///
+/// **Example**
+/// ```ignore
+/// let clock = Arc::new(VirtualClock::new(Utc::now()));
+/// let mut cycle = ServiceCycle::with_clock("App", Duration::from_millis(100), clock.clone());
+/// ...
+/// clock.wait_sleeping(1, Duration::from_secs(1));
+/// clock.advance(Duration::from_millis(100));
+/// ```
+#[derive(Debug)]
+pub struct VirtualClock {
+    origin: Instant,
+    utc: DateTime<Utc>,
+    auto: bool,
+    time: Mutex<VirtualTime>,
+    changed: Condvar,
+}
+//
+//
+impl VirtualClock {
+    ///
+    /// Returns [VirtualClock] new instance, advanced only by `advance`
+    /// - `utc` - the wall-clock time at the start
+    pub fn new(utc: DateTime<Utc>) -> Self {
+        Self {
+            origin: Instant::now(),
+            utc,
+            auto: false,
+            time: Mutex::new(VirtualTime::default()),
+            changed: Condvar::new(),
+        }
+    }
+    ///
+    /// Returns [VirtualClock] new instance, advanced by each `sleep`
+    /// - `utc` - the wall-clock time at the start
+    pub fn auto(utc: DateTime<Utc>) -> Self {
+        Self { auto: true, ..Self::new(utc) }
+    }
+    ///
+    /// Returns the time elapsed since the start of the clock
+    pub fn elapsed_total(&self) -> Duration {
+        self.time.lock().elapsed
+    }
+    ///
+    /// Moves the time forward by the `duration`, sleeping threads are woken up
+    pub fn advance(&self, duration: Duration) {
+        self.time.lock().elapsed += duration;
+        self.changed.notify_all();
+    }
+    ///
+    /// Returns the number of the threads currently sleeping on this clock
+    pub fn sleeping(&self) -> usize {
+        self.time.lock().sleeping
+    }
+    ///
+    /// Waits up to real `timeout` until at least `count` threads are sleeping on this clock
+    /// - Used to advance the time only when the tested threads are waiting for it
+    ///
+    /// Returns `false` if the timeout is elapsed
+    pub fn wait_sleeping(&self, count: usize, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut time = self.time.lock();
+        while time.sleeping < count {
+            if self.changed.wait_until(&mut time, deadline).timed_out() {
+                return time.sleeping >= count;
+            }
+        }
+        true
+    }
+}
+//
+//
+impl Clock for VirtualClock {
+    //
+    //
+    fn now(&self) -> Instant {
+        self.origin + self.time.lock().elapsed
+    }
+    //
+    //
+    fn utc(&self) -> DateTime<Utc> {
+        self.utc + self.time.lock().elapsed
+    }
+    //
+    //
+    fn sleep(&self, duration: Duration) {
+        if self.auto {
+            self.advance(duration);
+            return;
+        }
+        let mut time = self.time.lock();
+        let until = time.elapsed + duration;
+        time.sleeping += 1;
+        self.changed.notify_all();
+        while time.elapsed < until {
+            self.changed.wait(&mut time);
+        }
+        time.sleeping -= 1;
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::{atomic::{AtomicU64, Ordering}, Arc};
+///
+/// Monotonically increasing value, cheap to clone, all clones share the same value
+#[derive(Debug, Clone, Default)]
+pub struct Counter {
+    value: Arc<AtomicU64>,
+}
+//
+//
+impl Counter {
+    ///
+    /// Returns [Counter] new instance, not registered in the [Metrics](super::Metrics)
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Increments the value by 1
+    pub fn inc(&self) {
+        self.value.fetch_add(1, Ordering::Relaxed);
+    }
+    ///
+    /// Increments the value by `value`
+    pub fn add(&self, value: u64) {
+        self.value.fetch_add(value, Ordering::Relaxed);
+    }
+    ///
+    /// Returns current value
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
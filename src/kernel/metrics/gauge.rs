@@ -0,0 +1,55 @@
+use std::sync::{atomic::{AtomicU64, Ordering}, Arc};
+///
+/// Value going up and down, cheap to clone, all clones share the same value
+#[derive(Debug, Clone)]
+pub struct Gauge {
+    bits: Arc<AtomicU64>,
+}
+//
+//
+impl Gauge {
+    ///
+    /// Returns [Gauge] new instance, not registered in the [Metrics](super::Metrics)
+    pub fn new() -> Self {
+        Self { bits: Arc::new(AtomicU64::new(0f64.to_bits())) }
+    }
+    ///
+    /// Sets the value
+    pub fn set(&self, value: f64) {
+        self.bits.store(value.to_bits(), Ordering::Relaxed);
+    }
+    ///
+    /// Adds the `value`, negative to decrease
+    pub fn add(&self, value: f64) {
+        let mut current = self.bits.load(Ordering::Relaxed);
+        loop {
+            let next = (f64::from_bits(current) + value).to_bits();
+            match self.bits.compare_exchange_weak(current, next, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => break,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+    ///
+    /// Increases the value by 1
+    pub fn inc(&self) {
+        self.add(1.0);
+    }
+    ///
+    /// Decreases the value by 1
+    pub fn dec(&self) {
+        self.add(-1.0);
+    }
+    ///
+    /// Returns current value
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+}
+//
+//
+impl Default for Gauge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,81 @@
+use std::{sync::{atomic::{AtomicU64, Ordering}, Arc}, time::Duration};
+///
+/// Shared state of the [Histogram]
+#[derive(Debug)]
+struct HistogramState {
+    bounds: Vec<f64>,
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    sum: AtomicU64,
+}
+///
+/// Distribution of the observed values by the buckets, cheap to clone, all clones share the same state
+/// - `bounds` - upper inclusive bounds of the buckets, `+Inf` bucket is implicit
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    state: Arc<HistogramState>,
+}
+//
+//
+impl Histogram {
+    ///
+    /// Default bounds of the buckets, in seconds, from 10 us to 10 s
+    pub const DEFAULT_BOUNDS: &'static [f64] = &[0.00001, 0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 10.0];
+    ///
+    /// Returns [Histogram] new instance, not registered in the [Metrics](super::Metrics)
+    /// - `bounds` - sorted and deduplicated
+    pub fn new(bounds: &[f64]) -> Self {
+        let mut bounds: Vec<f64> = bounds.iter().copied().filter(|bound| bound.is_finite()).collect();
+        bounds.sort_by(|a, b| a.total_cmp(b));
+        bounds.dedup();
+        Self {
+            state: Arc::new(HistogramState {
+                buckets: bounds.iter().map(|_| AtomicU64::new(0)).collect(),
+                bounds,
+                count: AtomicU64::new(0),
+                sum: AtomicU64::new(0f64.to_bits()),
+            }),
+        }
+    }
+    ///
+    /// Records the `value`
+    pub fn observe(&self, value: f64) {
+        let state = &self.state;
+        if let Some(index) = state.bounds.iter().position(|bound| value <= *bound) {
+            state.buckets[index].fetch_add(1, Ordering::Relaxed);
+        }
+        state.count.fetch_add(1, Ordering::Relaxed);
+        let mut current = state.sum.load(Ordering::Relaxed);
+        loop {
+            let next = (f64::from_bits(current) + value).to_bits();
+            match state.sum.compare_exchange_weak(current, next, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => break,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+    ///
+    /// Records the `duration` in seconds
+    pub fn observe_duration(&self, duration: Duration) {
+        self.observe(duration.as_secs_f64());
+    }
+    ///
+    /// Returns the bounds of the buckets with the cumulative number of the values, `+Inf` bucket is not included
+    pub fn buckets(&self) -> Vec<(f64, u64)> {
+        let mut cumulative = 0;
+        self.state.bounds.iter().zip(self.state.buckets.iter()).map(|(bound, count)| {
+            cumulative += count.load(Ordering::Relaxed);
+            (*bound, cumulative)
+        }).collect()
+    }
+    ///
+    /// Returns the number of the observed values
+    pub fn count(&self) -> u64 {
+        self.state.count.load(Ordering::Relaxed)
+    }
+    ///
+    /// Returns the sum of the observed values
+    pub fn sum(&self) -> f64 {
+        f64::from_bits(self.state.sum.load(Ordering::Relaxed))
+    }
+}
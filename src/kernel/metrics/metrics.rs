@@ -0,0 +1,237 @@
+use std::{collections::BTreeMap, fmt::Write, sync::{Arc, OnceLock}};
+use crate::sync::Mutex;
+use super::{Counter, Gauge, Histogram};
+///
+/// Value of the gauge calculated on the each render, `None` if the source is gone, then the series is removed
+pub type GaugeFn = Arc<dyn Fn() -> Option<f64> + Send + Sync>;
+///
+/// Kind of the metric family
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+//
+//
+impl MetricKind {
+    ///
+    /// Returns the name of the kind in the Prometheus text format
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Histogram => "histogram",
+        }
+    }
+}
+///
+/// Single labeled series of the metric family
+#[derive(Clone)]
+enum Series {
+    Counter(Counter),
+    Gauge(Gauge),
+    GaugeFn(GaugeFn),
+    Histogram(Histogram),
+}
+//
+//
+impl Series {
+    fn kind(&self) -> MetricKind {
+        match self {
+            Series::Counter(_) => MetricKind::Counter,
+            Series::Gauge(_) | Series::GaugeFn(_) => MetricKind::Gauge,
+            Series::Histogram(_) => MetricKind::Histogram,
+        }
+    }
+}
+///
+/// All series of the metric with the same name
+struct Family {
+    help: String,
+    kind: MetricKind,
+    series: BTreeMap<String, Series>,
+}
+///
+/// ### Registry of the counters, gauges and histograms
+/// - Metric is identified by the name and the labels, registering of the existing one returns it
+/// - Returned handles are cheap to update, registry is locked on registering and rendering only
+/// - Rendered in the Prometheus text exposition format, served by the `MetricsService`
+/// - Components of the crate report into the `Metrics::global()`
+///
+/// **Example**
+/// ```ignore
+/// let points = Metrics::global().counter("sal_points_total", "Points received", &[("service", "/App/Task")]);
+/// points.inc();
+/// let text = Metrics::global().render();
+/// ```
+pub struct Metrics {
+    families: Mutex<BTreeMap<String, Family>>,
+}
+//
+//
+impl Metrics {
+    ///
+    /// Returns [Metrics] new empty instance
+    pub fn new() -> Self {
+        Self { families: Mutex::new(BTreeMap::new()) }
+    }
+    ///
+    /// Returns the registry of the application
+    pub fn global() -> &'static Metrics {
+        static GLOBAL: OnceLock<Metrics> = OnceLock::new();
+        GLOBAL.get_or_init(Metrics::new)
+    }
+    ///
+    /// Returns the registered [Counter], registers new if not exists
+    pub fn counter(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Counter {
+        match self.register(name, help, labels, MetricKind::Counter, || Series::Counter(Counter::new())) {
+            Some(Series::Counter(counter)) => counter,
+            _ => Counter::new(),
+        }
+    }
+    ///
+    /// Returns the registered [Gauge], registers new if not exists
+    pub fn gauge(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Gauge {
+        match self.register(name, help, labels, MetricKind::Gauge, || Series::Gauge(Gauge::new())) {
+            Some(Series::Gauge(gauge)) => gauge,
+            _ => Gauge::new(),
+        }
+    }
+    ///
+    /// Registers the gauge calculated by the `value` on the each render, replaces existing one
+    /// - `value` returning `None` removes the series, usually it holds a `Weak` reference to the source
+    pub fn gauge_fn(&self, name: &str, help: &str, labels: &[(&str, &str)], value: impl Fn() -> Option<f64> + Send + Sync + 'static) {
+        let value: GaugeFn = Arc::new(value);
+        self.remove(name, labels);
+        self.register(name, help, labels, MetricKind::Gauge, move || Series::GaugeFn(value));
+    }
+    ///
+    /// Returns the registered [Histogram], registers new with the `bounds` if not exists
+    pub fn histogram(&self, name: &str, help: &str, labels: &[(&str, &str)], bounds: &[f64]) -> Histogram {
+        match self.register(name, help, labels, MetricKind::Histogram, || Series::Histogram(Histogram::new(bounds))) {
+            Some(Series::Histogram(histogram)) => histogram,
+            _ => Histogram::new(bounds),
+        }
+    }
+    ///
+    /// Removes the series, the family is removed with the last series
+    pub fn remove(&self, name: &str, labels: &[(&str, &str)]) {
+        let mut families = self.families.lock();
+        if let Some(family) = families.get_mut(name) {
+            family.series.remove(&Self::labels(labels));
+            if family.series.is_empty() {
+                families.remove(name);
+            }
+        }
+    }
+    ///
+    /// Returns all metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let families: Vec<(String, String, MetricKind, Vec<(String, Series)>)> = self.families.lock().iter().map(|(name, family)| {
+            (name.clone(), family.help.clone(), family.kind, family.series.iter().map(|(labels, series)| (labels.clone(), series.clone())).collect())
+        }).collect();
+        let mut text = String::new();
+        let mut gone = vec![];
+        for (name, help, kind, series) in families {
+            let _ = writeln!(text, "# HELP {} {}", name, help.replace('\\', "\\\\").replace('\n', "\\n"));
+            let _ = writeln!(text, "# TYPE {} {}", name, kind.as_str());
+            for (labels, series) in series {
+                match series {
+                    Series::Counter(counter) => Self::line(&mut text, &name, "", &labels, None, counter.get() as f64),
+                    Series::Gauge(gauge) => Self::line(&mut text, &name, "", &labels, None, gauge.get()),
+                    Series::GaugeFn(value) => match value() {
+                        Some(value) => Self::line(&mut text, &name, "", &labels, None, value),
+                        None => gone.push((name.clone(), labels)),
+                    },
+                    Series::Histogram(histogram) => {
+                        for (bound, count) in histogram.buckets() {
+                            Self::line(&mut text, &name, "_bucket", &labels, Some(&Self::value(bound)), count as f64);
+                        }
+                        Self::line(&mut text, &name, "_bucket", &labels, Some("+Inf"), histogram.count() as f64);
+                        Self::line(&mut text, &name, "_sum", &labels, None, histogram.sum());
+                        Self::line(&mut text, &name, "_count", &labels, None, histogram.count() as f64);
+                    }
+                }
+            }
+        }
+        if !gone.is_empty() {
+            let mut families = self.families.lock();
+            for (name, labels) in gone {
+                if let Some(family) = families.get_mut(&name) {
+                    if matches!(family.series.get(&labels), Some(Series::GaugeFn(_))) {
+                        family.series.remove(&labels);
+                    }
+                    if family.series.is_empty() {
+                        families.remove(&name);
+                    }
+                }
+            }
+        }
+        text
+    }
+    ///
+    /// Returns the existing series or registers the new one,
+    /// `None` if the name is registered with another kind
+    fn register(&self, name: &str, help: &str, labels: &[(&str, &str)], kind: MetricKind, series: impl FnOnce() -> Series) -> Option<Series> {
+        let mut families = self.families.lock();
+        let family = families.entry(name.to_owned()).or_insert_with(|| Family { help: help.to_owned(), kind, series: BTreeMap::new() });
+        if family.kind != kind {
+            log::error!("Metrics.register | '{}' is registered as {}, but requested as {}", name, family.kind.as_str(), kind.as_str());
+            return None;
+        }
+        let series = family.series.entry(Self::labels(labels)).or_insert_with(series);
+        match series.kind() == kind {
+            true => Some(series.clone()),
+            false => None,
+        }
+    }
+    ///
+    /// Returns the labels in the Prometheus text format, sorted by the name
+    fn labels(labels: &[(&str, &str)]) -> String {
+        let mut labels = labels.to_vec();
+        labels.sort_by(|a, b| a.0.cmp(b.0));
+        labels.iter()
+            .map(|(name, value)| format!("{}=\"{}\"", name, value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+    ///
+    /// Returns the value in the Prometheus text format
+    fn value(value: f64) -> String {
+        match value {
+            v if v.is_nan() => "NaN".to_owned(),
+            v if v == f64::INFINITY => "+Inf".to_owned(),
+            v if v == f64::NEG_INFINITY => "-Inf".to_owned(),
+            v => v.to_string(),
+        }
+    }
+    ///
+    /// Appends the sample line
+    fn line(text: &mut String, name: &str, suffix: &str, labels: &str, le: Option<&str>, value: f64) {
+        let labels = match (labels.is_empty(), le) {
+            (true, None) => String::new(),
+            (false, None) => format!("{{{}}}", labels),
+            (true, Some(le)) => format!("{{le=\"{}\"}}", le),
+            (false, Some(le)) => format!("{{{},le=\"{}\"}}", labels, le),
+        };
+        let _ = writeln!(text, "{}{}{} {}", name, suffix, labels, Self::value(value));
+    }
+}
+//
+//
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+//
+//
+impl std::fmt::Debug for Metrics {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("Metrics")
+            .field("families", &self.families.lock().len())
+            .finish()
+    }
+}
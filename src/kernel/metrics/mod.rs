@@ -0,0 +1,9 @@
+mod counter;
+mod gauge;
+mod histogram;
+mod metrics;
+
+pub use counter::*;
+pub use gauge::*;
+pub use histogram::*;
+pub use metrics::*;
@@ -1 +1,4 @@
-pub mod state;
\ No newline at end of file
+pub mod clock;
+pub mod metrics;
+pub mod realtime;
+pub mod state;
@@ -0,0 +1,3 @@
+mod real_time;
+
+pub use real_time::*;
@@ -0,0 +1,71 @@
+use sal_core::error::Error;
+///
+/// ### Real-time scheduling of the calling thread
+/// - `fifo` - requests `SCHED_FIFO` policy with the priority
+/// - `pin` - pins the thread to the CPU core
+///
+/// Supported on Linux only, requires `CAP_SYS_NICE` or the `rtprio` limit for `SCHED_FIFO`,
+/// if not permitted the error is returned and the thread keeps its current scheduling
+///
+/// **Example**
+/// ```ignore
+/// // Inside the service thread, before the cyclic loop
+/// if let Err(err) = RealTime::fifo(80) {
+///     log::warn!("{}.run | {}", dbg, err);
+/// }
+/// RealTime::pin(3)?;
+/// let mut cycle = ServiceCycle::new(&dbg, Duration::from_micros(250)).with_spin(Duration::from_micros(100));
+/// ```
+pub struct RealTime;
+//
+//
+impl RealTime {
+    ///
+    /// Sets the `SCHED_FIFO` scheduling policy with the `priority` (1..=99) for the calling thread
+    #[cfg(target_os = "linux")]
+    pub fn fifo(priority: i32) -> Result<(), Error> {
+        let error = Error::new("RealTime", "fifo");
+        let (min, max) = unsafe { (libc::sched_get_priority_min(libc::SCHED_FIFO), libc::sched_get_priority_max(libc::SCHED_FIFO)) };
+        if priority < min || priority > max {
+            return Err(error.err(format!("Priority {} out of range {}..={}", priority, min, max)));
+        }
+        let param = libc::sched_param { sched_priority: priority };
+        // SAFETY: `param` is valid for the call, `pthread_self` is always valid for the calling thread
+        let result = unsafe { libc::pthread_setschedparam(libc::pthread_self(), libc::SCHED_FIFO, &param) };
+        match result {
+            0 => Ok(()),
+            code => Err(error.err(format!("SCHED_FIFO({}) - not permitted: {}", priority, std::io::Error::from_raw_os_error(code)))),
+        }
+    }
+    ///
+    /// Sets the `SCHED_FIFO` scheduling policy, not supported on this platform
+    #[cfg(not(target_os = "linux"))]
+    pub fn fifo(priority: i32) -> Result<(), Error> {
+        Err(Error::new("RealTime", "fifo").err(format!("SCHED_FIFO({}) - not supported on this platform", priority)))
+    }
+    ///
+    /// Pins the calling thread to the `cpu` core
+    #[cfg(target_os = "linux")]
+    pub fn pin(cpu: usize) -> Result<(), Error> {
+        let error = Error::new("RealTime", "pin");
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return Err(error.err(format!("CPU {} out of range 0..{}", cpu, libc::CPU_SETSIZE)));
+        }
+        // SAFETY: `set` is zero-initialized plain data, valid for the calls
+        let result = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            libc::CPU_SET(cpu, &mut set);
+            libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+        };
+        match result {
+            0 => Ok(()),
+            _ => Err(error.err(format!("CPU {} - not permitted: {}", cpu, std::io::Error::last_os_error()))),
+        }
+    }
+    ///
+    /// Pins the calling thread to the CPU core, not supported on this platform
+    #[cfg(not(target_os = "linux"))]
+    pub fn pin(cpu: usize) -> Result<(), Error> {
+        Err(Error::new("RealTime", "pin").err(format!("CPU {} - not supported on this platform", cpu)))
+    }
+}
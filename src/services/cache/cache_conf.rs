@@ -0,0 +1,49 @@
+use std::time::Duration;
+use sal_core::{dbg::Dbg, error::Error};
+use crate::services::{
+    conf::{ConfTree, ConfTreeGet},
+    entity::Name,
+    subscription::ConfSubscribe,
+};
+///
+/// Configuration of the `CacheService`
+///
+/// ```yaml
+/// service CacheService Cache:
+///     subscribe: MultiQueue   # points to be cached, see [ConfSubscribe]
+///     retain: true            # optional, store cached points into the retain directory, default false
+///     retain-cycle: 1 s       # optional, interval of the storing changed points, default 1 s
+/// ```
+#[derive(Debug, Clone)]
+pub struct CacheConf {
+    pub(crate) name: Name,
+    pub(crate) subscribe: ConfSubscribe,
+    pub(crate) retain: bool,
+    pub(crate) retain_cycle: Duration,
+}
+//
+//
+impl CacheConf {
+    const DEFAULT_RETAIN_CYCLE: Duration = Duration::from_secs(1);
+    ///
+    /// Returns [CacheConf] parsed from the service config node
+    /// - `parent` - the name of the application
+    pub fn new(parent: impl Into<String>, conf: ConfTree) -> Result<Self, Error> {
+        let me = conf.sufix_or(conf.name()?);
+        let name = Name::new(parent, &me);
+        let dbg = Dbg::new(name.parent(), format!("CacheConf({})", me));
+        let error = Error::new(&dbg, "new");
+        log::trace!("{}.new | conf: {:?}", dbg, conf);
+        let subscribe = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "subscribe") {
+            Some(subscribe) => ConfSubscribe::new(subscribe),
+            None => return Err(error.err("Parameter 'subscribe' - not found")),
+        };
+        let retain = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "retain") {
+            Some(_) => conf.as_bool("retain").map_err(|err| error.pass_with("Parameter 'retain' - Bool expected", err.to_string()))?,
+            None => false,
+        };
+        let retain_cycle = conf.get_duration("retain-cycle").unwrap_or(Self::DEFAULT_RETAIN_CYCLE);
+        log::debug!("{}.new | subscribe: {:?}, retain: {}, retain-cycle: {:?}", dbg, subscribe, retain, retain_cycle);
+        Ok(Self { name, subscribe, retain, retain_cycle })
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    path::PathBuf,
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
+    time::{Duration, Instant},
+};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    services::{
+        entity::{Cot, Name, Object, Point, Status},
+        future::Future,
+        service::{Service, ServiceError},
+        services::Services,
+        subscription::{ServiceSubscription, SubscriptionCriteria},
+    },
+    sync::Handles,
+    thread_pool::Scheduler,
+};
+use super::{cache_conf::CacheConf, point_cache::PointCache};
+///
+/// ### Keeps the latest value of the each point
+/// - Points received by `subscribe` are stored per name and [Cot]
+/// - Answers `gi` requests with the stored points, without waiting for the next change
+/// - Stored points can be queried by the exact name, by the name prefix and by the status
+/// - If `retain` is enabled, stored points are written into `<retain path>/cache/<App.Service>.json`
+///   every `retain-cycle` if changed and on exit, and loaded back on the next start
+pub struct CacheService {
+    dbg: Dbg,
+    name: Name,
+    conf: CacheConf,
+    cache: Arc<PointCache>,
+    retain_path: Option<PathBuf>,
+    services: Arc<Services>,
+    scheduler: Option<Scheduler>,
+    handles: Handles<()>,
+    exit: Arc<AtomicBool>,
+}
+//
+//
+impl CacheService {
+    const CYCLE: Duration = Duration::from_millis(10);
+    ///
+    /// Returns [CacheService] new instance
+    /// - Retained points are loaded immediately, so they are available before the service is started
+    pub fn new(conf: CacheConf, services: Arc<Services>, scheduler: Option<Scheduler>) -> Self {
+        let dbg = Dbg::new(conf.name.parent(), conf.name.me());
+        let cache = Arc::new(PointCache::new(&dbg));
+        let retain_path = match (conf.retain, services.retain().path) {
+            (true, Some(path)) => {
+                let file = format!("{}.json", conf.name.join().trim_start_matches('/').replace('/', "."));
+                Some(path.join("cache").join(file))
+            }
+            (true, None) => {
+                log::warn!("{}.new | 'retain' enabled, but retain path is not specified in the application config", dbg);
+                None
+            }
+            (false, _) => None,
+        };
+        if let Some(path) = &retain_path {
+            if let Err(err) = cache.load(path) {
+                log::warn!("{}.new | Retained points are not loaded: {}", dbg, err);
+            }
+        }
+        Self {
+            name: conf.name.clone(),
+            conf,
+            cache,
+            retain_path,
+            services,
+            scheduler,
+            handles: Handles::new(&dbg),
+            exit: Arc::new(AtomicBool::new(false)),
+            dbg,
+        }
+    }
+    ///
+    /// Returns the latest point with the `name` and the `cot`
+    pub fn get(&self, name: &str, cot: Cot) -> Option<Point> {
+        self.cache.get(name, cot)
+    }
+    ///
+    /// Returns the latest points with the exact `name`, all [Cot]'s
+    pub fn by_name(&self, name: &str) -> Vec<Point> {
+        self.cache.name(name)
+    }
+    ///
+    /// Returns the latest points having the name starting with the `prefix`
+    pub fn by_prefix(&self, prefix: &str) -> Vec<Point> {
+        self.cache.prefix(prefix)
+    }
+    ///
+    /// Returns the latest points having the `status`
+    pub fn by_status(&self, status: Status) -> Vec<Point> {
+        self.cache.status(status)
+    }
+    ///
+    /// Returns the number of the stored points
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+    ///
+    /// Returns `true` if nothing stored
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+    ///
+    /// Main loop
+    fn run_(dbg: Dbg, cache: Arc<PointCache>, subscription: ServiceSubscription, retain_path: Option<PathBuf>, retain_cycle: Duration, exit: Arc<AtomicBool>) {
+        log::info!("{}.run | Preparing thread - ok", dbg);
+        let mut changed = false;
+        let mut stored = Instant::now();
+        let store = |changed: &mut bool| {
+            if let Some(path) = &retain_path {
+                match cache.store(path) {
+                    Ok(_) => *changed = false,
+                    Err(err) => log::warn!("{}.run | {}", dbg, err),
+                }
+            }
+        };
+        loop {
+            if let Some(point) = subscription.recv(Self::CYCLE) {
+                log::trace!("{}.run | caching: {:?}", dbg, point);
+                cache.insert(point);
+                changed = true;
+            }
+            if changed && stored.elapsed() >= retain_cycle {
+                store(&mut changed);
+                stored = Instant::now();
+            }
+            if exit.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+        if changed {
+            store(&mut changed);
+        }
+        subscription.unsubscribe();
+        log::info!("{}.run | Exit", dbg);
+    }
+}
+//
+//
+impl Object for CacheService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+//
+impl std::fmt::Debug for CacheService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("CacheService")
+            .field("id", &self.dbg)
+            .field("len", &self.cache.len())
+            .finish()
+    }
+}
+//
+//
+impl Service for CacheService {
+    //
+    //
+    fn subscribed_to(&self) -> Vec<String> {
+        ServiceSubscription::services(&self.name, &self.conf.subscribe)
+    }
+    //
+    //
+    fn run(&self) -> Result<(), Error> {
+        log::info!("{}.run | Starting...", self.dbg);
+        let error = Error::new(&self.dbg, "run");
+        let dbg = self.dbg.clone();
+        let cache = self.cache.clone();
+        let subscription = ServiceSubscription::new(&self.name, &self.conf.subscribe, self.services.clone())
+            .map_err(|err| error.pass(err))?;
+        let retain_path = self.retain_path.clone();
+        let retain_cycle = self.conf.retain_cycle;
+        let exit = self.exit.clone();
+        match &self.scheduler {
+            Some(scheduler) => {
+                let handle = scheduler.spawn(move || {
+                    Self::run_(dbg, cache, subscription, retain_path, retain_cycle, exit);
+                    Ok(())
+                }).map_err(|err| error.pass_with("Start failed on Scheduler", err.to_string()))?;
+                self.handles.push(handle);
+            }
+            None => {
+                let handle = std::thread::Builder::new().name(format!("{}.run", dbg)).spawn(move || {
+                    Self::run_(dbg, cache, subscription, retain_path, retain_cycle, exit);
+                }).map_err(|err| error.pass_with("Start failed on std::thread", err.to_string()))?;
+                self.handles.push(handle);
+            }
+        };
+        log::info!("{}.run | Started", self.dbg);
+        Ok(())
+    }
+    //
+    //
+    fn try_gi(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<Future<Vec<Point>>, ServiceError> {
+        log::debug!("{}.gi | Requested by '{}', points: {}", self.dbg, receiver_name, points.len());
+        let (future, sink) = Future::new();
+        sink.add(self.cache.gi(points));
+        Ok(future)
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::SeqCst);
+    }
+}
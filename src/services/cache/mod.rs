@@ -0,0 +1,22 @@
+//!
+//! # Last-value store of the points
+//!
+//! - `CacheService` - keeps the latest point per name and Cot,
+//!   answers `gi` requests and queries by name, prefix and status
+//! - `PointCache` - thread safe store used by the `CacheService`
+//!
+//! ## Configuration example
+//!
+//! ```yaml
+//! service CacheService Cache:
+//!     subscribe: MultiQueue
+//!     retain: true
+//!     retain-cycle: 1 s
+//! ```
+mod cache_conf;
+mod cache_service;
+mod point_cache;
+
+pub use cache_conf::*;
+pub use cache_service::*;
+pub use point_cache::*;
@@ -0,0 +1,125 @@
+use std::{fs, path::{Path, PathBuf}};
+use dashmap::DashMap;
+use sal_core::{dbg::Dbg, error::Error};
+use crate::services::{
+    entity::{Cot, Point, Status},
+    subscription::SubscriptionCriteria,
+};
+///
+/// Thread safe store of the latest [Point] per name and [Cot]
+/// - Keyed by the subscription destination `Cot:point name`
+/// - Query results are sorted by the point name, then by the [Cot]
+#[derive(Debug)]
+pub struct PointCache {
+    dbg: Dbg,
+    points: DashMap<String, Point>,
+}
+//
+//
+impl PointCache {
+    ///
+    /// Returns [PointCache] new empty instance
+    pub fn new(parent: impl Into<String>) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "PointCache"),
+            points: DashMap::new(),
+        }
+    }
+    ///
+    /// Stores the `point`, replacing previous one with the same name and [Cot]
+    pub fn insert(&self, point: Point) {
+        self.points.insert(SubscriptionCriteria::dest(&point.cot(), &point.name()), point);
+    }
+    ///
+    /// Returns the latest point with the `name` and the `cot`
+    pub fn get(&self, name: &str, cot: Cot) -> Option<Point> {
+        self.points.get(&SubscriptionCriteria::dest(&cot, name)).map(|point| point.value().clone())
+    }
+    ///
+    /// Returns the latest points with the exact `name`, all [Cot]'s
+    pub fn name(&self, name: &str) -> Vec<Point> {
+        self.filter(|point| point.name() == name)
+    }
+    ///
+    /// Returns the latest points having the name starting with the `prefix`
+    pub fn prefix(&self, prefix: &str) -> Vec<Point> {
+        self.filter(|point| point.name().starts_with(prefix))
+    }
+    ///
+    /// Returns the latest points having the `status`
+    pub fn status(&self, status: Status) -> Vec<Point> {
+        self.filter(|point| point.status() == status)
+    }
+    ///
+    /// Returns the latest points matching any of the `points` criterias, or all points if `points` is empty
+    /// - `Cot::All` in the criteria matches the point with any [Cot]
+    pub fn gi(&self, points: &[SubscriptionCriteria]) -> Vec<Point> {
+        if points.is_empty() {
+            return self.all();
+        }
+        self.filter(|point| points.iter().any(|criteria| {
+            criteria.name() == point.name() && (criteria.cot() == Cot::All || criteria.cot() == point.cot())
+        }))
+    }
+    ///
+    /// Returns all stored points
+    pub fn all(&self) -> Vec<Point> {
+        self.filter(|_| true)
+    }
+    ///
+    /// Returns the number of stored points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+    ///
+    /// Returns `true` if nothing stored
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+    ///
+    /// Writes all stored points into the json file at `path`
+    /// - The file is replaced atomically, so it stays consistent if the application is stopped while writing
+    pub fn store(&self, path: &Path) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "store");
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| error.pass_with(format!("Create dir '{}' error", dir.display()), err.to_string()))?;
+        }
+        let json = serde_json::to_vec_pretty(&self.all()).map_err(|err| error.pass_with("Serialize error", err.to_string()))?;
+        let mut tmp = PathBuf::from(path);
+        tmp.set_extension("tmp");
+        fs::write(&tmp, json).map_err(|err| error.pass_with(format!("Write '{}' error", tmp.display()), err.to_string()))?;
+        fs::rename(&tmp, path).map_err(|err| error.pass_with(format!("Rename '{}' error", tmp.display()), err.to_string()))?;
+        log::debug!("{}.store | {} points stored into '{}'", self.dbg, self.len(), path.display());
+        Ok(())
+    }
+    ///
+    /// Reads points from the json file at `path`, stored by [PointCache::store]
+    /// - Returns the number of loaded points, 0 if the file does not exists
+    pub fn load(&self, path: &Path) -> Result<usize, Error> {
+        let error = Error::new(&self.dbg, "load");
+        if !path.exists() {
+            log::debug!("{}.load | File '{}' not found, nothing to load", self.dbg, path.display());
+            return Ok(0);
+        }
+        let json = fs::read(path).map_err(|err| error.pass_with(format!("Read '{}' error", path.display()), err.to_string()))?;
+        let points: Vec<Point> = serde_json::from_slice(&json)
+            .map_err(|err| error.pass_with(format!("Deserialize '{}' error", path.display()), err.to_string()))?;
+        let len = points.len();
+        for point in points {
+            self.insert(point);
+        }
+        log::debug!("{}.load | {} points loaded from '{}'", self.dbg, len, path.display());
+        Ok(len)
+    }
+    ///
+    /// Returns sorted points matching the `filter`
+    fn filter(&self, filter: impl Fn(&Point) -> bool) -> Vec<Point> {
+        let mut points: Vec<(String, Point)> = self.points
+            .iter()
+            .filter(|entry| filter(entry.value()))
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        points.sort_by(|(a_key, a), (b_key, b)| a.name().cmp(&b.name()).then_with(|| a_key.cmp(b_key)));
+        points.into_iter().map(|(_, point)| point).collect()
+    }
+}
@@ -70,15 +70,15 @@ impl ConfDuration {
         }
     }
     ///
-    /// 
+    /// Returns the [Duration] of the value in the unit, saturated on overflow
     pub fn to_duration(&self) -> Duration {
         match self.unit {
             ConfDurationUnit::Nanos => Duration::from_nanos(self.value),
             ConfDurationUnit::Micros => Duration::from_micros(self.value),
             ConfDurationUnit::Millis => Duration::from_millis(self.value),
             ConfDurationUnit::Secs => Duration::from_secs(self.value),
-            ConfDurationUnit::Mins => Duration::from_secs(self.value),
-            ConfDurationUnit::Hours => Duration::from_secs(self.value),
+            ConfDurationUnit::Mins => Duration::from_secs(self.value.saturating_mul(60)),
+            ConfDurationUnit::Hours => Duration::from_secs(self.value.saturating_mul(3600)),
         }
     }
 }
@@ -0,0 +1,110 @@
+use std::{str::FromStr, time::Duration};
+use serde_yaml::Value;
+use crate::services::service::LinkName;
+use super::{conf_duration::ConfDuration, conf_schema_type::ConfSchemaType};
+///
+/// The value of the config parameter, read by the [ConfReader](super::ConfReader)
+/// - Returns the reason, the parameter key is added by the reader
+pub trait ConfField: Sized {
+    ///
+    /// Returns the value parsed from the yaml `value`
+    fn from_value(value: &Value) -> Result<Self, String>;
+}
+///
+/// Returns the message about the unexpected `value`
+fn expected(type_: &str, value: &Value) -> String {
+    format!("{} expected, but found: {}", type_, ConfSchemaType::found(value))
+}
+//
+//
+impl ConfField for Value {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        Ok(value.clone())
+    }
+}
+//
+//
+impl ConfField for String {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        value.as_str().map(|value| value.to_owned()).ok_or_else(|| expected("String", value))
+    }
+}
+//
+//
+impl ConfField for bool {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        value.as_bool().ok_or_else(|| expected("Bool", value))
+    }
+}
+//
+//
+impl ConfField for i64 {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        value.as_i64().ok_or_else(|| expected("Int", value))
+    }
+}
+//
+//
+impl ConfField for u64 {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        value.as_u64().ok_or_else(|| expected("positive Int", value))
+    }
+}
+//
+//
+impl ConfField for usize {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        value.as_u64().and_then(|value| usize::try_from(value).ok()).ok_or_else(|| expected("positive Int", value))
+    }
+}
+//
+//
+impl ConfField for f64 {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        value.as_f64().ok_or_else(|| expected("Real", value))
+    }
+}
+//
+//
+impl ConfField for ConfDuration {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        let duration = match value {
+            Value::Number(number) if number.is_u64() => number.to_string(),
+            Value::String(duration) => duration.clone(),
+            _ => return Err(expected("Duration", value)),
+        };
+        ConfDuration::from_str(&duration).map_err(|_| expected("Duration (like '100 ms', '1 s')", value))
+    }
+}
+//
+//
+impl ConfField for Duration {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        ConfDuration::from_value(value).map(|duration| duration.to_duration())
+    }
+}
+//
+//
+impl ConfField for LinkName {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value.as_str() {
+            Some(link) => LinkName::from_str(link).map_err(|_| format!("'Service.link' expected, but found: '{}'", link)),
+            None => Err(expected("'Service.link'", value)),
+        }
+    }
+}
+//
+//
+impl<T: ConfField> ConfField for Vec<T> {
+    ///
+    /// The single item is accepted as the list of one item
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Sequence(items) => items.iter().enumerate()
+                .map(|(index, item)| T::from_value(item).map_err(|err| format!("item {}: {}", index, err)))
+                .collect(),
+            Value::Null => Ok(vec![]),
+            value => T::from_value(value).map(|item| vec![item]),
+        }
+    }
+}
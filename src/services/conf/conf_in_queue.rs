@@ -0,0 +1,12 @@
+///
+/// The `in queue` of the service
+///
+/// ```yaml
+/// in queue in-queue:
+///     max-length: 10000
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfInQueue {
+    pub name: String,
+    pub max_length: i64,
+}
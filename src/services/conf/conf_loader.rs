@@ -0,0 +1,259 @@
+use std::{collections::HashMap, fs, path::{Path, PathBuf}};
+use sal_core::{dbg::Dbg, error::Error};
+use serde_yaml::{Mapping, Value};
+use crate::collections::FxIndexMap;
+use super::{
+    conf_path::ConfPath, conf_positions::ConfPositions, conf_sources::ConfSources,
+    conf_templates::ConfTemplates, conf_tree::ConfTree,
+};
+///
+/// ### Loads the configuration from the multiple yaml files
+/// - `include: path.yaml` / `include: [a.yaml, b.yaml]` - nodes of the included files are inserted
+///   into the mapping containing `include`, paths are relative to the including file,
+///   includes are allowed on any level, cyclic and duplicated keys are errors
+/// - `${NAME}` / `${NAME:-default}` inside string scalars (keys, values, include paths)
+///   are expanded from the environment, variables passed by `with_vars` take precedence,
+///   `$${` is a literal `${`
+/// - The scalar consisting of the single `${...}` is parsed as yaml after the expansion,
+///   so `port: ${PORT:-8080}` is a number
+/// - Templates are expanded after all files are loaded, see [ConfTemplates]
+/// - Source file of the each node and the position of its key are recorded in the [ConfSources]
+///
+/// **Example**
+/// ```yaml
+/// # app.yaml
+/// name: ${APP_NAME:-App}
+/// include:
+///     - services/multi-queue.yaml
+///     - devices/ied01.yaml
+/// ```
+/// ```ignore
+/// let (conf, sources) = ConfLoader::new(&dbg).load("app.yaml")?;
+/// let services = ServiceRegistry::new(&dbg).load(&conf, &services, None)?;
+/// ```
+pub struct ConfLoader {
+    dbg: Dbg,
+    vars: FxIndexMap<String, String>,
+}
+//
+//
+impl ConfLoader {
+    ///
+    /// The key of the include directive
+    pub const INCLUDE: &'static str = "include";
+    ///
+    /// Returns [ConfLoader] new instance
+    pub fn new(parent: impl Into<String>) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "ConfLoader"),
+            vars: FxIndexMap::default(),
+        }
+    }
+    ///
+    /// Returns [ConfLoader] expanding the `vars` before the environment variables
+    pub fn with_vars(mut self, vars: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>) -> Self {
+        self.vars.extend(vars.into_iter().map(|(name, value)| (name.into(), value.into())));
+        self
+    }
+    ///
+    /// Returns the root [ConfTree] loaded from the `path` with all includes resolved
+    /// and templates expanded, and the source files of the nodes
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<(ConfTree, ConfSources), Error> {
+        let error = Error::new(&self.dbg, "load");
+        let mut sources = ConfSources::new();
+        let mut stack = vec![];
+        let conf = self.load_file(path.as_ref(), "", &mut stack, &mut sources).map_err(|err| error.pass(err))?;
+        let conf = ConfTemplates::new(&self.dbg).expand_value(conf, &mut sources).map_err(|err| error.pass(err))?;
+        log::debug!("{}.load | Loaded files: {:?}", self.dbg, sources.files());
+        Ok((ConfTree::new_root(conf), sources))
+    }
+    ///
+    /// Returns the content of the file with all includes resolved
+    /// - `path` - the key path of the node the file is included into
+    fn load_file(&self, file: &Path, path: &str, stack: &mut Vec<PathBuf>, sources: &mut ConfSources) -> Result<Value, Error> {
+        let error = Error::new(&self.dbg, "load_file");
+        let file = fs::canonicalize(file)
+            .map_err(|err| error.pass_with(format!("File '{}' - not found", file.display()), err.to_string()))?;
+        if stack.contains(&file) {
+            let cycle: Vec<String> = stack.iter().chain([&file]).map(|file| file.display().to_string()).collect();
+            return Err(error.err(format!("Include cycle: {}", cycle.join(" -> "))));
+        }
+        log::trace!("{}.load_file | Loading '{}'...", self.dbg, file.display());
+        let text = fs::read_to_string(&file)
+            .map_err(|err| error.pass_with(format!("File '{}' - read error", file.display()), err.to_string()))?;
+        let conf: Value = serde_yaml::from_str(&text)
+            .map_err(|err| error.pass_with(format!("File '{}' - parse error", file.display()), err.to_string()))?;
+        if stack.is_empty() {
+            sources.insert(path, &file);
+        }
+        let source = Source { file: &file, positions: ConfPositions::new(&text) };
+        stack.push(file.clone());
+        let conf = self.resolve(conf, &source, path, "", stack, sources);
+        stack.pop();
+        conf
+    }
+    ///
+    /// Returns the node with includes resolved and variables expanded
+    /// - `raw` - the key path of the node within the `source` file, variables not expanded
+    fn resolve(&self, conf: Value, source: &Source, path: &str, raw: &str, stack: &mut Vec<PathBuf>, sources: &mut ConfSources) -> Result<Value, Error> {
+        let error = Error::new(&self.dbg, "resolve");
+        let file = source.file;
+        match conf {
+            Value::Mapping(mapping) => {
+                let mut resolved = Mapping::new();
+                let mut origins: HashMap<Value, PathBuf> = HashMap::new();
+                for (key, value) in mapping {
+                    if key.as_str() == Some(Self::INCLUDE) {
+                        for include in self.includes(value, file, path)? {
+                            let include = file.parent().unwrap_or(Path::new("")).join(include);
+                            match self.load_file(&include, path, stack, sources)? {
+                                Value::Mapping(included) => {
+                                    let include = fs::canonicalize(&include).unwrap_or(include);
+                                    for (key, value) in included {
+                                        Self::insert(&error, &mut resolved, &mut origins, key, value, &include, path)?;
+                                    }
+                                }
+                                Value::Null => log::debug!("{}.resolve | Empty include '{}'", self.dbg, include.display()),
+                                other => return Err(error.err(format!(
+                                    "{} - include '{}' must contain mapping, but found: {:?}",
+                                    sources.describe(path), include.display(), other,
+                                ))),
+                            }
+                        }
+                    } else {
+                        let raw_path = ConfPath::join(raw, &Self::key_str(&key));
+                        let key = match key {
+                            Value::String(key) => Value::String(self.expand(&key, file, path)?),
+                            key => key,
+                        };
+                        let key_path = ConfPath::join(path, &Self::key_str(&key));
+                        sources.insert(&key_path, file);
+                        if let Some(position) = source.positions.get(&raw_path) {
+                            sources.insert_position(&key_path, position);
+                        }
+                        let value = self.resolve(value, source, &key_path, &raw_path, stack, sources)?;
+                        Self::insert(&error, &mut resolved, &mut origins, key, value, file, path)?;
+                    }
+                }
+                Ok(Value::Mapping(resolved))
+            }
+            Value::Sequence(items) => {
+                let items = items.into_iter()
+                    .map(|item| self.resolve(item, source, path, raw, stack, sources))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Sequence(items))
+            }
+            Value::String(value) => self.expand_value(&value, file, path),
+            conf => Ok(conf),
+        }
+    }
+    ///
+    /// Returns paths of the include directive, variables expanded
+    fn includes(&self, value: Value, file: &Path, path: &str) -> Result<Vec<String>, Error> {
+        let error = Error::new(&self.dbg, "includes");
+        let items = match value {
+            Value::String(include) => vec![Value::String(include)],
+            Value::Sequence(items) => items,
+            other => return Err(error.err(format!(
+                "'{}' in '{}' - path or list of paths expected in '{}', but found: {:?}",
+                path, file.display(), Self::INCLUDE, other,
+            ))),
+        };
+        items.into_iter().map(|item| match item {
+            Value::String(include) => self.expand(&include, file, path),
+            other => Err(error.err(format!(
+                "'{}' in '{}' - path expected in '{}', but found: {:?}",
+                path, file.display(), Self::INCLUDE, other,
+            ))),
+        }).collect()
+    }
+    ///
+    /// Inserts the node into the resolved mapping, duplicated keys are errors
+    fn insert(error: &Error, resolved: &mut Mapping, origins: &mut HashMap<Value, PathBuf>, key: Value, value: Value, file: &Path, path: &str) -> Result<(), Error> {
+        if let Some(origin) = origins.get(&key) {
+            return Err(error.err(format!(
+                "Duplicated key '{}' in '{}': defined in '{}' and '{}'",
+                Self::key_str(&key), path, origin.display(), file.display(),
+            )));
+        }
+        origins.insert(key.clone(), file.to_owned());
+        resolved.insert(key, value);
+        Ok(())
+    }
+    ///
+    /// Returns the string scalar with variables expanded,
+    /// the scalar consisting of the single variable is parsed as yaml
+    fn expand_value(&self, value: &str, file: &Path, path: &str) -> Result<Value, Error> {
+        let expanded = self.expand(value, file, path)?;
+        let single = value.starts_with("${") && value.find('}') == Some(value.len() - 1);
+        if single && !expanded.is_empty() {
+            if let Ok(scalar @ (Value::Bool(_) | Value::Number(_))) = serde_yaml::from_str::<Value>(&expanded) {
+                return Ok(scalar);
+            }
+        }
+        Ok(Value::String(expanded))
+    }
+    ///
+    /// Returns the `text` with `${NAME}` / `${NAME:-default}` expanded
+    fn expand(&self, text: &str, file: &Path, path: &str) -> Result<String, Error> {
+        let error = Error::new(&self.dbg, "expand");
+        if !text.contains("${") {
+            return Ok(text.to_owned());
+        }
+        let mut expanded = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("${") {
+            if rest[..start].ends_with('$') {
+                expanded.push_str(&rest[..start - 1]);
+                expanded.push_str("${");
+                rest = &rest[start + 2..];
+                continue;
+            }
+            expanded.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find('}') else {
+                return Err(error.err(format!("'{}' in '{}' - unclosed '${{' in: '{}'", path, file.display(), text)));
+            };
+            let expr = &rest[start + 2..start + end];
+            let (name, default) = match expr.split_once(":-") {
+                Some((name, default)) => (name.trim(), Some(default)),
+                None => (expr.trim(), None),
+            };
+            let value = self.vars.get(name).cloned().or_else(|| std::env::var(name).ok());
+            match (value, default) {
+                (Some(value), _) => expanded.push_str(&value),
+                (None, Some(default)) => expanded.push_str(default),
+                (None, None) => return Err(error.err(format!(
+                    "'{}' in '{}' - variable '{}' is not set and has no default",
+                    path, file.display(), name,
+                ))),
+            }
+            rest = &rest[start + end + 1..];
+        }
+        expanded.push_str(rest);
+        Ok(expanded)
+    }
+    ///
+    /// Returns the key as string for the key paths and messages
+    fn key_str(key: &Value) -> String {
+        match key {
+            Value::String(key) => key.clone(),
+            key => serde_yaml::to_string(key).map(|key| key.trim_end().to_owned()).unwrap_or_default(),
+        }
+    }
+}
+///
+/// The file being resolved by the [ConfLoader]
+struct Source<'a> {
+    file: &'a Path,
+    positions: ConfPositions,
+}
+//
+//
+impl std::fmt::Debug for ConfLoader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfLoader")
+            .field("dbg", &self.dbg)
+            .field("vars", &self.vars.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,159 @@
+///
+/// ### Path of the nodes in the [ConfTree](super::ConfTree)
+/// - Keys are separated by `/`, for example `service Task Task1/in queue recv-queue/max-length`
+/// - `/` and `\` inside of the key are escaped by `\`, for example `subscribe/\/App\/MultiQueue`,
+///   see [ConfPath::escape], paths returned by the `join` are already escaped
+/// - The key is matched by the words, so the spacing is insignificant
+/// - The word `*` matches any single word, `Drive*` / `*.Speed` - by the prefix / suffix,
+///   the key `*` matches any key
+///
+/// | path                        | matches                                     |
+/// | --------------------------- | ------------------------------------------- |
+/// | `service Task *`            | `service Task Task1`, `service Task Task2`  |
+/// | `service * *`               | all services having the sufix               |
+/// | `* /in queue *`             | in queues of the all root nodes             |
+/// | `point Drive.*`             | `point Drive.Speed`, `point Drive.Torque`   |
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfPath {
+    segments: Vec<Vec<String>>,
+}
+//
+//
+impl ConfPath {
+    ///
+    /// Separator of the keys in the path
+    pub const SEPARATOR: char = '/';
+    ///
+    /// Escapes the [ConfPath::SEPARATOR] inside of the key
+    pub const ESCAPE: char = '\\';
+    ///
+    /// Returns [ConfPath] parsed from the `path`, empty keys are ignored
+    pub fn new(path: &str) -> Self {
+        Self {
+            segments: Self::split(path)
+                .into_iter()
+                .map(|key| key.split_whitespace().map(|word| word.to_owned()).collect::<Vec<_>>())
+                .filter(|words| !words.is_empty())
+                .collect(),
+        }
+    }
+    ///
+    /// Returns the `key` with escaped [ConfPath::SEPARATOR] and [ConfPath::ESCAPE]
+    /// - `/App/MultiQueue` => `\/App\/MultiQueue`
+    pub fn escape(key: &str) -> String {
+        let mut escaped = String::with_capacity(key.len());
+        for c in key.chars() {
+            if c == Self::SEPARATOR || c == Self::ESCAPE {
+                escaped.push(Self::ESCAPE);
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+    ///
+    /// Returns the unescaped keys of the `path`, split by the not escaped [ConfPath::SEPARATOR]
+    pub fn split(path: &str) -> Vec<String> {
+        let mut keys = vec![];
+        let mut key = String::new();
+        let mut chars = path.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                Self::ESCAPE => key.extend(chars.next()),
+                Self::SEPARATOR => keys.push(std::mem::take(&mut key)),
+                _ => key.push(c),
+            }
+        }
+        keys.push(key);
+        keys
+    }
+    ///
+    /// Returns the `path` without leading and not escaped trailing [ConfPath::SEPARATOR]
+    pub fn trim(path: &str) -> &str {
+        let mut path = path.trim_start_matches(Self::SEPARATOR);
+        while path.ends_with(Self::SEPARATOR) && !Self::is_escaped(path, path.len() - 1) {
+            path = &path[..path.len() - 1];
+        }
+        path
+    }
+    ///
+    /// Returns the parent path and the last key, still escaped,
+    /// split by the last not escaped [ConfPath::SEPARATOR]
+    pub fn split_last(path: &str) -> Option<(&str, &str)> {
+        path.char_indices()
+            .rev()
+            .find(|(i, c)| *c == Self::SEPARATOR && !Self::is_escaped(path, *i))
+            .map(|(i, _)| (&path[..i], &path[i + 1..]))
+    }
+    ///
+    /// Returns the number of the keys
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+    ///
+    /// Returns `true` if the path is empty, matches the root
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+    ///
+    /// Returns `true` if the key number `index` of the path matches the `key`
+    pub fn matches(&self, index: usize, key: &str) -> bool {
+        match self.segments.get(index) {
+            Some(pattern) => Self::matches_key(pattern, key),
+            None => false,
+        }
+    }
+    ///
+    /// Returns the path of the child node, the `key` is escaped
+    pub fn join(parent: &str, key: &str) -> String {
+        match parent.is_empty() {
+            true => Self::escape(key),
+            false => format!("{}{}{}", parent, Self::SEPARATOR, Self::escape(key)),
+        }
+    }
+    ///
+    /// Returns `true` if the char at the byte `index` of the `path` is escaped,
+    /// preceded by the odd number of [ConfPath::ESCAPE]
+    fn is_escaped(path: &str, index: usize) -> bool {
+        path[..index].chars().rev().take_while(|c| *c == Self::ESCAPE).count() % 2 == 1
+    }
+    ///
+    /// Returns `true` if the words of the `key` are matched by the `pattern`
+    fn matches_key(pattern: &[String], key: &str) -> bool {
+        if pattern.len() == 1 && pattern[0] == "*" {
+            return true;
+        }
+        let words: Vec<&str> = key.split_whitespace().collect();
+        words.len() == pattern.len() && pattern.iter().zip(words).all(|(pattern, word)| Self::matches_word(pattern, word))
+    }
+    ///
+    /// Returns `true` if the `word` is matched by the `pattern`
+    fn matches_word(pattern: &str, word: &str) -> bool {
+        match pattern {
+            "*" => true,
+            p if p.len() > 1 && p.ends_with('*') => word.starts_with(&p[..p.len() - 1]),
+            p if p.len() > 1 && p.starts_with('*') => word.ends_with(&p[1..]),
+            p => p == word,
+        }
+    }
+}
+//
+//
+impl From<&str> for ConfPath {
+    fn from(path: &str) -> Self {
+        Self::new(path)
+    }
+}
+//
+//
+impl From<&String> for ConfPath {
+    fn from(path: &String) -> Self {
+        Self::new(path)
+    }
+}
+//
+//
+impl From<String> for ConfPath {
+    fn from(path: String) -> Self {
+        Self::new(&path)
+    }
+}
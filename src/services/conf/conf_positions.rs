@@ -0,0 +1,101 @@
+use crate::collections::FxIndexMap;
+use super::conf_path::ConfPath;
+///
+/// ### Positions of the keys in the yaml text
+/// - Keys are identified by the key path, keys joined by `/`, see [ConfPath]
+/// - Position is `(line, column)`, both starting from 1
+/// - Only block mappings are indexed, the keys inside of the sequences,
+///   flow collections and block scalars are not recorded
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfPositions {
+    positions: FxIndexMap<String, (usize, usize)>,
+}
+//
+//
+impl ConfPositions {
+    ///
+    /// Returns [ConfPositions] of the keys found in the yaml `text`
+    pub fn new(text: &str) -> Self {
+        let mut positions = FxIndexMap::default();
+        // (indent, key), `None` - the content of the node is not indexed
+        let mut stack: Vec<(usize, Option<String>)> = vec![];
+        for (index, line) in text.lines().enumerate() {
+            let content = line.trim_start();
+            if content.is_empty() || content.starts_with('#') || content.starts_with("---") || content.starts_with("...") {
+                continue;
+            }
+            let indent = line.len() - content.len();
+            while stack.last().is_some_and(|(level, _)| *level >= indent) {
+                stack.pop();
+            }
+            if stack.last().is_some_and(|(_, key)| key.is_none()) {
+                continue;
+            }
+            if content.starts_with('-') || content.starts_with('[') || content.starts_with('{') {
+                stack.push((indent, None));
+                continue;
+            }
+            match Self::key(content) {
+                Some((key, value)) => {
+                    let path = stack.iter()
+                        .filter_map(|(_, key)| key.as_deref())
+                        .chain([key.as_str()])
+                        .fold(String::new(), |path, key| ConfPath::join(&path, key));
+                    positions.entry(path).or_insert((index + 1, indent + 1));
+                    match value.starts_with('|') || value.starts_with('>') {
+                        true => stack.push((indent, None)),
+                        false => stack.push((indent, Some(key))),
+                    }
+                }
+                None => stack.push((indent, None)),
+            }
+        }
+        Self { positions }
+    }
+    ///
+    /// Returns the `(line, column)` of the key by its `path`
+    pub fn get(&self, path: &str) -> Option<(usize, usize)> {
+        self.positions.get(ConfPath::trim(path)).copied()
+    }
+    ///
+    /// Returns the `(line, column)` of the key by its `path`,
+    /// if not recorded - of the nearest recorded parent
+    pub fn nearest(&self, path: &str) -> Option<(usize, usize)> {
+        let mut path = ConfPath::trim(path);
+        loop {
+            if let Some(position) = self.positions.get(path) {
+                return Some(*position);
+            }
+            match ConfPath::split_last(path) {
+                Some((parent, _)) => path = parent,
+                None => return None,
+            }
+        }
+    }
+    ///
+    /// Returns all recorded key paths with their positions
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &(usize, usize))> {
+        self.positions.iter()
+    }
+    ///
+    /// Returns the key and the rest of the line, if the line is the `key: value`
+    fn key(content: &str) -> Option<(String, &str)> {
+        let (key, rest) = match content.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let end = content[1..].find(quote)? + 1;
+                (content[1..end].to_owned(), content[end + 1..].trim_start())
+            }
+            _ => {
+                let end = content.char_indices()
+                    .find(|(i, c)| *c == ':' && content[i + 1..].chars().next().is_none_or(char::is_whitespace))
+                    .map(|(i, _)| i)?;
+                (content[..end].trim_end().to_owned(), &content[end..])
+            }
+        };
+        let value = rest.strip_prefix(':')?.trim();
+        match key.is_empty() {
+            true => None,
+            false => Some((key, value)),
+        }
+    }
+}
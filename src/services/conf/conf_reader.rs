@@ -0,0 +1,128 @@
+use std::time::Duration;
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    collections::FxIndexMap,
+    services::{entity::{Name, PointConfig}, service::LinkName, subscription::ConfSubscribe},
+};
+use super::{
+    conf_field::ConfField, conf_in_queue::ConfInQueue, conf_schema::ConfSchema,
+    conf_tree::{ConfTree, ConfTreeGet}, diag_keywd::DiagKeywd,
+};
+///
+/// ### Reads the typed parameters of the service config node
+/// - The name of the service is `sufix` of the key if specified, otherwise `name`,
+///   `service Task Task1` => `Task1`, `service MultiQueue` => `MultiQueue`
+/// - Errors are consistent: `Parameter '<key>' - <reason>`
+///
+/// Used by the [conf_struct](crate::conf_struct) generated [FromConfTree](super::FromConfTree) implementations
+pub struct ConfReader<'a> {
+    dbg: Dbg,
+    name: Name,
+    conf: &'a ConfTree,
+}
+//
+//
+impl<'a> ConfReader<'a> {
+    ///
+    /// Returns [ConfReader] of the service config node
+    /// - `parent` - the name of the application
+    /// - `type_` - the name of the config type, for the debug messages
+    pub fn new(parent: impl Into<String>, type_: &str, conf: &'a ConfTree) -> Result<Self, Error> {
+        let me = conf.sufix_or(conf.name()?);
+        let name = Name::new(parent, &me);
+        let dbg = Dbg::new(name.parent(), format!("{}({})", type_, me));
+        log::trace!("{}.new | conf: {:?}", dbg, conf);
+        Ok(Self { dbg, name, conf })
+    }
+    ///
+    /// Returns the debug name of the config
+    pub fn dbg(&self) -> &Dbg {
+        &self.dbg
+    }
+    ///
+    /// Returns the config node
+    pub fn conf(&self) -> &ConfTree {
+        self.conf
+    }
+    ///
+    /// Returns the name of the service
+    pub fn name(&self) -> Result<Name, Error> {
+        Ok(self.name.clone())
+    }
+    ///
+    /// Returns the value of the required parameter `key`
+    pub fn required<T: ConfField>(&self, key: &str) -> Result<T, Error> {
+        let error = Error::new(&self.dbg, "required");
+        match self.optional(key)? {
+            Some(value) => Ok(value),
+            None => Err(error.err(format!("Parameter '{}' - not found", key))),
+        }
+    }
+    ///
+    /// Returns the value of the optional parameter `key`, `None` if missing or empty
+    pub fn optional<T: ConfField>(&self, key: &str) -> Result<Option<T>, Error> {
+        let error = Error::new(&self.dbg, "optional");
+        match ConfTreeGet::<serde_yaml::Value>::get(self.conf, key) {
+            Some(serde_yaml::Value::Null) | None => Ok(None),
+            Some(value) => T::from_value(&value)
+                .map(Some)
+                .map_err(|err| error.err(format!("Parameter '{}' - {}", key, err))),
+        }
+    }
+    ///
+    /// Returns the value of the optional parameter `key`, `default` if missing or empty
+    pub fn or<T: ConfField>(&self, key: &str, default: T) -> Result<T, Error> {
+        Ok(self.optional(key)?.unwrap_or(default))
+    }
+    ///
+    /// Returns the required `in queue <name>` with its `max-length`
+    pub fn in_queue(&self) -> Result<ConfInQueue, Error> {
+        let error = Error::new(&self.dbg, "in_queue");
+        self.conf.get_in_queue()
+            .map(|(name, max_length)| ConfInQueue { name, max_length })
+            .map_err(|err| error.pass_with("Parameter 'in queue' - not found or invalid", err.to_string()))
+    }
+    ///
+    /// Returns the links of the optional `send-to`, `Service.link` or the list of them
+    pub fn send_to(&self) -> Result<Vec<LinkName>, Error> {
+        Ok(self.optional("send-to")?.unwrap_or_default())
+    }
+    ///
+    /// Returns the optional `cycle`
+    pub fn cycle(&self) -> Result<Option<Duration>, Error> {
+        self.optional("cycle")
+    }
+    ///
+    /// Returns the optional `subscribe`, empty if missing
+    pub fn subscribe(&self) -> Result<ConfSubscribe, Error> {
+        let conf = self.optional("subscribe")?.unwrap_or_default();
+        Ok(ConfSubscribe::new(conf))
+    }
+    ///
+    /// Returns the points of the optional `diagnosis`, validated by the [ConfSchema::diagnosis]
+    pub fn diagnosis(&self) -> Result<FxIndexMap<DiagKeywd, PointConfig>, Error> {
+        let error = Error::new(&self.dbg, "diagnosis");
+        match self.optional::<serde_yaml::Value>("diagnosis")? {
+            Some(conf) => {
+                let violations = ConfSchema::diagnosis().validate(&ConfTree::new("diagnosis", conf));
+                if violations.is_empty() {
+                    Ok(self.conf.get_diagnosis(self.name.join()))
+                } else {
+                    let violations: Vec<String> = violations.iter().map(|violation| violation.to_string()).collect();
+                    Err(error.err(format!("Parameter 'diagnosis' - invalid: \n\t{}", violations.join("\n\t"))))
+                }
+            }
+            None => Ok(FxIndexMap::default()),
+        }
+    }
+}
+//
+//
+impl std::fmt::Debug for ConfReader<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfReader")
+            .field("dbg", &self.dbg)
+            .field("conf", &self.conf)
+            .finish()
+    }
+}
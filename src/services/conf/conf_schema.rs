@@ -0,0 +1,278 @@
+use serde_yaml::Value;
+use super::{
+    conf_path::ConfPath, conf_positions::ConfPositions, conf_schema_type::ConfSchemaType,
+    conf_sources::ConfSources, conf_tree::ConfTree, conf_violation::ConfViolation,
+};
+///
+/// ### Schema of the configuration node
+/// - Keys are matched by the words, `*` matches any word, see [ConfPath],
+///   so `in queue *` matches `in queue in-queue`
+/// - Required key is satisfied by at least one matching key or its alias
+/// - Unknown keys are violations, unless the schema is `open`
+/// - Schemas of the known nodes: [ConfSchema::app], [ConfSchema::service], [ConfSchema::task],
+///   [ConfSchema::queue], [ConfSchema::point], [ConfSchema::retain], [ConfSchema::diagnosis]
+///
+/// **Example**
+/// ```ignore
+/// let (conf, sources) = ConfLoader::new(&dbg).load("app.yaml")?;
+/// for violation in ConfSchema::app().validate_sources(&conf, &sources) {
+///     log::error!("{}", violation);   // services/ied01.yaml:12:9: 'service Task Task1/in queue in-queue' - required key 'max-length' is missing in 'queue'
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfSchema {
+    name: String,
+    keys: Vec<ConfSchemaKey>,
+    open: bool,
+}
+//
+//
+impl ConfSchema {
+    ///
+    /// Returns [ConfSchema] new instance without keys, the `name` is used in the messages
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            keys: vec![],
+            open: false,
+        }
+    }
+    ///
+    /// Returns [ConfSchema] with the required `key` of the `type_`
+    pub fn required(mut self, key: impl Into<String>, type_: ConfSchemaType) -> Self {
+        self.keys.push(ConfSchemaKey::new(key, true, type_));
+        self
+    }
+    ///
+    /// Returns [ConfSchema] with the optional `key` of the `type_`
+    pub fn optional(mut self, key: impl Into<String>, type_: ConfSchemaType) -> Self {
+        self.keys.push(ConfSchemaKey::new(key, false, type_));
+        self
+    }
+    ///
+    /// Returns [ConfSchema] where the last added key is also matched by the `alias`
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        if let Some(key) = self.keys.last_mut() {
+            key.aliases.push(ConfPath::new(&alias.into()));
+        }
+        self
+    }
+    ///
+    /// Returns [ConfSchema] allowing the keys not described in the schema
+    pub fn open(mut self) -> Self {
+        self.open = true;
+        self
+    }
+    ///
+    /// Returns the name of the schema
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    ///
+    /// Returns all violations of the schema found in the `conf`,
+    /// key paths are starting from the key of the `conf`, empty for the root
+    pub fn validate(&self, conf: &ConfTree) -> Vec<ConfViolation> {
+        let mut violations = vec![];
+        self.check(&conf.conf, &conf.key, &mut violations);
+        violations
+    }
+    ///
+    /// Returns all violations of the schema found in the root `conf` loaded by the `ConfLoader`,
+    /// located in the source files
+    pub fn validate_sources(&self, conf: &ConfTree, sources: &ConfSources) -> Vec<ConfViolation> {
+        self.validate(conf).into_iter()
+            .map(|violation| {
+                let file = sources.file(&violation.path).map(|file| file.to_owned());
+                let position = sources.position(&violation.path);
+                violation.located(file, position)
+            })
+            .collect()
+    }
+    ///
+    /// Returns all violations of the schema found in the yaml `text`,
+    /// located by line and column
+    pub fn validate_str(&self, text: &str) -> Vec<ConfViolation> {
+        match serde_yaml::from_str::<Value>(text) {
+            Ok(conf) => {
+                let positions = ConfPositions::new(text);
+                self.validate(&ConfTree::new_root(conf)).into_iter()
+                    .map(|violation| {
+                        let position = positions.nearest(&violation.path);
+                        violation.located(None, position)
+                    })
+                    .collect()
+            }
+            Err(err) => {
+                let position = err.location().map(|location| (location.line(), location.column()));
+                vec![ConfViolation::new("", format!("invalid yaml: {}", err)).located(None, position)]
+            }
+        }
+    }
+    ///
+    /// Checks the mapping `value` of the node by its key `path`, appends found violations
+    pub(super) fn check(&self, value: &Value, path: &str, violations: &mut Vec<ConfViolation>) {
+        let empty = serde_yaml::Mapping::new();
+        let mapping = match value {
+            Value::Mapping(mapping) => mapping,
+            Value::Null => &empty,
+            value => {
+                violations.push(ConfViolation::new(path, format!(
+                    "'{}' mapping expected, but found: {}",
+                    self.name, ConfSchemaType::found(value),
+                )));
+                return;
+            }
+        };
+        let mut matched = vec![false; self.keys.len()];
+        for (key, value) in mapping {
+            let Some(key) = key.as_str() else {
+                violations.push(ConfViolation::new(
+                    ConfPath::join(path, &ConfSchemaType::found(key)),
+                    format!("String key expected, but found: {}", ConfSchemaType::found(key)),
+                ));
+                continue;
+            };
+            let key_path = ConfPath::join(path, key);
+            match self.keys.iter().position(|schema_key| schema_key.matches(key)) {
+                Some(index) => {
+                    matched[index] = true;
+                    self.keys[index].type_.check(value, &key_path, violations);
+                }
+                None if self.open => {}
+                None => violations.push(ConfViolation::new(key_path, format!(
+                    "unknown key in '{}', expected: {:?}",
+                    self.name, self.keys.iter().map(|schema_key| schema_key.key.as_str()).collect::<Vec<_>>(),
+                ))),
+            }
+        }
+        for (schema_key, matched) in self.keys.iter().zip(matched) {
+            if schema_key.required && !matched {
+                violations.push(ConfViolation::new(path, format!(
+                    "required key '{}' ({}) is missing in '{}'",
+                    schema_key.key, schema_key.type_.describe(), self.name,
+                )));
+            }
+        }
+    }
+    ///
+    /// Returns the schema of the application root
+    /// - `name`, `description`, `retain`, `service ...`, `task ...` are checked,
+    ///   other keys are allowed
+    pub fn app() -> Self {
+        Self::new("application")
+            .optional("name", ConfSchemaType::String)
+            .optional("description", ConfSchemaType::String)
+            .optional("retain", ConfSchemaType::Node(Self::retain()))
+            .optional("service *", ConfSchemaType::Node(Self::service()))
+            .optional("service * *", ConfSchemaType::Node(Self::service()))
+            .optional("task *", ConfSchemaType::Node(Self::task()))
+            .optional("task * *", ConfSchemaType::Node(Self::task()))
+            .open()
+    }
+    ///
+    /// Returns the common schema of the service,
+    /// the service specific keys are allowed
+    pub fn service() -> Self {
+        Self::new("service")
+            .optional("cycle", ConfSchemaType::Duration)
+            .optional("in queue *", ConfSchemaType::Node(Self::queue()))
+            .optional("send-to", ConfSchemaType::OneOf(vec![ConfSchemaType::String, ConfSchemaType::list(ConfSchemaType::String)]))
+            .optional("subscribe", ConfSchemaType::Any)
+            .optional("diagnosis", ConfSchemaType::Node(Self::diagnosis()))
+            .optional("point *", ConfSchemaType::Node(Self::point()))
+            .open()
+    }
+    ///
+    /// Returns the common schema of the task,
+    /// the functions and the task specific keys are allowed
+    pub fn task() -> Self {
+        Self::new("task")
+            .optional("cycle", ConfSchemaType::Duration)
+            .optional("in queue *", ConfSchemaType::Node(Self::queue()))
+            .optional("subscribe", ConfSchemaType::Any)
+            .optional("diagnosis", ConfSchemaType::Node(Self::diagnosis()))
+            .open()
+    }
+    ///
+    /// Returns the schema of the queue
+    pub fn queue() -> Self {
+        Self::new("queue")
+            .required("max-length", ConfSchemaType::Int)
+    }
+    ///
+    /// Returns the schema of the point, see `PointConfig`,
+    /// the service specific keys are allowed, like `generator` of the `ProducerService`
+    pub fn point() -> Self {
+        Self::new("point")
+            .required("type", ConfSchemaType::values(&[
+                "Bool", "bool", "Int", "int", "Real", "real", "Double", "double", "String", "string", "Json", "json",
+            ]))
+            .alias("Type")
+            .optional("history", ConfSchemaType::values(&[
+                "", "None", "none", "r", "read", "Read", "w", "write", "Write", "rw", "readwrite", "ReadWrite",
+            ]))
+            .optional("alarm", ConfSchemaType::Range(0, 15))
+            .optional("address", ConfSchemaType::Node(Self::new("address")
+                .optional("offset", ConfSchemaType::Range(0, i64::from(u32::MAX)))
+                .optional("bit", ConfSchemaType::Range(0, i64::from(u8::MAX)))
+            ))
+            .optional("filters", ConfSchemaType::Node(Self::new("filters")
+                .required("threshold", ConfSchemaType::Real)
+                .optional("factor", ConfSchemaType::Real)
+            ))
+            .optional("comment", ConfSchemaType::String)
+            .open()
+    }
+    ///
+    /// Returns the schema of the retain, see `RetainConf`
+    pub fn retain() -> Self {
+        Self::new("retain")
+            .optional("path", ConfSchemaType::String)
+            .optional("point", ConfSchemaType::Node(Self::new("retain point")
+                .required("path", ConfSchemaType::String)
+                .optional("api", ConfSchemaType::Node(Self::new("retain point api")
+                    .required("table", ConfSchemaType::String)
+                    .required("address", ConfSchemaType::String)
+                    .required("auth_token", ConfSchemaType::String)
+                    .required("database", ConfSchemaType::String)
+                ))
+            ))
+    }
+    ///
+    /// Returns the schema of the diagnosis
+    pub fn diagnosis() -> Self {
+        Self::new("diagnosis")
+            .optional("point *", ConfSchemaType::Node(Self::point()))
+    }
+}
+///
+/// The key described in the [ConfSchema]
+#[derive(Debug, Clone)]
+struct ConfSchemaKey {
+    key: String,
+    pattern: ConfPath,
+    aliases: Vec<ConfPath>,
+    required: bool,
+    type_: ConfSchemaType,
+}
+//
+//
+impl ConfSchemaKey {
+    ///
+    /// Returns [ConfSchemaKey] new instance
+    fn new(key: impl Into<String>, required: bool, type_: ConfSchemaType) -> Self {
+        let key = key.into();
+        Self {
+            pattern: ConfPath::new(&key),
+            aliases: vec![],
+            key,
+            required,
+            type_,
+        }
+    }
+    ///
+    /// Returns `true` if the `key` is matched by the pattern or any of the aliases
+    fn matches(&self, key: &str) -> bool {
+        self.pattern.matches(0, key) || self.aliases.iter().any(|alias| alias.matches(0, key))
+    }
+}
@@ -0,0 +1,113 @@
+use std::str::FromStr;
+use serde_yaml::Value;
+use super::{conf_duration::ConfDuration, conf_path::ConfPath, conf_schema::ConfSchema, conf_violation::ConfViolation};
+///
+/// The type of the value expected by the [ConfSchema]
+#[derive(Debug, Clone)]
+pub enum ConfSchemaType {
+    /// Any value
+    Any,
+    /// `true` / `false`
+    Bool,
+    /// Integer number
+    Int,
+    /// Integer number within the inclusive range
+    Range(i64, i64),
+    /// Integer or real number
+    Real,
+    /// String
+    String,
+    /// Duration, like `100 ms`, `1 s`, see [ConfDuration]
+    Duration,
+    /// One of the listed strings
+    Enum(Vec<String>),
+    /// Sequence of the items of the type
+    List(Box<ConfSchemaType>),
+    /// Any of the listed types
+    OneOf(Vec<ConfSchemaType>),
+    /// Mapping validated by the schema
+    Node(ConfSchema),
+}
+//
+//
+impl ConfSchemaType {
+    ///
+    /// Returns [ConfSchemaType::Enum] of the `values`
+    pub fn values(values: &[&str]) -> Self {
+        Self::Enum(values.iter().map(|value| (*value).to_owned()).collect())
+    }
+    ///
+    /// Returns [ConfSchemaType::List] of the `item`
+    pub fn list(item: ConfSchemaType) -> Self {
+        Self::List(Box::new(item))
+    }
+    ///
+    /// Returns human readable description of the type
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Any => "any value".to_owned(),
+            Self::Bool => "Bool".to_owned(),
+            Self::Int => "Int".to_owned(),
+            Self::Range(min, max) => format!("Int in {}..{}", min, max),
+            Self::Real => "Real".to_owned(),
+            Self::String => "String".to_owned(),
+            Self::Duration => "Duration (like '100 ms', '1 s')".to_owned(),
+            Self::Enum(values) => format!("one of {:?}", values),
+            Self::List(item) => format!("list of {}", item.describe()),
+            Self::OneOf(types) => types.iter().map(|type_| type_.describe()).collect::<Vec<_>>().join(" or "),
+            Self::Node(schema) => format!("'{}' mapping", schema.name()),
+        }
+    }
+    ///
+    /// Checks the `value` of the node by its key `path`, appends found violations
+    pub(super) fn check(&self, value: &Value, path: &str, violations: &mut Vec<ConfViolation>) {
+        let valid = match self {
+            Self::Any => true,
+            Self::Bool => value.is_bool(),
+            Self::Int => value.is_i64() || value.is_u64(),
+            Self::Range(min, max) => value.as_i64().is_some_and(|value| (*min..=*max).contains(&value)),
+            Self::Real => value.is_number(),
+            Self::String => value.is_string(),
+            Self::Duration => match value {
+                Value::Number(value) => value.is_u64(),
+                Value::String(value) => ConfDuration::from_str(value).is_ok(),
+                _ => false,
+            },
+            Self::Enum(values) => value.as_str().is_some_and(|value| values.iter().any(|v| v == value)),
+            Self::List(item) => match value {
+                Value::Sequence(items) => {
+                    for (index, value) in items.iter().enumerate() {
+                        item.check(value, &ConfPath::join(path, &index.to_string()), violations);
+                    }
+                    true
+                }
+                _ => false,
+            },
+            Self::OneOf(types) => types.iter().any(|type_| {
+                let mut found = vec![];
+                type_.check(value, path, &mut found);
+                found.is_empty()
+            }),
+            Self::Node(schema) => {
+                schema.check(value, path, violations);
+                true
+            }
+        };
+        if !valid {
+            violations.push(ConfViolation::new(path, format!("{} expected, but found: {}", self.describe(), Self::found(value))));
+        }
+    }
+    ///
+    /// Returns the short description of the found `value`
+    pub(super) fn found(value: &Value) -> String {
+        match value {
+            Value::Null => "empty value".to_owned(),
+            Value::Bool(value) => value.to_string(),
+            Value::Number(value) => value.to_string(),
+            Value::String(value) => format!("'{}'", value),
+            Value::Sequence(_) => "list".to_owned(),
+            Value::Mapping(_) => "mapping".to_owned(),
+            Value::Tagged(value) => format!("tagged {}", value.tag),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+use crate::collections::FxIndexMap;
+use super::conf_path::ConfPath;
+///
+/// Source files of the configuration nodes loaded by the `ConfLoader`
+/// - Nodes are identified by the key path, keys joined by `/`, root is `""`, see [ConfPath]
+/// - Node not recorded explicitly belongs to the file of the nearest recorded parent
+/// - Position `(line, column)` of the key in its file is recorded where available
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfSources {
+    files: FxIndexMap<String, PathBuf>,
+    positions: FxIndexMap<String, (usize, usize)>,
+}
+//
+//
+impl ConfSources {
+    ///
+    /// Path separator of the keys
+    pub const SEPARATOR: char = ConfPath::SEPARATOR;
+    ///
+    /// Returns [ConfSources] new empty instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Records the source `file` of the node by its key `path`
+    pub fn insert(&mut self, path: impl Into<String>, file: impl Into<PathBuf>) {
+        self.files.insert(path.into(), file.into());
+    }
+    ///
+    /// Records the `(line, column)` of the key by its `path`
+    pub fn insert_position(&mut self, path: impl Into<String>, position: (usize, usize)) {
+        self.positions.insert(path.into(), position);
+    }
+    ///
+    /// Returns the source file of the node by its key `path`
+    pub fn file(&self, path: &str) -> Option<&Path> {
+        let mut path = ConfPath::trim(path);
+        loop {
+            if let Some(file) = self.files.get(path) {
+                return Some(file);
+            }
+            match ConfPath::split_last(path) {
+                Some((parent, _)) => path = parent,
+                None if !path.is_empty() => path = "",
+                None => return None,
+            }
+        }
+    }
+    ///
+    /// Returns the `(line, column)` of the key by its `path`,
+    /// if not recorded - of the nearest recorded parent in the same file
+    pub fn position(&self, path: &str) -> Option<(usize, usize)> {
+        let file = self.file(path)?;
+        let mut path = ConfPath::trim(path);
+        loop {
+            if self.file(path) != Some(file) {
+                return None;
+            }
+            if let Some(position) = self.positions.get(path) {
+                return Some(*position);
+            }
+            match ConfPath::split_last(path) {
+                Some((parent, _)) => path = parent,
+                None => return None,
+            }
+        }
+    }
+    ///
+    /// Returns all loaded files in the loading order, without duplicates
+    pub fn files(&self) -> Vec<&Path> {
+        let mut files: Vec<&Path> = vec![];
+        for file in self.files.values() {
+            if !files.contains(&file.as_path()) {
+                files.push(file);
+            }
+        }
+        files
+    }
+    ///
+    /// Returns the key `path` with its source file, for the error messages
+    pub fn describe(&self, path: &str) -> String {
+        match (self.file(path), self.position(path)) {
+            (Some(file), Some((line, column))) => format!("'{}' in '{}:{}:{}'", path, file.display(), line, column),
+            (Some(file), None) => format!("'{}' in '{}'", path, file.display()),
+            _ => format!("'{}'", path),
+        }
+    }
+}
@@ -0,0 +1,521 @@
+use sal_core::{dbg::Dbg, error::Error};
+use serde_yaml::{Mapping, Value};
+use crate::collections::FxIndexMap;
+use super::{conf_path::ConfPath, conf_sources::ConfSources, conf_tree::ConfTree};
+///
+/// ### Expands the templates of the configuration
+/// - `template <Name>:` - defines the template of the nodes, on any level,
+///   definitions are removed from the tree, names are global
+///   - `params:` - optional, parameters of the template with the default values,
+///     `~` - the parameter is required, if specified, other parameters are not allowed
+/// - `use template <Name> as <Instance>: {param: value, ...}` - the nodes of the template are inserted
+///   into the mapping containing `use`, `as <Instance>` is optional, default is the name of the template
+/// - `$(param)` inside the keys and the string values of the template is replaced by the parameter,
+///   the instance name is the parameter `name`, `$$(` is a literal `$(`
+/// - `$(offset + i * 4)` - integer expression of the parameters, operators `+`, `-`, `*`
+/// - The scalar consisting of the single `$(...)` takes the type of the parameter,
+///   so `offset: $(offset)` is a number
+/// - The parameter `1..4` / `1..=4` creates the instance for the each value of the range,
+///   several ranges create the instance for the each combination,
+///   other arguments and the instance name can refer to the range parameters,
+///   empty or reversed range like `5..1` is an error
+/// - Templates can use other templates, the arguments of the nested `use` can refer
+///   to the parameters of the outer template, cyclic use and duplicated keys are errors
+///
+/// **Example**
+/// ```yaml
+/// template Drive:
+///     params:
+///         offset: ~
+///         history: r
+///     point $(name).Speed:
+///         type: Real
+///         history: $(history)
+///         address:
+///             offset: $(offset)
+///     point $(name).Torque:
+///         type: Real
+///         address:
+///             offset: $(offset + 4)
+/// service ProfinetClient Ied01:
+///     use template Drive as Drive1: {offset: 0}
+///     use template Drive as Drive$(i): {i: 2..=4, offset: $(i * 8)}
+/// ```
+pub struct ConfTemplates {
+    dbg: Dbg,
+}
+//
+//
+impl ConfTemplates {
+    ///
+    /// The keyword of the template definition
+    pub const TEMPLATE: &'static str = "template";
+    ///
+    /// The keyword of the template instantiation
+    pub const USE: &'static str = "use";
+    ///
+    /// The key of the template parameters
+    pub const PARAMS: &'static str = "params";
+    ///
+    /// The parameter holding the instance name
+    pub const NAME: &'static str = "name";
+    ///
+    /// Maximum number of the instances of the all `use template` of the configuration,
+    /// including nested ones and unrolled from the ranges
+    pub const MAX_INSTANCES: usize = 10_000;
+    ///
+    /// Returns [ConfTemplates] new instance
+    pub fn new(parent: impl Into<String>) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "ConfTemplates"),
+        }
+    }
+    ///
+    /// Returns the `conf` with all templates expanded
+    pub fn expand(&self, conf: &ConfTree) -> Result<ConfTree, Error> {
+        let mut sources = ConfSources::new();
+        let expanded = self.expand_value(conf.conf.clone(), &mut sources)?;
+        Ok(ConfTree::new(&conf.key, expanded))
+    }
+    ///
+    /// Returns the `conf` with all templates expanded,
+    /// instantiated nodes are recorded in the `sources` at the location of the `use`
+    pub(super) fn expand_value(&self, conf: Value, sources: &mut ConfSources) -> Result<Value, Error> {
+        let mut templates = FxIndexMap::default();
+        let conf = self.collect(conf, "", &mut templates)?;
+        if !templates.is_empty() {
+            log::debug!("{}.expand | Templates: {:?}", self.dbg, templates.keys().collect::<Vec<_>>());
+        }
+        let mut budget = Self::MAX_INSTANCES;
+        self.expand_node(conf, "", &templates, &mut vec![], &mut budget, sources)
+    }
+    ///
+    /// Returns the node with the template definitions removed into the `templates`
+    fn collect(&self, conf: Value, path: &str, templates: &mut FxIndexMap<String, Template>) -> Result<Value, Error> {
+        let error = Error::new(&self.dbg, "collect");
+        match conf {
+            Value::Mapping(mapping) => {
+                let mut collected = Mapping::new();
+                for (key, value) in mapping {
+                    let key_str = key.as_str().unwrap_or_default().to_owned();
+                    match Self::template_name(&key_str) {
+                        Some(name) => {
+                            let key_path = ConfPath::join(path, &key_str);
+                            let mut body = match value {
+                                Value::Mapping(body) => body,
+                                Value::Null => Mapping::new(),
+                                other => return Err(error.err(format!(
+                                    "'{}' - mapping expected, but found: {:?}", key_path, other,
+                                ))),
+                            };
+                            let params = match body.shift_remove(Self::PARAMS) {
+                                Some(Value::Mapping(params)) => params,
+                                Some(Value::Null) | None => Mapping::new(),
+                                Some(other) => return Err(error.err(format!(
+                                    "'{}' - '{}' must be a mapping, but found: {:?}", key_path, Self::PARAMS, other,
+                                ))),
+                            };
+                            if let Some(template) = templates.get(&name) {
+                                return Err(error.err(format!(
+                                    "'{}' - template '{}' already defined in '{}'", key_path, name, template.path,
+                                )));
+                            }
+                            templates.insert(name, Template { path: key_path, params, body });
+                        }
+                        None => {
+                            let value = self.collect(value, &ConfPath::join(path, &key_str), templates)?;
+                            collected.insert(key, value);
+                        }
+                    }
+                }
+                Ok(Value::Mapping(collected))
+            }
+            Value::Sequence(items) => {
+                let items = items.into_iter()
+                    .map(|item| self.collect(item, path, templates))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Sequence(items))
+            }
+            conf => Ok(conf),
+        }
+    }
+    ///
+    /// Returns the node with the `use template` instantiated
+    /// - `stack` - templates being instantiated, to detect the cycles
+    /// - `budget` - number of the instances still allowed for the whole expansion
+    fn expand_node(
+        &self,
+        conf: Value,
+        path: &str,
+        templates: &FxIndexMap<String, Template>,
+        stack: &mut Vec<String>,
+        budget: &mut usize,
+        sources: &mut ConfSources,
+    ) -> Result<Value, Error> {
+        let error = Error::new(&self.dbg, "expand_node");
+        match conf {
+            Value::Mapping(mapping) => {
+                let mut expanded = Mapping::new();
+                for (key, value) in mapping {
+                    let key_str = key.as_str().unwrap_or_default().to_owned();
+                    let use_path = ConfPath::join(path, &key_str);
+                    match Self::template_use(&key_str).map_err(|err| error.err(format!("'{}' - {}", use_path, err)))? {
+                        Some((name, instance)) => {
+                            let Some(template) = templates.get(&name) else {
+                                return Err(error.err(format!(
+                                    "'{}' - template '{}' is not defined, defined: {:?}", use_path, name, templates.keys().collect::<Vec<_>>(),
+                                )));
+                            };
+                            if stack.contains(&name) {
+                                return Err(error.err(format!(
+                                    "'{}' - template cycle: {} -> {}", use_path, stack.join(" -> "), name,
+                                )));
+                            }
+                            let args = match value {
+                                Value::Mapping(args) => args,
+                                Value::Null => Mapping::new(),
+                                other => return Err(error.err(format!(
+                                    "'{}' - mapping of the parameters expected, but found: {:?}", use_path, other,
+                                ))),
+                            };
+                            let loops: Vec<Value> = args.iter()
+                                .filter(|(_, value)| value.as_str().and_then(Self::range).is_some())
+                                .map(|(key, _)| key.clone())
+                                .collect();
+                            let instances = Self::instances(args)
+                                .map_err(|err| error.err(format!("'{}' - template '{}': {}", use_path, name, err)))?;
+                            *budget = budget.checked_sub(instances.len()).ok_or_else(|| error.err(format!(
+                                "'{}' - template '{}': total number of the instances exceeds {}", use_path, name, Self::MAX_INSTANCES,
+                            )))?;
+                            for args in instances {
+                                let (instance, body) = Self::instantiate(template, &instance, args, &loops)
+                                    .map_err(|err| error.err(format!("'{}' - template '{}': {}", use_path, name, err)))?;
+                                log::trace!("{}.expand_node | '{}' - template '{}' as '{}'", self.dbg, path, name, instance);
+                                stack.push(name.clone());
+                                let body = self.expand_node(body, path, templates, stack, budget, sources)?;
+                                stack.pop();
+                                let body = match body {
+                                    Value::Mapping(body) => body,
+                                    _ => Mapping::new(),
+                                };
+                                for (key, value) in body {
+                                    let key_path = ConfPath::join(path, key.as_str().unwrap_or_default());
+                                    if let Some(file) = sources.file(&use_path).map(|file| file.to_owned()) {
+                                        sources.insert(&key_path, file);
+                                    }
+                                    if let Some(position) = sources.position(&use_path) {
+                                        sources.insert_position(&key_path, position);
+                                    }
+                                    Self::insert(&error, &mut expanded, key, value, path)?;
+                                }
+                            }
+                        }
+                        None => {
+                            let value = self.expand_node(value, &use_path, templates, stack, budget, sources)?;
+                            Self::insert(&error, &mut expanded, key, value, path)?;
+                        }
+                    }
+                }
+                Ok(Value::Mapping(expanded))
+            }
+            Value::Sequence(items) => {
+                let items = items.into_iter()
+                    .map(|item| self.expand_node(item, path, templates, stack, budget, sources))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Sequence(items))
+            }
+            conf => Ok(conf),
+        }
+    }
+    ///
+    /// Returns the instance name and the body of the `template` with the parameters substituted
+    /// - `args` - the arguments of the instance, may refer to the range parameters `loops`
+    fn instantiate(template: &Template, instance: &str, args: Mapping, loops: &[Value]) -> Result<(String, Value), String> {
+        let args = match Self::substitute(Value::Mapping(args.clone()), &args, false)? {
+            Value::Mapping(substituted) => substituted,
+            _ => args,
+        };
+        let instance = Self::interpolate(instance, &args, false)?;
+        let mut params = Mapping::new();
+        if !template.params.is_empty() {
+            for key in args.keys() {
+                if !template.params.contains_key(key) && !loops.contains(key) {
+                    return Err(format!(
+                        "unknown parameter '{}' for '{}', expected: {:?}",
+                        Self::key_str(key), instance, template.params.keys().map(Self::key_str).collect::<Vec<_>>(),
+                    ));
+                }
+            }
+        }
+        for (key, default) in &template.params {
+            match (args.get(key), default) {
+                (Some(value), _) => params.insert(key.clone(), value.clone()),
+                (None, Value::Null) => return Err(format!("parameter '{}' is required for '{}'", Self::key_str(key), instance)),
+                (None, default) => params.insert(key.clone(), default.clone()),
+            };
+        }
+        for (key, value) in args {
+            if !params.contains_key(&key) {
+                params.insert(key, value);
+            }
+        }
+        params.insert(Value::String(Self::NAME.to_owned()), Value::String(instance.clone()));
+        let body = Self::substitute(Value::Mapping(template.body.clone()), &params, false)
+            .map_err(|err| format!("'{}': {}", instance, err))?;
+        Ok((instance, body))
+    }
+    ///
+    /// Returns the arguments of the each instance, the ranges are unrolled
+    /// - Error if the number of the instances exceeds [ConfTemplates::MAX_INSTANCES]
+    fn instances(args: Mapping) -> Result<Vec<Mapping>, String> {
+        let mut instances = vec![Mapping::new()];
+        for (key, value) in args {
+            match value.as_str().and_then(Self::range) {
+                Some(Err(err)) => return Err(err),
+                Some(Ok(range)) => {
+                    if instances.len().saturating_mul(range.len()) > Self::MAX_INSTANCES {
+                        return Err(format!("number of the instances exceeds {}", Self::MAX_INSTANCES));
+                    }
+                    let mut unrolled = vec![];
+                    for instance in instances {
+                        for item in &range {
+                            let mut instance = instance.clone();
+                            instance.insert(key.clone(), Value::Number((*item).into()));
+                            unrolled.push(instance);
+                        }
+                    }
+                    instances = unrolled;
+                }
+                None => instances.iter_mut().for_each(|instance| {
+                    instance.insert(key.clone(), value.clone());
+                }),
+            }
+        }
+        Ok(instances)
+    }
+    ///
+    /// Returns the values of the range `1..4` / `1..=4`, `None` if the `text` is not a range
+    /// - Error if the range is empty, reversed or longer than [ConfTemplates::MAX_INSTANCES]
+    fn range(text: &str) -> Option<Result<Vec<i64>, String>> {
+        let (start, end) = text.split_once("..")?;
+        let start: i64 = start.trim().parse().ok()?;
+        let (end, inclusive) = match end.strip_prefix('=') {
+            Some(end) => (end.trim().parse::<i64>().ok()?, true),
+            None => (end.trim().parse::<i64>().ok()?, false),
+        };
+        let len = i128::from(end) - i128::from(start) + i128::from(inclusive);
+        if len <= 0 {
+            return Some(Err(format!("range '{}' has no values, start must be less than end", text)));
+        }
+        if len > Self::MAX_INSTANCES as i128 {
+            return Some(Err(format!("range '{}' exceeds {} values", text, Self::MAX_INSTANCES)));
+        }
+        match inclusive {
+            true => Some(Ok((start..=end).collect())),
+            false => Some(Ok((start..end).collect())),
+        }
+    }
+    ///
+    /// Returns the `conf` with `$(...)` in the keys and the string values replaced by the `params`
+    /// - `lenient` - `$(...)` not resolved by the `params` are kept,
+    ///   used for the nested `use template`, resolved later by its own arguments
+    fn substitute(conf: Value, params: &Mapping, lenient: bool) -> Result<Value, String> {
+        match conf {
+            Value::Mapping(mapping) => {
+                let mut substituted = Mapping::new();
+                for (key, value) in mapping {
+                    let lenient = lenient || key.as_str().is_some_and(|key| matches!(Self::template_use(key), Ok(Some(_))));
+                    let key = match key {
+                        Value::String(key) => Value::String(Self::interpolate(&key, params, lenient)?),
+                        key => key,
+                    };
+                    substituted.insert(key, Self::substitute(value, params, lenient)?);
+                }
+                Ok(Value::Mapping(substituted))
+            }
+            Value::Sequence(items) => items.into_iter()
+                .map(|item| Self::substitute(item, params, lenient))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Sequence),
+            Value::String(value) => {
+                let single = value.starts_with("$(") && value.find(')') == Some(value.len() - 1);
+                match single {
+                    true => match Self::evaluate(&value[2..value.len() - 1], params) {
+                        Err(_) if lenient => Ok(Value::String(value)),
+                        result => result,
+                    },
+                    false => Self::interpolate(&value, params, lenient).map(Value::String),
+                }
+            }
+            conf => Ok(conf),
+        }
+    }
+    ///
+    /// Returns the `text` with `$(...)` replaced by the `params`
+    /// - `lenient` - `$(...)` not resolved by the `params` are kept
+    fn interpolate(text: &str, params: &Mapping, lenient: bool) -> Result<String, String> {
+        if !text.contains("$(") {
+            return Ok(text.to_owned());
+        }
+        let mut interpolated = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("$(") {
+            if rest[..start].ends_with('$') {
+                interpolated.push_str(&rest[..start - 1]);
+                interpolated.push_str("$(");
+                rest = &rest[start + 2..];
+                continue;
+            }
+            interpolated.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find(')') else {
+                return Err(format!("unclosed '$(' in: '{}'", text));
+            };
+            match Self::evaluate(&rest[start + 2..start + end], params) {
+                Err(_) if lenient => interpolated.push_str(&rest[start..start + end + 1]),
+                Err(err) => return Err(err),
+                Ok(Value::String(value)) => interpolated.push_str(&value),
+                Ok(Value::Number(value)) => interpolated.push_str(&value.to_string()),
+                Ok(Value::Bool(value)) => interpolated.push_str(&value.to_string()),
+                Ok(other) => return Err(format!("scalar expected in '{}', but found: {:?}", text, other)),
+            }
+            rest = &rest[start + end + 1..];
+        }
+        interpolated.push_str(rest);
+        Ok(interpolated)
+    }
+    ///
+    /// Returns the value of the parameter or the integer expression `expr`
+    /// - Error if the result overflows `i64`
+    fn evaluate(expr: &str, params: &Mapping) -> Result<Value, String> {
+        let overflow = || format!("integer overflow in '$({})'", expr);
+        let tokens = Self::tokens(expr)?;
+        if let [Token::Name(name)] = tokens.as_slice() {
+            return params.get(name.as_str()).cloned().ok_or_else(|| format!("parameter '{}' is not set", name));
+        }
+        let mut sum = 0i64;
+        let mut product = 1i64;
+        let mut sign = 1i64;
+        let mut operand = true;
+        for token in tokens {
+            match (token, operand) {
+                (Token::Int(value), true) => {
+                    product = product.checked_mul(value).ok_or_else(overflow)?;
+                    operand = false;
+                }
+                (Token::Name(name), true) => {
+                    let value = match params.get(name.as_str()) {
+                        Some(value) => value.as_i64().ok_or_else(|| format!("parameter '{}' must be an integer, but found: {:?}", name, value))?,
+                        None => return Err(format!("parameter '{}' is not set", name)),
+                    };
+                    product = product.checked_mul(value).ok_or_else(overflow)?;
+                    operand = false;
+                }
+                (Token::Op('*'), false) => operand = true,
+                (Token::Op(op), false) => {
+                    sum = product.checked_mul(sign).and_then(|term| sum.checked_add(term)).ok_or_else(overflow)?;
+                    product = 1;
+                    sign = if op == '-' { -1 } else { 1 };
+                    operand = true;
+                }
+                (token, _) => return Err(format!("unexpected {:?} in '$({})'", token, expr)),
+            }
+        }
+        if operand {
+            return Err(format!("incomplete expression '$({})'", expr));
+        }
+        let sum = product.checked_mul(sign).and_then(|term| sum.checked_add(term)).ok_or_else(overflow)?;
+        Ok(Value::Number(sum.into()))
+    }
+    ///
+    /// Returns the tokens of the expression
+    fn tokens(expr: &str) -> Result<Vec<Token>, String> {
+        let mut tokens = vec![];
+        let mut chars = expr.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            match c {
+                c if c.is_whitespace() => {
+                    chars.next();
+                }
+                '+' | '-' | '*' => {
+                    tokens.push(Token::Op(c));
+                    chars.next();
+                }
+                c if c.is_ascii_digit() => {
+                    let mut value = String::new();
+                    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+                        value.push(c);
+                    }
+                    tokens.push(Token::Int(value.parse().map_err(|_| format!("invalid number '{}' in '$({})'", value, expr))?));
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    let mut name = String::new();
+                    while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                        name.push(c);
+                    }
+                    tokens.push(Token::Name(name));
+                }
+                c => return Err(format!("unexpected '{}' in '$({})'", c, expr)),
+            }
+        }
+        Ok(tokens)
+    }
+    ///
+    /// Returns the name of the template, if the `key` is `template <Name>`
+    fn template_name(key: &str) -> Option<String> {
+        match key.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [Self::TEMPLATE, name] => Some((*name).to_owned()),
+            _ => None,
+        }
+    }
+    ///
+    /// Returns the template and the instance names, if the `key` is `use template <Name> [as <Instance>]`
+    fn template_use(key: &str) -> Result<Option<(String, String)>, String> {
+        match key.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [Self::USE, Self::TEMPLATE, name] => Ok(Some(((*name).to_owned(), (*name).to_owned()))),
+            [Self::USE, Self::TEMPLATE, name, "as", instance @ ..] if !instance.is_empty() => Ok(Some(((*name).to_owned(), instance.join(" ")))),
+            [Self::USE, Self::TEMPLATE, ..] => Err(format!("'{} {} <Name> as <Instance>' expected, but found: '{}'", Self::USE, Self::TEMPLATE, key)),
+            _ => Ok(None),
+        }
+    }
+    ///
+    /// Returns the key as string for the messages
+    fn key_str(key: &Value) -> String {
+        match key {
+            Value::String(key) => key.clone(),
+            key => serde_yaml::to_string(key).map(|key| key.trim_end().to_owned()).unwrap_or_default(),
+        }
+    }
+    ///
+    /// Inserts the node into the expanded mapping, duplicated keys are errors
+    fn insert(error: &Error, expanded: &mut Mapping, key: Value, value: Value, path: &str) -> Result<(), Error> {
+        if expanded.contains_key(&key) {
+            return Err(error.err(format!("Duplicated key '{}' in '{}'", Self::key_str(&key), path)));
+        }
+        expanded.insert(key, value);
+        Ok(())
+    }
+}
+//
+//
+impl std::fmt::Debug for ConfTemplates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfTemplates")
+            .field("dbg", &self.dbg)
+            .finish()
+    }
+}
+///
+/// The template defined in the configuration
+struct Template {
+    path: String,
+    params: Mapping,
+    body: Mapping,
+}
+///
+/// The token of the template expression
+#[derive(Debug)]
+enum Token {
+    Int(i64),
+    Name(String),
+    Op(char),
+}
@@ -8,7 +8,7 @@ use crate::{
         task::functions::{FnConfKeywd, FnConfKindName},
     },
 };
-use super::{conf_duration::ConfDuration, conf_keywd::ConfKeywd, conf_kind::ConfKind, diag_keywd::DiagKeywd};
+use super::{conf_duration::ConfDuration, conf_keywd::ConfKeywd, conf_kind::ConfKind, conf_path::ConfPath, diag_keywd::DiagKeywd};
 ///
 /// ConfTree holds sede_yaml::Value and it key
 /// for root key = ""
@@ -70,11 +70,14 @@ impl ConfTree {
     /// iterate across all sub nodes
     pub fn sub_nodes(&self) -> Option<impl Iterator<Item = ConfTree> + '_> {
         if self.conf.is_mapping() {
-            let iter = self.conf.as_mapping().unwrap().into_iter().map( |(key, value)| {
-                ConfTree::new(
-                    key.as_str().unwrap(),
-                    value.clone(),
-                )
+            let iter = self.conf.as_mapping().unwrap().into_iter().filter_map( |(key, value)| {
+                match key.as_str() {
+                    Some(key) => Some(ConfTree::new(key, value.clone())),
+                    None => {
+                        log::warn!("{}.sub_nodes | String key expected, but found: {:?}, skipped", self.id, key);
+                        None
+                    }
+                }
             });
             Some(iter)
         } else {
@@ -82,6 +85,97 @@ impl ConfTree {
         }
     }
     ///
+    /// Returns the first node matched by the `path`, see [ConfPath]
+    /// 
+    /// ```ignore
+    /// let max_length = conf.at("service Task Task1/in queue recv-queue/max-length");
+    /// ```
+    pub fn at(&self, path: impl Into<ConfPath>) -> Option<ConfTree> {
+        self.select(path).into_iter().next().map(|(_, node)| node)
+    }
+    ///
+    /// Returns the value of the first node matched by the `path`, see [ConfPath]
+    /// 
+    /// ```ignore
+    /// let max_length: Option<i64> = conf.get_at("service Task Task1/in queue recv-queue/max-length");
+    /// ```
+    pub fn get_at<T>(&self, path: impl Into<ConfPath>) -> Option<T> where ConfTree: ConfTreeGet<T> {
+        let (parent, key) = Self::parent_of(self.at(path)?);
+        ConfTreeGet::<T>::get(&parent, key)
+    }
+    ///
+    /// Returns the duration of the first node matched by the `path`, see [ConfPath] and [ConfTree::get_duration]
+    pub fn duration_at(&self, path: impl Into<ConfPath>) -> Result<Duration, Error> {
+        let path = path.into();
+        let error = Error::new(&self.id, "duration_at");
+        match self.at(path.clone()) {
+            Some(node) => {
+                let (parent, key) = Self::parent_of(node);
+                parent.get_duration(key).map_err(|err| error.pass(err))
+            }
+            None => Err(error.err(format!("Path {:?} - not found", path))),
+        }
+    }
+    ///
+    /// Returns all nodes matched by the `path` with their full key paths, see [ConfPath]
+    /// 
+    /// ```ignore
+    /// for (path, task) in conf.select("service Task *") { ... }
+    /// ```
+    pub fn select(&self, path: impl Into<ConfPath>) -> Vec<(String, ConfTree)> {
+        let path = path.into();
+        let mut found = vec![(String::new(), self.clone())];
+        for index in 0..path.len() {
+            found = found.into_iter().flat_map(|(parent, node)| {
+                node.mapping_nodes()
+                    .into_iter()
+                    .filter(|sub_node| path.matches(index, &sub_node.key))
+                    .map(|sub_node| (ConfPath::join(&parent, &sub_node.key), sub_node))
+                    .collect::<Vec<_>>()
+            }).collect();
+        }
+        found
+    }
+    ///
+    /// Returns all nodes on any depth with the key matched by the `key` pattern, with their full key paths
+    /// - The `key` is a single key of the [ConfPath], for example `point *`
+    /// - Nodes are returned in the depth-first order
+    pub fn find_all(&self, key: &str) -> Vec<(String, ConfTree)> {
+        let key = ConfPath::new(key);
+        let mut found = vec![];
+        Self::find_all_(&key, "", self, &mut found);
+        found
+    }
+    ///
+    /// Collects nodes matched by the `key` into the `found`
+    fn find_all_(key: &ConfPath, parent: &str, node: &ConfTree, found: &mut Vec<(String, ConfTree)>) {
+        for sub_node in node.mapping_nodes() {
+            let path = ConfPath::join(parent, &sub_node.key);
+            if key.matches(0, &sub_node.key) {
+                found.push((path.clone(), sub_node.clone()));
+            }
+            Self::find_all_(key, &path, &sub_node, found);
+        }
+    }
+    ///
+    /// Returns the `node` wrapped into the parent, with its key,
+    /// so the getters by the key can be applied
+    fn parent_of(node: ConfTree) -> (ConfTree, String) {
+        let key = node.key;
+        let mapping = serde_yaml::Mapping::from_iter([(serde_yaml::Value::String(key.clone()), node.conf)]);
+        (ConfTree::new_root(serde_yaml::Value::Mapping(mapping)), key)
+    }
+    ///
+    /// Returns sub nodes having string keys
+    fn mapping_nodes(&self) -> Vec<ConfTree> {
+        match self.conf.as_mapping() {
+            Some(mapping) => mapping.iter()
+                .filter_map(|(key, value)| key.as_str().map(|key| ConfTree::new(key, value.clone())))
+                .collect(),
+            None => vec![],
+        }
+    }
+    ///
     /// Returns keys, excluding specified
     /// - `exclude` - list of keys to be filtered
     pub fn keys(&self, exclude: &[impl AsRef<str>]) -> Vec<String> {
@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+///
+/// The configuration node violating the [ConfSchema](super::ConfSchema)
+/// - `path` - the key path of the node, keys joined by `/`
+/// - `message` - human readable description of the violation
+/// - `file`, `line`, `column` - the location of the key, where available
+///
+/// Displayed as `file:line:column: 'path' - message`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfViolation {
+    pub path: String,
+    pub message: String,
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+//
+//
+impl ConfViolation {
+    ///
+    /// Returns [ConfViolation] new instance without location
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+        }
+    }
+    ///
+    /// Returns [ConfViolation] located in the `file` at the `position` (line, column)
+    pub fn located(self, file: Option<PathBuf>, position: Option<(usize, usize)>) -> Self {
+        Self {
+            file,
+            line: position.map(|(line, _)| line),
+            column: position.map(|(_, column)| column),
+            ..self
+        }
+    }
+}
+//
+//
+impl std::fmt::Display for ConfViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+            if let Some(column) = self.column {
+                write!(f, "{}:", column)?;
+            }
+        }
+        if self.file.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "'{}' - {}", self.path, self.message)
+    }
+}
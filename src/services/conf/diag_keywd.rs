@@ -7,6 +7,30 @@ use strum_macros::{AsRefStr, EnumIter};
 pub enum DiagKeywd {
     Status,
     Connection,
+    ///
+    /// Minimal execution time of the `ServiceCycle`, us
+    #[strum(serialize = "cyclemin")]
+    CycleMin,
+    ///
+    /// Average execution time of the `ServiceCycle`, us
+    #[strum(serialize = "cycleavg")]
+    CycleAvg,
+    ///
+    /// Maximal execution time of the `ServiceCycle`, us
+    #[strum(serialize = "cyclemax")]
+    CycleMax,
+    ///
+    /// Maximal deviation of the `ServiceCycle` start from the scheduled, us
+    #[strum(serialize = "cyclejitter")]
+    CycleJitter,
+    ///
+    /// Number of the `ServiceCycle` overruns
+    #[strum(serialize = "cycleoverruns")]
+    CycleOverruns,
+    ///
+    /// Longest overrun of the `ServiceCycle`, us
+    #[strum(serialize = "cycleoverrunmax")]
+    CycleOverrunMax,
 }
 //
 //
@@ -18,6 +42,12 @@ impl DiagKeywd {
         match "" {
             _ if value.ends_with(Self::Status.as_str())       => Self::Status,
             _ if value.ends_with(Self::Connection.as_str())   => Self::Connection,
+            _ if value.ends_with(Self::CycleMin.as_str())     => Self::CycleMin,
+            _ if value.ends_with(Self::CycleAvg.as_str())     => Self::CycleAvg,
+            _ if value.ends_with(Self::CycleMax.as_str())     => Self::CycleMax,
+            _ if value.ends_with(Self::CycleJitter.as_str())  => Self::CycleJitter,
+            _ if value.ends_with(Self::CycleOverruns.as_str())    => Self::CycleOverruns,
+            _ if value.ends_with(Self::CycleOverrunMax.as_str())  => Self::CycleOverrunMax,
             _ => panic!("DiagKeywd.from_str | Diagnosis point '{}' - does not supported", value)
         }
     }
@@ -0,0 +1,74 @@
+use sal_core::error::Error;
+use super::conf_tree::ConfTree;
+///
+/// ### Typed configuration parsed from the [ConfTree] node
+/// - `parent` - the name of the application
+///
+/// Usually implemented by the [conf_struct](crate::conf_struct) macro
+pub trait FromConfTree: Sized {
+    ///
+    /// Returns the config parsed from the `conf` node
+    fn from_conf_tree(parent: &str, conf: &ConfTree) -> Result<Self, Error>;
+}
+///
+/// ### Declares the config struct and implements [FromConfTree] for it
+/// - Each field is read by the [ConfReader](crate::services::conf::ConfReader) method named after `=`:
+///   - `name` - the [Name](crate::services::entity::Name) of the service
+///   - `required("key")` / `optional("key")` / `or("key", default)` - the parameter of the [ConfField](crate::services::conf::ConfField) type
+///   - `in_queue` - the `in queue <name>` as [ConfInQueue](crate::services::conf::ConfInQueue)
+///   - `send_to` - the `send-to` links
+///   - `cycle` - the optional `cycle` duration
+///   - `subscribe` - the `subscribe` as [ConfSubscribe](crate::services::subscription::ConfSubscribe)
+///   - `diagnosis` - the `diagnosis` points
+/// - Errors are `Parameter '<key>' - <reason>`, reported by the first invalid field
+///
+/// **Example**
+/// ```ignore
+/// conf_struct! {
+///     ///
+///     /// Configuration of the `Drive`
+///     #[derive(Debug, Clone)]
+///     pub struct DriveConf {
+///         pub(crate) name: Name = name,
+///         pub(crate) rx: ConfInQueue = in_queue,
+///         pub(crate) send_to: Vec<LinkName> = send_to,
+///         pub(crate) cycle: Option<Duration> = cycle,
+///         pub(crate) address: String = required("address"),
+///         pub(crate) timeout: Duration = or("timeout", Duration::from_secs(3)),
+///     }
+/// }
+/// let conf = DriveConf::from_conf_tree("App", &conf)?;
+/// ```
+#[macro_export]
+macro_rules! conf_struct {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident: $type:ty = $method:ident $(($($arg:expr),* $(,)?))?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: $type,
+            )*
+        }
+        //
+        //
+        impl $crate::services::conf::FromConfTree for $name {
+            fn from_conf_tree(parent: &str, conf: &$crate::services::conf::ConfTree) -> Result<Self, ::sal_core::error::Error> {
+                let reader = $crate::services::conf::ConfReader::new(parent, stringify!($name), conf)?;
+                let error = ::sal_core::error::Error::new(reader.dbg(), "from_conf_tree");
+                Ok(Self {
+                    $(
+                        $field: reader.$method($($($arg),*)?).map_err(|err| error.pass(err))?,
+                    )*
+                })
+            }
+        }
+    };
+}
@@ -2,15 +2,39 @@
 //! # Common entities and tools for configuration stored in yaml
 //! 
 mod conf_duration;
+mod conf_field;
+mod conf_in_queue;
 mod conf_keywd;
 mod conf_kind;
+mod conf_loader;
+mod conf_path;
+mod conf_positions;
+mod conf_reader;
+mod conf_schema;
+mod conf_schema_type;
+mod conf_sources;
+mod conf_templates;
 mod conf_tree;
+mod conf_violation;
 mod diag_keywd;
+mod from_conf_tree;
 mod services_conf;
 
 pub use conf_duration::*;
+pub use conf_field::*;
+pub use conf_in_queue::*;
 pub use conf_keywd::*;
 pub use conf_kind::*;
+pub use conf_loader::*;
+pub use conf_path::*;
+pub use conf_positions::*;
+pub use conf_reader::*;
+pub use conf_schema::*;
+pub use conf_schema_type::*;
+pub use conf_sources::*;
+pub use conf_templates::*;
 pub use conf_tree::*;
+pub use conf_violation::*;
 pub use diag_keywd::*;
+pub use from_conf_tree::*;
 pub use services_conf::*;
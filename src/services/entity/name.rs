@@ -97,6 +97,17 @@ impl Name {
     pub fn me(&self) -> String {
         self.me.clone()
     }
+    ///
+    /// Returns the name of the another service, resolved within the same parent if relative
+    /// - `/App/MultiQueue` => `/App/MultiQueue`
+    /// - `MultiQueue` => `/<parent>/MultiQueue`
+    pub fn resolve(&self, service: &str) -> String {
+        if service.starts_with('/') {
+            service.to_owned()
+        } else {
+            Name::new(self.parent(), service).join()
+        }
+    }
 }
 //
 // 
@@ -75,6 +75,7 @@
 //! </details>
 mod point;
 mod point_hlr;
+mod point_lines;
 mod point_tx_id;
 mod point_config_type;
 mod point_config;
@@ -84,6 +85,7 @@ mod point_config_filters;
 
 pub use point::*;
 pub use point_hlr::*;
+pub use point_lines::*;
 pub use point_tx_id::*;
 pub use point_config_type::*;
 pub use point_config::*;
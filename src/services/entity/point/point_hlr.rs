@@ -1,7 +1,10 @@
 use chrono::DateTime;
-use crate::services::{
-    entity::{Cot, Status},
-    types::Bool,
+use crate::{
+    kernel::clock::GlobalClock,
+    services::{
+        entity::{Cot, Status},
+        types::Bool,
+    },
 };
 ///
 /// Holds the unit of the information 
@@ -40,7 +43,7 @@ impl<T> PointHlr<T> {
 // 
 impl PointHlr<Bool> {
     ///
-    /// creates `Point<Bool>` with given `name` & `value`, taking current timestamp, `Status::Ok`, `Direction::Read`
+    /// creates `Point<Bool>` with given `name` & `value`, taking current timestamp of the `GlobalClock`, `Status::Ok`, `Direction::Read`
     pub fn new_bool(tx_id: usize, name: &str, value: bool) -> PointHlr<Bool> {
         PointHlr {
             tx_id,
@@ -48,7 +51,7 @@ impl PointHlr<Bool> {
             value: Bool(value),
             status: Status::Ok,
             cot: Cot::default(),
-            timestamp: chrono::offset::Utc::now(),
+            timestamp: GlobalClock::utc(),
         }
     }
     ///
@@ -129,7 +132,7 @@ impl PointHlr<Bool> {
 impl PointHlr<i64> {
     ///
     /// Creates `Point<i64>` with given `name` & `value`,
-    /// taking current timestamp, `Status::Ok`, `Direction::Read`
+    /// taking current timestamp of the `GlobalClock`, `Status::Ok`, `Direction::Read`
     pub fn new_int(tx_id: usize, name: &str, value: i64) -> PointHlr<i64> {
         PointHlr {
             tx_id,
@@ -137,7 +140,7 @@ impl PointHlr<i64> {
             value,
             status: Status::Ok,
             cot: Cot::default(),
-            timestamp: chrono::offset::Utc::now(),
+            timestamp: GlobalClock::utc(),
         }
     }
     ///
@@ -244,7 +247,7 @@ impl PointHlr<i64> {
 // 
 impl PointHlr<f32> {
     ///
-    /// creates `Point<f32>` with given `name` & `value`, taking current timestamp, `Status::Ok`, `Direction::Read`
+    /// creates `Point<f32>` with given `name` & `value`, taking current timestamp of the `GlobalClock`, `Status::Ok`, `Direction::Read`
     pub fn new_real(tx_id: usize, name: &str, value: f32) -> PointHlr<f32> {
         PointHlr {
             tx_id,
@@ -252,7 +255,7 @@ impl PointHlr<f32> {
             value,
             status: Status::Ok,
             cot: Cot::default(),
-            timestamp: chrono::offset::Utc::now(),
+            timestamp: GlobalClock::utc(),
         }
     }
     ///
@@ -359,7 +362,7 @@ impl PointHlr<f32> {
 // 
 impl PointHlr<f64> {
     ///
-    /// creates `Point<f64>` with given `name` & `value`, taking current timestamp, `Status::Ok`, `Direction::Read`
+    /// creates `Point<f64>` with given `name` & `value`, taking current timestamp of the `GlobalClock`, `Status::Ok`, `Direction::Read`
     pub fn new_double(tx_id: usize, name: &str, value: f64) -> PointHlr<f64> {
         PointHlr {
             tx_id,
@@ -367,7 +370,7 @@ impl PointHlr<f64> {
             value,
             status: Status::Ok,
             cot: Cot::default(),
-            timestamp: chrono::offset::Utc::now(),
+            timestamp: GlobalClock::utc(),
         }
     }
     ///
@@ -474,7 +477,7 @@ impl PointHlr<f64> {
 // 
 impl PointHlr<String> {
     ///
-    /// creates `Point<String>`` with given `name` & `value`, taking current timestamp, `Status::Ok`, `Direction::Read`
+    /// creates `Point<String>`` with given `name` & `value`, taking current timestamp of the `GlobalClock`, `Status::Ok`, `Direction::Read`
     pub fn new_string(tx_id: usize, name: &str, value: impl Into<String>) -> PointHlr<String> {
         PointHlr {
             tx_id,
@@ -482,7 +485,7 @@ impl PointHlr<String> {
             value: value.into(),
             status: Status::Ok,
             cot: Cot::default(),
-            timestamp: chrono::offset::Utc::now(),
+            timestamp: GlobalClock::utc(),
         }
     }
 }
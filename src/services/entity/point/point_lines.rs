@@ -0,0 +1,44 @@
+use sal_core::error::Error;
+use super::point::Point;
+///
+/// Encodes / decodes [Point] as a single line of JSON, terminated by `\n`
+///
+/// ```json
+/// {"type":"Int","value":3,"name":"/App/Service/Point.Name","status":0,"cot":"Inf","timestamp":"2024-02-19T12:16:57.648504907+00:00"}
+/// ```
+pub struct PointLines;
+//
+//
+impl PointLines {
+    ///
+    /// Returns the JSON line of the `point`, including trailing `\n`
+    pub fn encode(point: &Point) -> Result<Vec<u8>, Error> {
+        let mut line = serde_json::to_vec(point)
+            .map_err(|err| Error::new("PointLines", "encode").pass_with(format!("Point '{}' serialize error", point.name()), err.to_string()))?;
+        line.push(b'\n');
+        Ok(line)
+    }
+    ///
+    /// Returns [Point] parsed from the JSON `line`, trailing `\r\n` is ignored
+    /// - `tx_id` - the producer of the point, identifies the service the point was received by
+    pub fn decode(line: &[u8], tx_id: usize) -> Result<Point, Error> {
+        let line = line.trim_ascii_end();
+        let point: Point = serde_json::from_slice(line)
+            .map_err(|err| Error::new("PointLines", "decode").pass_with(
+                format!("Point deserialize error in: '{}'", String::from_utf8_lossy(line)),
+                err.to_string(),
+            ))?;
+        Ok(Self::with_tx_id(point, tx_id))
+    }
+    ///
+    /// Returns the `point` with replaced `tx_id`
+    fn with_tx_id(point: Point, tx_id: usize) -> Point {
+        match point {
+            Point::Bool(mut point) => { point.tx_id = tx_id; Point::Bool(point) }
+            Point::Int(mut point) => { point.tx_id = tx_id; Point::Int(point) }
+            Point::Real(mut point) => { point.tx_id = tx_id; Point::Real(point) }
+            Point::Double(mut point) => { point.tx_id = tx_id; Point::Double(point) }
+            Point::String(mut point) => { point.tx_id = tx_id; Point::String(point) }
+        }
+    }
+}
@@ -1,40 +1,229 @@
+use std::{pin::Pin, sync::Arc, task::{Context, Poll, Waker}, time::Duration};
 use log::error;
+use parking_lot::Condvar;
 use sal_core::error::Error;
-use crate::{services::types::TypeOf, sync::channel::{self, Receiver, Sender}, thread_pool::Scheduler};
+use crate::{kernel::clock::GlobalClock, services::types::TypeOf, sync::{channel::Sender, Mutex}, thread_pool::Scheduler};
 ///
-/// Contains future callback
+/// Callback receiving the result of the [Future], registered by the combinators
+type OnComplete<T> = Box<dyn FnOnce(Result<T, String>) + Send>;
+///
+/// State of the value of the [Future]
+enum FutureState<T> {
+    /// Value is not yet received
+    Pending,
+    /// Value is received and not yet taken
+    Ready(T),
+    /// Value is taken by the [Future]
+    Taken,
+    /// Cancelled by the [Future], value will be dropped
+    Cancelled,
+    /// [Sink] is failed or dropped without value
+    Closed(String),
+}
+///
+/// Mutable part of the state shared between [Future] and [Sink]
+struct FutureInner<T> {
+    state: FutureState<T>,
+    waker: Option<Waker>,
+    on_complete: Option<OnComplete<T>>,
+    on_cancel: Option<Box<dyn FnOnce() + Send>>,
+}
+///
+/// State shared between [Future] and [Sink]
+struct FutureShared<T> {
+    inner: Mutex<FutureInner<T>>,
+    changed: Condvar,
+}
+//
+//
+impl<T> FutureShared<T> {
+    ///
+    /// Returns the result if completed, the value can be taken only once
+    fn take(inner: &mut FutureInner<T>) -> Option<Result<T, String>> {
+        match std::mem::replace(&mut inner.state, FutureState::Taken) {
+            FutureState::Pending => {
+                inner.state = FutureState::Pending;
+                None
+            }
+            FutureState::Ready(value) => Some(Ok(value)),
+            FutureState::Taken => Some(Err("Value already taken".to_owned())),
+            FutureState::Cancelled => {
+                inner.state = FutureState::Cancelled;
+                Some(Err("Cancelled".to_owned()))
+            }
+            FutureState::Closed(message) => {
+                inner.state = FutureState::Closed(message.clone());
+                Some(Err(message))
+            }
+        }
+    }
+    ///
+    /// Completes the pending [Future] with the `result`,
+    /// returns the `result` back if already completed or cancelled
+    fn complete(&self, result: Result<T, String>) -> Result<(), Result<T, String>> {
+        let mut inner = self.inner.lock();
+        if !matches!(inner.state, FutureState::Pending) {
+            return Err(result);
+        }
+        let on_complete = inner.on_complete.take();
+        inner.on_cancel = None;
+        match (on_complete, result) {
+            (Some(on_complete), result) => {
+                inner.state = match &result {
+                    Ok(_) => FutureState::Taken,
+                    Err(message) => FutureState::Closed(message.clone()),
+                };
+                drop(inner);
+                on_complete(result);
+            }
+            (None, result) => {
+                inner.state = match result {
+                    Ok(value) => FutureState::Ready(value),
+                    Err(message) => FutureState::Closed(message),
+                };
+                let waker = inner.waker.take();
+                drop(inner);
+                self.changed.notify_all();
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+        }
+        Ok(())
+    }
+    ///
+    /// Cancels the pending [Future], returns `false` if already completed
+    fn cancel(&self) -> bool {
+        let mut inner = self.inner.lock();
+        if !matches!(inner.state, FutureState::Pending) {
+            return false;
+        }
+        inner.state = FutureState::Cancelled;
+        let on_complete = inner.on_complete.take();
+        let on_cancel = inner.on_cancel.take();
+        let waker = inner.waker.take();
+        drop(inner);
+        self.changed.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        if let Some(on_cancel) = on_cancel {
+            on_cancel();
+        }
+        if let Some(on_complete) = on_complete {
+            on_complete(Err("Cancelled".to_owned()));
+        }
+        true
+    }
+}
+///
+/// ### One-shot result of the pending operation
+/// - The value is sent by the corresponding [Sink], can be taken only once
+/// - Blocking: `wait`, `wait_timeout`, `then`, not blocking: `try_get`
+/// - Can be awaited from the async code, implements `std::future::Future`
+/// - Combinators `map`, `and_then`, `join_all`, `select` are not blocking,
+///   the continuation is executed in the thread of the [Sink]
+/// - `cancel` - the [Sink] can check `is_cancelled` to skip the work,
+///   cancellation of the combined future is propagated to the sources
+/// - Fails if the [Sink] is dropped without value or `Sink::fail` is called
+///
+/// **Example**
+/// ```ignore
+/// let points = services.points(&dbg);
+/// let ids = services.point_ids(&dbg, PointsRequest::All);
+/// let (points, ids) = match Future::join_all(vec![points.map(|p| p.len()), ids.map(|i| i.len())]).wait_timeout(timeout) {
+///     Ok(lens) => (lens[0], lens[1]),
+///     Err(err) => ...
+/// };
+/// let points = services.points(&dbg).await?;
+/// ```
 pub struct Future<T> {
-    recv: Receiver<T>
-    // exec: Box<dyn Fn() -> T>,
-    // then: Box<dyn Fn(T) -> T>,
+    shared: Arc<FutureShared<T>>,
 }
 //
 //
 impl<T: Send + 'static> Future<T> {
+    ///
+    /// Maximum real time of the single wait in the `wait_timeout`,
+    /// so the time advanced by the [VirtualClock](crate::kernel::clock::VirtualClock) is noticed
+    const WAIT_INTERVAL: Duration = Duration::from_millis(10);
     ///
     /// Returns Future new instance
     pub fn new() -> (Self, Sink<T>) {
-        let (send, recv) = kanal::bounded(1);
+        let shared = Arc::new(FutureShared {
+            inner: Mutex::new(FutureInner { state: FutureState::Pending, waker: None, on_complete: None, on_cancel: None }),
+            changed: Condvar::new(),
+        });
         (
-            Self { recv },
-            Sink::new(send),
+            Self { shared: shared.clone() },
+            Sink { shared },
         )
     }
-    /// 
+    ///
+    /// Returns Future already completed with the `value`
+    pub fn ready(value: T) -> Self {
+        let (future, sink) = Self::new();
+        sink.add(value);
+        future
+    }
+    ///
     /// Returns value from future
     pub fn wait(&self) -> Result<T, Error> {
-        match self.recv.recv() {
-            Ok(event) => Ok(event),
-            Err(err) => {
-                log::warn!("Future.wait | Recv error: {:?}", err);
-                Err(Error::new("Future", "wait").pass(err.to_string()))
+        let mut inner = self.shared.inner.lock();
+        loop {
+            if let Some(result) = FutureShared::take(&mut inner) {
+                return result.map_err(|err| {
+                    log::warn!("Future.wait | Error: {}", err);
+                    Error::new("Future", "wait").err(err)
+                });
+            }
+            self.shared.changed.wait(&mut inner);
+        }
+    }
+    ///
+    /// Returns value from future, if received within the `timeout`
+    /// - The `timeout` is measured by the [GlobalClock]
+    /// - On timeout the future remains pending, can be waited again
+    pub fn wait_timeout(&self, timeout: Duration) -> Result<T, Error> {
+        let clock = GlobalClock::get();
+        let time = clock.now();
+        let mut inner = self.shared.inner.lock();
+        loop {
+            if let Some(result) = FutureShared::take(&mut inner) {
+                return result.map_err(|err| Error::new("Future", "wait_timeout").err(err));
             }
+            let remaining = timeout.saturating_sub(clock.elapsed(time));
+            if remaining.is_zero() {
+                return Err(Error::new("Future", "wait_timeout").err(format!("Timeout {:?} exceeded", timeout)));
+            }
+            self.shared.changed.wait_for(&mut inner, remaining.min(Self::WAIT_INTERVAL));
+        }
+    }
+    ///
+    /// Returns value if already received, `Ok(None)` if still pending, not blocking
+    pub fn try_get(&self) -> Result<Option<T>, Error> {
+        let mut inner = self.shared.inner.lock();
+        match FutureShared::take(&mut inner) {
+            Some(result) => result.map(Some).map_err(|err| Error::new("Future", "try_get").err(err)),
+            None => Ok(None),
         }
     }
-    /// 
+    ///
+    /// Returns `true` if the value is received, or the future is failed / cancelled
+    pub fn is_done(&self) -> bool {
+        !matches!(self.shared.inner.lock().state, FutureState::Pending)
+    }
+    ///
+    /// Cancels the pending future, the [Sink] observes it by `is_cancelled`
+    /// - The value sent after cancellation is dropped
+    /// - Returns `false` if already completed
+    pub fn cancel(&self) -> bool {
+        self.shared.cancel()
+    }
+    ///
     /// Returns future callback
     pub fn then(&self, on_done: impl Fn(T) -> T, on_err: impl Fn(String) -> T) -> T {
-        match self.recv.recv() {
+        match self.wait() {
             Ok(event) => {
                 (on_done)(event)
             }
@@ -44,46 +233,263 @@ impl<T: Send + 'static> Future<T> {
         }
     }
     ///
+    /// Returns the future of the value converted by `f`, not blocking
+    pub fn map<U: Send + 'static>(self, f: impl FnOnce(T) -> U + Send + 'static) -> Future<U> {
+        let (future, sink) = Future::new();
+        future.on_cancel(&self);
+        self.on_complete(move |result| sink.complete(result.map(f)));
+        future
+    }
+    ///
+    /// Returns the future of the operation started by `f` on the received value, not blocking
+    pub fn and_then<U: Send + 'static>(self, f: impl FnOnce(T) -> Future<U> + Send + 'static) -> Future<U> {
+        let (future, sink) = Future::new();
+        future.on_cancel(&self);
+        let next = Arc::downgrade(&future.shared);
+        self.on_complete(move |result| match result {
+            Ok(value) => {
+                let inner = f(value);
+                if let Some(next) = next.upgrade() {
+                    Future { shared: next }.on_cancel(&inner);
+                }
+                inner.on_complete(move |result| sink.complete(result));
+            }
+            Err(err) => sink.complete(Err(err)),
+        });
+        future
+    }
+    ///
+    /// Returns the future of the all values in the order of the `futures`, not blocking
+    /// - Fails with the first failed future, the rest are cancelled
+    pub fn join_all(futures: Vec<Future<T>>) -> Future<Vec<T>> {
+        if futures.is_empty() {
+            return Future::ready(vec![]);
+        }
+        let (future, sink) = Future::new();
+        let sources: Vec<Arc<FutureShared<T>>> = futures.iter().map(|f| f.shared.clone()).collect();
+        future.on_cancel_all(sources.clone());
+        let values = Arc::new(Mutex::new((
+            futures.iter().map(|_| None).collect(),
+            futures.len(),
+            Some(sink),
+        )));
+        for (index, source) in futures.into_iter().enumerate() {
+            let values = values.clone();
+            let sources = sources.clone();
+            source.on_complete(move |result| {
+                let mut guard = values.lock();
+                let (slots, remaining, sink) = &mut *guard;
+                match result {
+                    Ok(value) => {
+                        slots[index] = Some(value);
+                        *remaining -= 1;
+                        if *remaining == 0 {
+                            if let Some(sink) = sink.take() {
+                                let values = slots.iter_mut().filter_map(|slot| slot.take()).collect();
+                                drop(guard);
+                                sink.add(values);
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        if let Some(sink) = sink.take() {
+                            drop(guard);
+                            sink.fail(format!("Future {}: {}", index, err));
+                            for source in sources {
+                                source.cancel();
+                            }
+                        }
+                    }
+                }
+            });
+        }
+        future
+    }
+    ///
+    /// Returns the future of the first received value with its index in the `futures`, not blocking
+    /// - The rest are cancelled
+    /// - Fails if the all futures are failed
+    pub fn select(futures: Vec<Future<T>>) -> Future<(usize, T)> {
+        let (future, sink) = Future::new();
+        if futures.is_empty() {
+            sink.fail("Nothing to select");
+            return future;
+        }
+        let sources: Vec<Arc<FutureShared<T>>> = futures.iter().map(|f| f.shared.clone()).collect();
+        future.on_cancel_all(sources.clone());
+        let state = Arc::new(Mutex::new((futures.len(), Some(sink))));
+        for (index, source) in futures.into_iter().enumerate() {
+            let state = state.clone();
+            let sources = sources.clone();
+            source.on_complete(move |result| {
+                let mut guard = state.lock();
+                let (remaining, sink) = &mut *guard;
+                match result {
+                    Ok(value) => {
+                        if let Some(sink) = sink.take() {
+                            drop(guard);
+                            sink.add((index, value));
+                            for source in sources {
+                                source.cancel();
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        *remaining -= 1;
+                        if *remaining == 0 {
+                            if let Some(sink) = sink.take() {
+                                drop(guard);
+                                sink.fail(format!("All futures failed, last: {}", err));
+                            }
+                        }
+                    }
+                }
+            });
+        }
+        future
+    }
+    ///
     /// Spawning the closure using [Scheduler]
     pub fn spawn<F>(scheduler: Scheduler, f: F) -> Result<Future<T>, Error>
     where
-        F: FnOnce() -> T + Send + 'static 
+        F: FnOnce() -> T + Send + 'static
     {
-        let (send, recv) = channel::bounded(1);
+        let (future, sink) = Self::new();
         let h = scheduler.spawn(move || {
             let result = f();
-            if let Err(err) = send.send(result) {
-                log::warn!("Future.spawn | Send error: {:?}", err);
-            }
+            sink.add(result);
             Ok(())
         });
         match h {
-            Ok(_) => Ok(Self { recv }),
+            Ok(_) => Ok(future),
             Err(err) => Err(Error::new("Future", "spawn").pass(err))
         }
     }
+    ///
+    /// Registers the callback to be called once with the result,
+    /// called immediately if already completed
+    fn on_complete(self, f: impl FnOnce(Result<T, String>) + Send + 'static) {
+        let mut inner = self.shared.inner.lock();
+        match FutureShared::take(&mut inner) {
+            Some(result) => {
+                drop(inner);
+                f(result);
+            }
+            None => inner.on_complete = Some(Box::new(f)),
+        }
+    }
+    ///
+    /// Cancellation of this future will cancel the `source`
+    fn on_cancel<S: Send + 'static>(&self, source: &Future<S>) {
+        self.on_cancel_all(vec![source.shared.clone()]);
+    }
+    ///
+    /// Cancellation of this future will cancel the all `sources`
+    fn on_cancel_all<S: Send + 'static>(&self, sources: Vec<Arc<FutureShared<S>>>) {
+        let sources: Vec<_> = sources.iter().map(Arc::downgrade).collect();
+        self.shared.inner.lock().on_cancel = Some(Box::new(move || {
+            for source in sources.iter().filter_map(|source| source.upgrade()) {
+                source.cancel();
+            }
+        }));
+    }
+}
+//
+//
+impl<T: Send + 'static> std::future::Future for Future<T> {
+    type Output = Result<T, Error>;
+    //
+    //
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.shared.inner.lock();
+        match FutureShared::take(&mut inner) {
+            Some(result) => Poll::Ready(result.map_err(|err| Error::new("Future", "poll").err(err))),
+            None => {
+                inner.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+//
+//
+impl<T> std::fmt::Debug for Future<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self.shared.inner.lock().state {
+            FutureState::Pending => "Pending",
+            FutureState::Ready(_) => "Ready",
+            FutureState::Taken => "Taken",
+            FutureState::Cancelled => "Cancelled",
+            FutureState::Closed(_) => "Closed",
+        };
+        f.debug_struct("Future").field("state", &state).finish()
+    }
 }
 ///
-/// Contains `Sender<T>`
+/// Sending side of the [Future]
+/// - The future fails if the [Sink] is dropped without value
 pub struct Sink<T> {
-    send: Sender<T>,
+    shared: Arc<FutureShared<T>>,
 }
 //
 //
-impl<T> Sink<T> {
+impl<T: Send + 'static> Sink<T> {
     ///
-    /// Creates new instance of `Sink<T>`
+    /// Creates new instance of `Sink<T>`, sending the value into the `send`
+    /// - The value is dropped if `send` is closed
+    #[deprecated(note = "Use `Future::new`, returning the `Future` with the corresponding `Sink`")]
     pub fn new(send: Sender<T>) -> Self {
-        Self {
-            send: send,
-        }
+        let (future, sink) = Future::new();
+        future.on_complete(move |result| {
+            if let Some(Err(err)) = result.ok().map(|value| send.send(value)) {
+                error!("Sink.add | Send error: {:#?}", err);
+            }
+        });
+        sink
     }
+}
+//
+//
+impl<T> Sink<T> {
     ///
     /// Sends value to the corresponding `Future<T>`
+    /// - Dropped if the future is cancelled or already completed
     pub fn add(&self, value: T) {
-        match self.send.send(value) {
-            Ok(_) => {}
-            Err(err) => error!("Sink.add | Send error: {:#?}", err),
+        if let Err(result) = self.shared.complete(Ok(value)) {
+            drop(result);
+            match self.is_cancelled() {
+                true => log::debug!("Sink.add | Future is cancelled, value dropped"),
+                false => error!("Sink.add | Future is already completed, value dropped"),
+            }
         }
     }
+    ///
+    /// Fails the corresponding `Future<T>` with the `message`
+    pub fn fail(&self, message: impl Into<String>) {
+        let message = message.into();
+        if self.shared.complete(Err(message.clone())).is_err() {
+            log::debug!("Sink.fail | Future is already completed, error dropped: {}", message);
+        }
+    }
+    ///
+    /// Returns `true` if the corresponding `Future<T>` is cancelled,
+    /// the pending operation can be skipped
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.shared.inner.lock().state, FutureState::Cancelled)
+    }
+    ///
+    /// Sends the value or fails the future by the `result`
+    fn complete(&self, result: Result<T, String>) {
+        match result {
+            Ok(value) => self.add(value),
+            Err(err) => self.fail(err),
+        }
+    }
+}
+//
+//
+impl<T> Drop for Sink<T> {
+    fn drop(&mut self) {
+        let _ = self.shared.complete(Err("Sink dropped without value".to_owned()));
+    }
 }
@@ -0,0 +1,100 @@
+use std::{path::PathBuf, time::Duration};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::services::{
+    conf::{ConfTree, ConfTreeGet},
+    entity::Name,
+    subscription::ConfSubscribe,
+};
+///
+/// Configuration of the `HistoryService`
+///
+/// ```yaml
+/// service HistoryService History:
+///     subscribe:
+///         MultiQueue:
+///             {cot: Inf, history: r}: []  # points from the devices, having `history: r` or `history: rw`
+///             {cot: Act, history: w}: []  # commands to the devices, having `history: w` or `history: rw`
+///     path: assets/history/               # directory of the segment files
+///     segment: 1 h                        # optional, time partition of the segment files, default 1 h
+///     flush: 1 s                          # optional, interval of the writing received points, default 1 s
+///     retention: 504 h                    # optional, segments older are removed
+///     max-size: 1024 MB                   # optional, oldest segments are removed if exceeded, B / KB / MB / GB
+/// ```
+#[derive(Debug, Clone)]
+pub struct HistoryConf {
+    pub(crate) name: Name,
+    pub(crate) subscribe: ConfSubscribe,
+    pub(crate) path: PathBuf,
+    pub(crate) segment: Duration,
+    pub(crate) flush: Duration,
+    pub(crate) retention: Option<Duration>,
+    pub(crate) max_size: Option<u64>,
+}
+//
+//
+impl HistoryConf {
+    const DEFAULT_SEGMENT: Duration = Duration::from_secs(3600);
+    const DEFAULT_FLUSH: Duration = Duration::from_secs(1);
+    ///
+    /// Returns [HistoryConf] parsed from the service config node
+    /// - `parent` - the name of the application
+    pub fn new(parent: impl Into<String>, conf: ConfTree) -> Result<Self, Error> {
+        let me = conf.sufix_or(conf.name()?);
+        let name = Name::new(parent, &me);
+        let dbg = Dbg::new(name.parent(), format!("HistoryConf({})", me));
+        let error = Error::new(&dbg, "new");
+        log::trace!("{}.new | conf: {:?}", dbg, conf);
+        let subscribe = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "subscribe") {
+            Some(subscribe) => ConfSubscribe::new(subscribe),
+            None => return Err(error.err("Parameter 'subscribe' - not found")),
+        };
+        let path: PathBuf = conf.as_str("path").map_err(|err| error.pass_with("Parameter 'path' - not found", err.to_string()))?.into();
+        let segment = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "segment") {
+            Some(_) => conf.get_duration("segment").map_err(|err| error.pass(err))?,
+            None => Self::DEFAULT_SEGMENT,
+        };
+        if segment.as_millis() == 0 {
+            return Err(error.err("Parameter 'segment' - can't be less then 1 ms"));
+        }
+        let flush = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "flush") {
+            Some(_) => conf.get_duration("flush").map_err(|err| error.pass(err))?,
+            None => Self::DEFAULT_FLUSH,
+        };
+        let retention = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "retention") {
+            Some(_) => Some(conf.get_duration("retention").map_err(|err| error.pass(err))?),
+            None => None,
+        };
+        let max_size = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "max-size") {
+            Some(value) => Some(Self::size(&value).ok_or_else(|| error.err(format!("Parameter 'max-size' - invalid size: {:?}", value)))?),
+            None => None,
+        };
+        log::debug!(
+            "{}.new | path: {:?}, segment: {:?}, flush: {:?}, retention: {:?}, max-size: {:?}",
+            dbg, path, segment, flush, retention, max_size,
+        );
+        Ok(Self { name, subscribe, path, segment, flush, retention, max_size })
+    }
+    ///
+    /// Returns the size in bytes
+    /// - Number of bytes, or number with the unit: `B`, `KB`, `MB`, `GB`
+    fn size(value: &serde_yaml::Value) -> Option<u64> {
+        match value {
+            serde_yaml::Value::Number(value) => value.as_u64(),
+            serde_yaml::Value::String(value) => {
+                let value = value.trim();
+                let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+                let (number, unit) = value.split_at(split);
+                let number: u64 = number.parse().ok()?;
+                let unit = match unit.trim() {
+                    "" | "B" => 1,
+                    "KB" => 1 << 10,
+                    "MB" => 1 << 20,
+                    "GB" => 1 << 30,
+                    _ => return None,
+                };
+                number.checked_mul(unit)
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
+    time::Duration,
+};
+use chrono::{DateTime, Utc};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    kernel::clock::{Clock, GlobalClock},
+    services::{
+        entity::{Name, Object, Point, PointConfig},
+        service::Service,
+        services::Services,
+        subscription::ServiceSubscription,
+    },
+    sync::{Handles, Mutex},
+    thread_pool::Scheduler,
+};
+use super::{history_conf::HistoryConf, history_store::{HistorySegment, HistoryStore}};
+///
+/// ### Local historian of the points flagged with `history`
+/// - Subscribes only on the points matching `subscribe` criterias,
+///   `history` criteria is resolved over the points of the all services inserted before `run`
+/// - Received points are written into the [HistoryStore] every `flush` interval
+/// - Retention by age and by size is applied after each flush, the age is measured by the [GlobalClock] captured on creation
+/// - Stored points can be queried by the point name and time window
+pub struct HistoryService {
+    dbg: Dbg,
+    name: Name,
+    conf: HistoryConf,
+    store: Arc<Mutex<HistoryStore>>,
+    services: Arc<Services>,
+    scheduler: Option<Scheduler>,
+    handles: Handles<()>,
+    exit: Arc<AtomicBool>,
+    clock: Arc<dyn Clock>,
+}
+//
+//
+impl HistoryService {
+    const CYCLE: Duration = Duration::from_millis(10);
+    ///
+    /// Returns [HistoryService] new instance
+    pub fn new(conf: HistoryConf, services: Arc<Services>, scheduler: Option<Scheduler>) -> Self {
+        let dbg = Dbg::new(conf.name.parent(), conf.name.me());
+        let store = HistoryStore::new(&dbg, &conf.path, conf.segment, conf.retention, conf.max_size);
+        Self {
+            name: conf.name.clone(),
+            conf,
+            store: Arc::new(Mutex::new(store)),
+            services,
+            scheduler,
+            handles: Handles::new(&dbg),
+            exit: Arc::new(AtomicBool::new(false)),
+            clock: GlobalClock::get(),
+            dbg,
+        }
+    }
+    ///
+    /// Returns stored points with the `name` and the timestamp in `from..=to`, sorted by the timestamp
+    /// - Points received but not flushed yet are included
+    pub fn range(&self, name: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<Point>, Error> {
+        self.store.lock().range(name, from, to)
+    }
+    ///
+    /// Returns the segment files of the store, sorted by the start time
+    pub fn segments(&self) -> Result<Vec<HistorySegment>, Error> {
+        self.store.lock().segments()
+    }
+    ///
+    /// Returns points of the all services except this one, used to resolve `history` criterias
+    fn all_points(&self) -> Vec<PointConfig> {
+        let me = self.name.join();
+        self.services.all()
+            .into_iter()
+            .filter(|(name, _)| *name != me)
+            .flat_map(|(_, service)| service.points())
+            .collect()
+    }
+    ///
+    /// Main loop
+    fn run_(dbg: Dbg, store: Arc<Mutex<HistoryStore>>, subscription: ServiceSubscription, flush: Duration, exit: Arc<AtomicBool>, clock: Arc<dyn Clock>) {
+        log::info!("{}.run | Preparing thread - ok", dbg);
+        let flush_store = |store: &Mutex<HistoryStore>| {
+            let mut store = store.lock();
+            if let Err(err) = store.flush() {
+                log::error!("{}.run | {}", dbg, err);
+            }
+            if let Err(err) = store.cleanup(clock.utc()) {
+                log::warn!("{}.run | {}", dbg, err);
+            }
+        };
+        let mut flushed = clock.now();
+        loop {
+            if let Some(point) = subscription.recv(Self::CYCLE) {
+                log::trace!("{}.run | storing: {:?}", dbg, point);
+                if let Err(err) = store.lock().append(&point) {
+                    log::warn!("{}.run | {}", dbg, err);
+                }
+            }
+            if clock.elapsed(flushed) >= flush {
+                flush_store(&store);
+                flushed = clock.now();
+            }
+            if exit.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+        flush_store(&store);
+        subscription.unsubscribe();
+        log::info!("{}.run | Exit", dbg);
+    }
+}
+//
+//
+impl Object for HistoryService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+//
+impl std::fmt::Debug for HistoryService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("HistoryService")
+            .field("id", &self.dbg)
+            .field("path", &self.conf.path)
+            .finish()
+    }
+}
+//
+//
+impl Service for HistoryService {
+    //
+    //
+    fn subscribed_to(&self) -> Vec<String> {
+        ServiceSubscription::services(&self.name, &self.conf.subscribe)
+    }
+    //
+    //
+    fn run(&self) -> Result<(), Error> {
+        log::info!("{}.run | Starting...", self.dbg);
+        let error = Error::new(&self.dbg, "run");
+        let dbg = self.dbg.clone();
+        let store = self.store.clone();
+        let points = self.all_points();
+        log::debug!("{}.run | Resolving subscription over {} points", self.dbg, points.len());
+        let subscription = ServiceSubscription::with_points(&self.name, &self.conf.subscribe, &points, self.services.clone())
+            .map_err(|err| error.pass(err))?;
+        let flush = self.conf.flush;
+        let exit = self.exit.clone();
+        let clock = self.clock.clone();
+        match &self.scheduler {
+            Some(scheduler) => {
+                let handle = scheduler.spawn(move || {
+                    Self::run_(dbg, store, subscription, flush, exit, clock);
+                    Ok(())
+                }).map_err(|err| error.pass_with("Start failed on Scheduler", err.to_string()))?;
+                self.handles.push(handle);
+            }
+            None => {
+                let handle = std::thread::Builder::new().name(format!("{}.run", dbg)).spawn(move || {
+                    Self::run_(dbg, store, subscription, flush, exit, clock);
+                }).map_err(|err| error.pass_with("Start failed on std::thread", err.to_string()))?;
+                self.handles.push(handle);
+            }
+        };
+        log::info!("{}.run | Started", self.dbg);
+        Ok(())
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::SeqCst);
+    }
+}
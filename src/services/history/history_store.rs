@@ -0,0 +1,192 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use flate2::{read::MultiGzDecoder, write::GzEncoder, Compression};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::services::entity::{Point, PointLines};
+///
+/// The file of the [HistoryStore], containing points with the timestamp in `start..start + segment`
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistorySegment {
+    pub start: DateTime<Utc>,
+    pub path: PathBuf,
+    pub size: u64,
+}
+///
+/// Segmented file store of the points
+/// - Points are partitioned by the timestamp into the segment files `<start unix ms>.jsonl.gz`
+/// - Each flush appends a new gzip member of JSON lines to the segment file,
+///   so the written data is never rewritten
+/// - Segments older then `retention` are removed,
+///   oldest segments are removed while total size exceeds `max-size`
+#[derive(Debug)]
+pub struct HistoryStore {
+    dbg: Dbg,
+    path: PathBuf,
+    segment: i64,
+    retention: Option<Duration>,
+    max_size: Option<u64>,
+    buffer: BTreeMap<i64, Vec<u8>>,
+}
+//
+//
+impl HistoryStore {
+    const EXTENSION: &'static str = ".jsonl.gz";
+    ///
+    /// Returns [HistoryStore] new instance
+    /// - `path` - the directory of the segment files, created on the first flush
+    /// - `segment` - time partition of the segment files, at least 1 ms
+    /// - `retention` - segments older are removed by `cleanup`
+    /// - `max_size` - oldest segments are removed by `cleanup` while total size exceeds
+    pub fn new(parent: impl Into<String>, path: impl AsRef<Path>, segment: Duration, retention: Option<Duration>, max_size: Option<u64>) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "HistoryStore"),
+            path: path.as_ref().to_path_buf(),
+            segment: i64::try_from(segment.as_millis()).unwrap_or(i64::MAX).max(1),
+            retention,
+            max_size,
+            buffer: BTreeMap::new(),
+        }
+    }
+    ///
+    /// Buffers the `point`, it will be written by the next `flush`
+    pub fn append(&mut self, point: &Point) -> Result<(), Error> {
+        let line = PointLines::encode(point).map_err(|err| Error::new(&self.dbg, "append").pass(err))?;
+        let start = self.segment_start(point.timestamp());
+        self.buffer.entry(start).or_default().extend(line);
+        Ok(())
+    }
+    ///
+    /// Writes buffered points into the segment files
+    pub fn flush(&mut self) -> Result<(), Error> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let error = Error::new(&self.dbg, "flush");
+        fs::create_dir_all(&self.path).map_err(|err| error.pass_with(format!("Create dir '{}' error", self.path.display()), err.to_string()))?;
+        let mut errors = vec![];
+        for (start, lines) in std::mem::take(&mut self.buffer) {
+            let path = self.path.join(format!("{}{}", start, Self::EXTENSION));
+            if let Err(err) = Self::write(&path, &lines) {
+                errors.push(format!("'{}': {}", path.display(), err));
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(error.err(format!("Write error, points lost: \n\t{}", errors.join("\n\t")))),
+        }
+    }
+    ///
+    /// Returns points with the `name` and the timestamp in `from..=to`, sorted by the timestamp
+    /// - Buffered points are written before reading
+    pub fn range(&mut self, name: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<Point>, Error> {
+        let error = Error::new(&self.dbg, "range");
+        self.flush().map_err(|err| error.pass(err))?;
+        let from_ms = self.segment_start(from);
+        let mut points = vec![];
+        for segment in self.segments().map_err(|err| error.pass(err))? {
+            if segment.start.timestamp_millis() < from_ms || segment.start > to {
+                continue;
+            }
+            points.extend(
+                self.read(&segment.path).map_err(|err| error.pass(err))?
+                    .into_iter()
+                    .filter(|point| point.name() == name && point.timestamp() >= from && point.timestamp() <= to),
+            );
+        }
+        points.sort_by_key(|point| point.timestamp());
+        Ok(points)
+    }
+    ///
+    /// Returns the segment files, sorted by the start time
+    pub fn segments(&self) -> Result<Vec<HistorySegment>, Error> {
+        let error = Error::new(&self.dbg, "segments");
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        let dir = fs::read_dir(&self.path).map_err(|err| error.pass_with(format!("Read dir '{}' error", self.path.display()), err.to_string()))?;
+        let mut segments: Vec<HistorySegment> = dir.filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name();
+            let start: i64 = name.to_str()?.strip_suffix(Self::EXTENSION)?.parse().ok()?;
+            Some(HistorySegment {
+                start: Utc.timestamp_millis_opt(start).single()?,
+                path: entry.path(),
+                size: entry.metadata().ok()?.len(),
+            })
+        }).collect();
+        segments.sort_by_key(|segment| segment.start);
+        Ok(segments)
+    }
+    ///
+    /// Removes segments by `retention` and `max-size`, returns the number of removed segments
+    /// - `now` - the current time, segments ended before `now - retention` are removed
+    /// - The latest segment is never removed by `max-size`
+    pub fn cleanup(&self, now: DateTime<Utc>) -> Result<usize, Error> {
+        let error = Error::new(&self.dbg, "cleanup");
+        let mut segments = self.segments().map_err(|err| error.pass(err))?;
+        let mut removed = vec![];
+        if let Some(retention) = self.retention {
+            let retention = i64::try_from(retention.as_millis()).unwrap_or(i64::MAX);
+            let oldest = now.timestamp_millis().saturating_sub(retention);
+            let (expired, actual): (Vec<_>, Vec<_>) = segments.into_iter().partition(|segment| {
+                segment.start.timestamp_millis().saturating_add(self.segment) <= oldest
+            });
+            removed = expired;
+            segments = actual;
+        }
+        if let Some(max_size) = self.max_size {
+            let mut size: u64 = segments.iter().map(|segment| segment.size).sum();
+            while size > max_size && segments.len() > 1 {
+                let segment = segments.remove(0);
+                size -= segment.size;
+                removed.push(segment);
+            }
+        }
+        for segment in &removed {
+            fs::remove_file(&segment.path).map_err(|err| error.pass_with(format!("Remove '{}' error", segment.path.display()), err.to_string()))?;
+            log::debug!("{}.cleanup | Removed '{}'", self.dbg, segment.path.display());
+        }
+        Ok(removed.len())
+    }
+    ///
+    /// Returns the start of the segment containing the `timestamp`, unix ms
+    fn segment_start(&self, timestamp: DateTime<Utc>) -> i64 {
+        timestamp.timestamp_millis().div_euclid(self.segment) * self.segment
+    }
+    ///
+    /// Appends the `lines` to the segment file as the new gzip member
+    fn write(path: &Path, lines: &[u8]) -> Result<(), std::io::Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        encoder.write_all(lines)?;
+        encoder.finish()?.sync_data()
+    }
+    ///
+    /// Returns all points of the segment file
+    /// - Reading stops on the damaged tail, for example if the application was stopped while writing
+    fn read(&self, path: &Path) -> Result<Vec<Point>, Error> {
+        let error = Error::new(&self.dbg, "read");
+        let file = File::open(path).map_err(|err| error.pass_with(format!("Open '{}' error", path.display()), err.to_string()))?;
+        let mut points = vec![];
+        for line in BufReader::new(MultiGzDecoder::new(BufReader::new(file))).lines() {
+            match line {
+                Ok(line) if line.trim().is_empty() => {}
+                Ok(line) => match PointLines::decode(line.as_bytes(), 0) {
+                    Ok(point) => points.push(point),
+                    Err(err) => log::warn!("{}.read | '{}': {}", self.dbg, path.display(), err),
+                },
+                Err(err) => {
+                    log::warn!("{}.read | '{}' - damaged, the rest is skipped: {}", self.dbg, path.display(), err);
+                    break;
+                }
+            }
+        }
+        Ok(points)
+    }
+}
@@ -0,0 +1,27 @@
+//!
+//! # Local historian of the points
+//!
+//! - `HistoryService` - stores points flagged with `history: r / w / rw`,
+//!   subscribed by `history` criteria of the `subscribe`
+//! - `HistoryStore` - segmented time-partitioned compressed file store with retention by age and size
+//!
+//! ## Configuration example
+//!
+//! ```yaml
+//! service HistoryService History:
+//!     subscribe:
+//!         MultiQueue:
+//!             {cot: Inf, history: r}: []
+//!             {cot: Act, history: w}: []
+//!     path: assets/history/
+//!     segment: 1 h
+//!     retention: 504 h
+//!     max-size: 1024 MB
+//! ```
+mod history_conf;
+mod history_service;
+mod history_store;
+
+pub use history_conf::*;
+pub use history_service::*;
+pub use history_store::*;
@@ -0,0 +1,26 @@
+use sal_core::error::Error;
+use crate::services::{conf::{ConfTree, FromConfTree}, entity::Name};
+crate::conf_struct! {
+    ///
+    /// Configuration of the `MetricsService`
+    ///
+    /// ```yaml
+    /// service MetricsService Metrics:
+    ///     address: 127.0.0.1:9100         # address to listen on, port 0 - assigned by the OS
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct MetricsConf {
+        pub(crate) name: Name = name,
+        pub(crate) address: String = required("address"),
+    }
+}
+//
+//
+impl MetricsConf {
+    ///
+    /// Returns [MetricsConf] parsed from the service config node
+    /// - `parent` - the name of the application
+    pub fn new(parent: impl Into<String>, conf: ConfTree) -> Result<Self, Error> {
+        Self::from_conf_tree(&parent.into(), &conf)
+    }
+}
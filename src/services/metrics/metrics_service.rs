@@ -0,0 +1,181 @@
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
+    time::Duration,
+};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    kernel::metrics::Metrics,
+    services::{
+        entity::{Name, Object},
+        service::Service,
+        services::Services,
+    },
+    sync::{Handles, Mutex},
+    thread_pool::Scheduler,
+};
+use super::metrics_conf::MetricsConf;
+///
+/// ### Serves the metrics over HTTP
+/// - Listens on the `address`
+/// - `GET /metrics` or `GET /` replies the `Metrics::global()` in the Prometheus text exposition format
+/// - Another methods and paths are replied with 405 / 404
+/// - Requests are served one by one, the connection is closed after the reply
+pub struct MetricsService {
+    dbg: Dbg,
+    name: Name,
+    conf: MetricsConf,
+    _services: Arc<Services>,
+    scheduler: Option<Scheduler>,
+    local_addr: Mutex<Option<SocketAddr>>,
+    handles: Handles<()>,
+    exit: Arc<AtomicBool>,
+}
+//
+//
+impl MetricsService {
+    const CYCLE: Duration = Duration::from_millis(10);
+    const READ_TIMEOUT: Duration = Duration::from_secs(1);
+    const CONTENT_TYPE: &'static str = "text/plain; version=0.0.4; charset=utf-8";
+    ///
+    /// Returns [MetricsService] new instance
+    pub fn new(conf: MetricsConf, services: Arc<Services>, scheduler: Option<Scheduler>) -> Self {
+        let dbg = Dbg::new(conf.name.parent(), conf.name.me());
+        Self {
+            name: conf.name.clone(),
+            conf,
+            _services: services,
+            scheduler,
+            local_addr: Mutex::new(None),
+            handles: Handles::new(&dbg),
+            exit: Arc::new(AtomicBool::new(false)),
+            dbg,
+        }
+    }
+    ///
+    /// Returns the address the service is listening on, `None` until started
+    ///
+    /// Useful if configured with port 0, the port assigned by the OS
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        *self.local_addr.lock()
+    }
+    ///
+    /// Main loop
+    fn run_(dbg: Dbg, listener: TcpListener, exit: Arc<AtomicBool>) {
+        log::info!("{}.run | Preparing thread - ok", dbg);
+        loop {
+            match listener.accept() {
+                Ok((stream, addr)) => {
+                    if let Err(err) = Self::serve(stream) {
+                        log::warn!("{}.run | Client '{}': {}", dbg, addr, err);
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => std::thread::sleep(Self::CYCLE),
+                Err(err) => {
+                    log::warn!("{}.run | Accept error: {:?}", dbg, err);
+                    std::thread::sleep(Self::CYCLE);
+                }
+            }
+            if exit.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+        log::info!("{}.run | Exit", dbg);
+    }
+    ///
+    /// Reads the request and writes the reply
+    fn serve(mut stream: TcpStream) -> Result<(), std::io::Error> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(Self::READ_TIMEOUT))?;
+        let mut request = vec![];
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 8192 {
+            match stream.read(&mut buf)? {
+                0 => break,
+                len => request.extend_from_slice(&buf[..len]),
+            }
+        }
+        let request = String::from_utf8_lossy(&request);
+        let mut line = request.lines().next().unwrap_or_default().split_whitespace();
+        let (status, body) = match (line.next(), line.next()) {
+            (Some("GET"), Some("/metrics" | "/")) => ("200 OK", Metrics::global().render()),
+            (Some("GET"), Some(_)) => ("404 Not Found", "Not Found\n".to_owned()),
+            _ => ("405 Method Not Allowed", "Method Not Allowed\n".to_owned()),
+        };
+        let reply = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status, Self::CONTENT_TYPE, body.len(), body,
+        );
+        stream.write_all(reply.as_bytes())?;
+        stream.flush()
+    }
+}
+//
+//
+impl Object for MetricsService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+//
+impl std::fmt::Debug for MetricsService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("MetricsService")
+            .field("id", &self.dbg)
+            .field("address", &self.conf.address)
+            .finish()
+    }
+}
+//
+//
+impl Service for MetricsService {
+    //
+    //
+    fn run(&self) -> Result<(), Error> {
+        log::info!("{}.run | Starting...", self.dbg);
+        let error = Error::new(&self.dbg, "run");
+        let dbg = self.dbg.clone();
+        let listener = TcpListener::bind(&self.conf.address)
+            .map_err(|err| error.pass_with(format!("Bind on '{}' failed", self.conf.address), err.to_string()))?;
+        listener.set_nonblocking(true).map_err(|err| error.pass_with("Set nonblocking error", err.to_string()))?;
+        let local_addr = listener.local_addr().map_err(|err| error.pass_with("Local address error", err.to_string()))?;
+        *self.local_addr.lock() = Some(local_addr);
+        log::info!("{}.run | Listening on '{}'", self.dbg, local_addr);
+        let exit = self.exit.clone();
+        match &self.scheduler {
+            Some(scheduler) => {
+                let handle = scheduler.spawn(move || {
+                    Self::run_(dbg, listener, exit);
+                    Ok(())
+                }).map_err(|err| error.pass_with("Start failed on Scheduler", err.to_string()))?;
+                self.handles.push(handle);
+            }
+            None => {
+                let handle = std::thread::Builder::new().name(format!("{}.run", dbg)).spawn(move || {
+                    Self::run_(dbg, listener, exit);
+                }).map_err(|err| error.pass_with("Start failed on std::thread", err.to_string()))?;
+                self.handles.push(handle);
+            }
+        };
+        log::info!("{}.run | Started", self.dbg);
+        Ok(())
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::SeqCst);
+    }
+}
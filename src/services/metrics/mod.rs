@@ -0,0 +1,17 @@
+//!
+//! # HTTP endpoint of the metrics
+//! 
+//! - `MetricsService` - serves the `Metrics::global()` in the Prometheus text exposition format
+//! - `GET /metrics` (or `/`) replies the metrics, another paths - 404
+//! 
+//! ## Configuration example
+//! 
+//! ```yaml
+//! service MetricsService Metrics:
+//!     address: 127.0.0.1:9100
+//! ```
+mod metrics_conf;
+mod metrics_service;
+
+pub use metrics_conf::*;
+pub use metrics_service::*;
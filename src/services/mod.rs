@@ -61,16 +61,40 @@
 mod service;
 mod services;
 
+mod cache;
 pub mod conf;
 pub mod entity;
 pub mod future;
+mod history;
+mod metrics;
 mod multi_queue;
+mod points_request;
+mod producer;
 pub mod retain;
+mod registry;
+mod shutdown;
 mod subscription;
+mod supervisor;
 pub mod task;
+mod tcp;
+#[cfg(any(test, feature = "test-service"))]
+mod test_service;
+mod topology;
 pub mod types;
 
+pub use cache::*;
+pub use history::*;
+pub use metrics::*;
 pub use multi_queue::*;
+pub use points_request::*;
+pub use producer::*;
+pub use registry::*;
 pub use subscription::*;
 pub use service::*;
-pub use services::*;
\ No newline at end of file
+pub use services::*;
+pub use shutdown::*;
+pub use supervisor::*;
+pub use tcp::*;
+#[cfg(any(test, feature = "test-service"))]
+pub use test_service::*;
+pub use topology::*;
\ No newline at end of file
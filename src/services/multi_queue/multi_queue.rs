@@ -6,10 +6,11 @@ use coco::Stack;
 use concat_string::concat_string;
 use sal_core::{dbg::{self, dbg, Dbg}, error::Error};
 use crate::{
-    collections::FxDashMap, services::{
+    collections::FxDashMap, kernel::metrics::Metrics, services::{
         entity::{Name, Object, Point, PointTxId},
-        service::{LinkName, Service, RECV_TIMEOUT},
+        service::{LinkName, Service, ServiceError, RECV_TIMEOUT},
         services::Services, subscription::{SubscriptionCriteria, Subscriptions},
+        topology::TopologySubscription,
     },
     sync::{channel::{self, Receiver, Sender}, Handles}, thread_pool::Scheduler,
 };
@@ -62,6 +63,92 @@ impl MultiQueue {
         }
     }
     ///
+    /// Registers the subscription of the receiver, points will be sent into the `send`
+    /// - Broadcast if `points` is empty
+    #[dbg]
+    fn add_subscription(&self, receiver_name: &str, points: &[SubscriptionCriteria], send: Sender<Point>) {
+        let receiver_hash = PointTxId::from_str(receiver_name);
+        self.receiver_dictionary.insert(receiver_hash, receiver_name.to_string());
+        if points.is_empty() {
+            self.subscriptions.add_broadcast(receiver_hash, send.clone());
+            self.log("/broadcast.log", receiver_name, receiver_hash, points);
+            dbg::debug!("Broadcast registered, receiver: \n\t{} ({})", receiver_name, receiver_hash);
+        } else {
+            for subscription_criteria in points {
+                self.subscriptions.add_multicast(receiver_hash, &subscription_criteria.destination(), send.clone());
+            }
+            self.log("/multicast.log", receiver_name, receiver_hash, points);
+            dbg::debug!("Multicast registered, receiver: \n\t{} ({}) \n\tpoints: {:#?}", receiver_name, receiver_hash, points.len());
+            dbg::trace!("Multicast registered, receiver: \n\t{} ({}) \n\tpoints: {:#?}", receiver_name, receiver_hash, points);
+        }
+        self.subscriptions_changed.store(true, Ordering::SeqCst);
+    }
+    ///
+    /// Extends the multicast subscription of the receiver with the `points`
+    #[dbg]
+    fn extend_subscriptions(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "extend_subscription");
+        let receiver_hash = PointTxId::from_str(receiver_name);
+        if points.is_empty() {
+            Err(error.err(format!("Can't be extended (broadcast), receiver: {} ({})", receiver_name, receiver_hash)))
+        } else {
+            let mut message = String::new();
+            for subscription_criteria in points {
+                dbg::trace!("Extending (multicast) for receiver: {} ({})...", receiver_name, receiver_hash);
+                if let Err(err) = self.subscriptions.extend_multicast(receiver_hash, &subscription_criteria.destination()) {
+                    message = concat_string!(message, err.to_string(), "\n");
+                };
+            }
+            self.log("/multicast.log", receiver_name, receiver_hash, points);
+            if message.is_empty() {
+                dbg::debug!("Extended (multicast), receiver: {} ({})", receiver_name, receiver_hash);
+                self.subscriptions_changed.store(true, Ordering::SeqCst);
+                Ok(())
+            } else {
+                dbg::debug!("Extended (multicast), receiver: {} ({}) \n\t with errors: {:?}", receiver_name, receiver_hash, message);
+                self.subscriptions_changed.store(true, Ordering::SeqCst);
+                Err(error.err(message))
+            }
+        }
+    }
+    ///
+    /// Removes the subscription of the receiver, all if `points` is empty
+    #[dbg]
+    fn remove_subscriptions(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), Error> {
+        let mut changed = false;
+        let error = Error::new(&self.dbg, "unsubscribe");
+        let receiver_hash = PointTxId::from_str(receiver_name);
+        if points.is_empty() {
+            match self.subscriptions.remove_all(&receiver_hash) {
+                Ok(_) => {
+                    self.receiver_dictionary.remove(&receiver_hash);
+                    changed |= true;
+                    dbg::debug!("Broadcast subscription removed, receiver: {} ({})", receiver_name, receiver_hash);
+                }
+                Err(err) => {
+                    return Err(error.pass(err))
+                }
+            }
+        } else {
+            for subscription_criteria in points {
+                match self.subscriptions.remove(&receiver_hash, &subscription_criteria.destination()) {
+                    Ok(_) => {
+                        self.receiver_dictionary.remove(&receiver_hash);
+                        changed |= true;
+                        dbg::debug!("Multicat subscription '{}' removed, receiver: {} ({})", subscription_criteria.destination(), receiver_name, receiver_hash);
+                    }
+                    Err(err) => {
+                        return Err(error.pass(err))
+                    }
+                }
+            }
+        }
+        if changed {
+            self.subscriptions_changed.store(true, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+    ///
     /// Writes Subscription's to the log file 
     fn log(&self, name: &str, receiver_name: &str, rceiver_hash: usize, points: &[SubscriptionCriteria]) {
         let path = concat_string!("./logs", self.name.join(), name);
@@ -104,6 +191,12 @@ impl MultiQueue {
     /// Main loop
     fn run_(dbg: Dbg, name: Name, recv: Receiver<Point>, subscriptions_ref: Arc<Subscriptions>, subscriptions_changed: Arc<AtomicBool>, exit: Arc<AtomicBool>) {
             log::info!("{}.run | Preparing thread - ok", dbg);
+            let service = name.join();
+            let labels = [("service", service.as_str())];
+            let received = Metrics::global().counter("sal_multi_queue_points_total", "Points received by the MultiQueue", &labels);
+            let routed = Metrics::global().counter("sal_multi_queue_routed_total", "Points sent to the subscribers by the MultiQueue", &labels);
+            let errors = Metrics::global().counter("sal_multi_queue_send_errors_total", "Points failed to be sent to the subscribers by the MultiQueue", &labels);
+            let depth = Metrics::global().gauge("sal_multi_queue_depth", "Points waiting in the MultiQueue input", &labels);
             let mut subscriptions = subscriptions_ref.clone();
             loop {
                 if subscriptions_changed.load(Ordering::Relaxed) {
@@ -113,6 +206,8 @@ impl MultiQueue {
                 }
                 match recv.recv_timeout(RECV_TIMEOUT) {
                     Ok(point) => {
+                        received.inc();
+                        depth.set(recv.len() as f64);
                         let point_id = SubscriptionCriteria::new(&point.name(), point.cot()).destination();
                         log::trace!("{}.run | received: \n\t{:?}", dbg, point);
                         Self::log_point(&dbg, &name, &point_id, &point);
@@ -120,9 +215,11 @@ impl MultiQueue {
                             if receiver_hash != point.tx_id() {
                                 match sender.send(point.clone()) {
                                     Ok(_) => {
+                                        routed.inc();
                                         log::trace!("{}.run | sent to '{}' point: {:?}", dbg, receiver_hash, point);
                                     }
                                     Err(err) => {
+                                        errors.inc();
                                         log::error!("{}.run | subscriptions '{}', receiver '{}' - send error: {:?}", dbg, point_id, receiver_hash, err);
                                     }
                                 };
@@ -130,6 +227,7 @@ impl MultiQueue {
                         }
                     }
                     Err(err) => {
+                        depth.set(0.0);
                         log::trace!("{}.run | recv timeout: {:?}", dbg, err);
                     }
                 }
@@ -163,101 +261,93 @@ impl Debug for MultiQueue {
 impl Service for MultiQueue {
     //
     //
-    fn get_link(&self, name: &str) -> Sender<Point> {
+    fn try_get_link(&self, name: &str) -> Result<Sender<Point>, ServiceError> {
         match self.rx_send.get(name) {
-            Some(send) => send.clone(),
-            None => panic!("{}.run | link '{:?}' - not found", self.dbg, name),
+            Some(send) => Ok(send.clone()),
+            None => Err(ServiceError::link_not_found(self.name.join(), name)),
         }
     }
     //
     //
-    #[dbg]
-    fn subscribe(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> (Sender<Point>, Receiver<Point>) {
+    fn try_subscribe(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(Sender<Point>, Receiver<Point>), ServiceError> {
         let (send, recv) = channel::unbounded();
-        let receiver_hash = PointTxId::from_str(receiver_name);
-        self.receiver_dictionary.insert(receiver_hash, receiver_name.to_string());
-        if points.is_empty() {
-            self.subscriptions.add_broadcast(receiver_hash, send.clone());
-            self.log("/broadcast.log", receiver_name, receiver_hash, points);
-            dbg::debug!("Broadcast registered, receiver: \n\t{} ({})", receiver_name, receiver_hash);
-        } else {
-            for subscription_criteria in points {
-                self.subscriptions.add_multicast(receiver_hash, &subscription_criteria.destination(), send.clone());
-            }
-            self.log("/multicast.log", receiver_name, receiver_hash, points);
-            dbg::debug!("Multicast registered, receiver: \n\t{} ({}) \n\tpoints: {:#?}", receiver_name, receiver_hash, points.len());
-            dbg::trace!("Multicast registered, receiver: \n\t{} ({}) \n\tpoints: {:#?}", receiver_name, receiver_hash, points);
-        }
-        self.subscriptions_changed.store(true, Ordering::SeqCst);
-        (send, recv)
+        self.add_subscription(receiver_name, points, send.clone());
+        Ok((send, recv))
     }
     //
     //
-    #[dbg]
-    fn extend_subscription(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), Error> {
-        let error = Error::new(&self.dbg, "extend_subscription");
-        let receiver_hash = PointTxId::from_str(receiver_name);
-        if points.is_empty() {
-            Err(error.err(format!("Can't be extended (broadcast), receiver: {} ({})", receiver_name, receiver_hash)))
-        } else {
-            let mut message = String::new();
-            for subscription_criteria in points {
-                dbg::trace!("Extending (multicast) for receiver: {} ({})...", receiver_name, receiver_hash);
-                if let Err(err) = self.subscriptions.extend_multicast(receiver_hash, &subscription_criteria.destination()) {
-                    message = concat_string!(message, err.to_string(), "\n");
-                };
-            }
-            self.log("/multicast.log", receiver_name, receiver_hash, points);
-            if message.is_empty() {
-                dbg::debug!("Extended (multicast), receiver: {} ({})", receiver_name, receiver_hash);
-                self.subscriptions_changed.store(true, Ordering::SeqCst);
-                Ok(())
-            } else {
-                dbg::debug!("Extended (multicast), receiver: {} ({}) \n\t with errors: {:?}", receiver_name, receiver_hash, message);
-                self.subscriptions_changed.store(true, Ordering::SeqCst);
-                Err(error.err(message))
-            }
-        }
+    fn try_extend_subscription(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), ServiceError> {
+        self.extend_subscriptions(receiver_name, points).map_err(|err| ServiceError::failed(self.name.join(), err))
     }
     //
     //
-    #[dbg]
-    fn unsubscribe(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), Error> {
-        let mut changed = false;
-        let error = Error::new(&self.dbg, "unsubscribe");
-        let receiver_hash = PointTxId::from_str(receiver_name);
-        if points.is_empty() {
-            match self.subscriptions.remove_all(&receiver_hash) {
-                Ok(_) => {
-                    self.receiver_dictionary.remove(&receiver_hash);
-                    changed |= true;
-                    dbg::debug!("Broadcast subscription removed, receiver: {} ({})", receiver_name, receiver_hash);
-                }
-                Err(err) => {
-                    return Err(error.pass(err))
-                }
-            }
-        } else {
-            for subscription_criteria in points {
-                match self.subscriptions.remove(&receiver_hash, &subscription_criteria.destination()) {
-                    Ok(_) => {
-                        self.receiver_dictionary.remove(&receiver_hash);
-                        changed |= true;
-                        dbg::debug!("Multicat subscription '{}' removed, receiver: {} ({})", subscription_criteria.destination(), receiver_name, receiver_hash);
-                    }
-                    Err(err) => {
-                        return Err(error.pass(err))
-                    }
-                }
-            }
-        }
-        if changed {
-            self.subscriptions_changed.store(true, Ordering::SeqCst);
+    fn try_unsubscribe(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), ServiceError> {
+        self.remove_subscriptions(receiver_name, points).map_err(|err| ServiceError::failed(self.name.join(), err))
+    }
+    //
+    //
+    fn resubscribe(&self, receiver_name: &str, points: &[SubscriptionCriteria], send: Sender<Point>) -> Result<(), Error> {
+        self.add_subscription(receiver_name, points, send);
+        Ok(())
+    }
+    //
+    //
+    fn in_queues(&self) -> Vec<String> {
+        let mut queues: Vec<String> = self.rx_send.keys().cloned().collect();
+        queues.sort();
+        queues
+    }
+    //
+    //
+    fn subscriptions(&self) -> Vec<TopologySubscription> {
+        let receiver = |receiver_hash: usize| {
+            self.receiver_dictionary
+                .get(&receiver_hash)
+                .map(|r| r.value().clone())
+                .unwrap_or(receiver_hash.to_string())
+        };
+        let mut subscriptions: Vec<TopologySubscription> = self.subscriptions.broadcast_receivers()
+            .into_iter()
+            .map(|receiver_hash| TopologySubscription::broadcast(receiver(receiver_hash)))
+            .chain(
+                self.subscriptions.multicast_receivers()
+                    .into_iter()
+                    .map(|(receiver_hash, destinations)| TopologySubscription::multicast(receiver(receiver_hash), destinations))
+            )
+            .collect();
+        subscriptions.sort_by(|a, b| a.receiver.cmp(&b.receiver).then(a.broadcast.cmp(&b.broadcast)));
+        subscriptions
+    }
+    //
+    //
+    fn send_to(&self) -> Vec<LinkName> {
+        self.send_queues.clone()
+    }
+    //
+    // Points are received by the `in queue` only, MultiQueue never subscribes on another services
+    fn subscribed_to(&self) -> Vec<String> {
+        vec![]
+    }
+    //
+    // `send-to` links are registered as broadcast subscriptions, replaced by the receiver's hash
+    fn relink(&self, link: &LinkName, send: Sender<Point>) -> Result<(), ServiceError> {
+        if !self.send_queues.contains(link) {
+            return Err(ServiceError::link_not_found(self.name.join(), link.name()));
         }
+        let receiver_hash = PointTxId::from_str(&link.name());
+        self.receiver_dictionary.insert(receiver_hash, link.name());
+        self.subscriptions.add_broadcast(receiver_hash, send);
+        self.subscriptions_changed.store(true, Ordering::SeqCst);
+        log::debug!("{}.relink | '{}' ({}) - replaced", self.dbg, link, receiver_hash);
         Ok(())
     }
     //
     //
+    fn supports_relink(&self) -> bool {
+        true
+    }
+    //
+    //
     fn run(&self) -> Result<(), Error> {
         log::info!("{}.run | Starting...", self.dbg);
         let dbg = self.dbg.clone();
@@ -266,16 +356,16 @@ impl Service for MultiQueue {
         let subscriptions_ref = self.subscriptions.clone();
         let subscriptions_changed = self.subscriptions_changed.clone();
         // let receiver_dictionary = self.receiver_dictionary.clone();
+        let error = Error::new(&self.dbg, "run");
         for receiver_name in &self.send_queues {
-            let send = self.services.get_link(receiver_name).unwrap_or_else(|err| {
-                panic!("{}.run | services.get_link error: {:#?}", dbg, err);
-            });
+            let send = self.services.get_link(receiver_name)
+                .map_err(|err| error.pass_with(format!("Invalid send-to '{}'", receiver_name), err.to_string()))?;
             let receiver_hash = PointTxId::from_str(&receiver_name.name());
+            self.receiver_dictionary.insert(receiver_hash, receiver_name.name());
             self.subscriptions.add_broadcast(receiver_hash, send.clone());
             log::debug!("{}.run | Broadcast subscription registered, receiver: \n\t{} ({})", self.dbg, receiver_name, receiver_hash);
         }
         let exit = self.exit.clone();
-        let error = Error::new(&self.dbg, "run");
         match &self.scheduler {
             Some(scheduler) => {
                 let handle = scheduler.spawn(move|| {
@@ -0,0 +1,9 @@
+//!
+//! # Requesting point configurations from the `Services`
+//! 
+//! - All points, points of the one service or matching the criteria
+//! - Retained ids of the points
+//! 
+mod points_request;
+
+pub use points_request::*;
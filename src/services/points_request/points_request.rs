@@ -0,0 +1,43 @@
+use crate::services::{entity::PointConfig, subscription::SubscriptionCriteria};
+///
+/// Kind of the points requested from the `Services`
+///
+/// Point configurations are returned with the retained `id`,
+/// nothing is returned if `retain.point` is not configured
+#[derive(Debug, Clone, PartialEq)]
+pub enum PointsRequest {
+    ///
+    /// Points of the all services, except the points of the requester
+    /// - The requester owns the service with the same name and it's children, `/App/Producer` does not own `/App/ProducerA`
+    All,
+    ///
+    /// Points of the service with the name
+    Service(String),
+    ///
+    /// Points with the names matching any of the criterias, `cot` is not taken into account
+    Criteria(Vec<SubscriptionCriteria>),
+}
+//
+//
+impl PointsRequest {
+    ///
+    /// Returns `true` if the `point` owned by the `owner` is requested by the `requester`
+    pub fn matches(&self, requester: &str, owner: &str, point: &PointConfig) -> bool {
+        match self {
+            PointsRequest::All => !Self::is_owner(owner, requester),
+            PointsRequest::Service(service) => Self::is_owner(owner, service),
+            PointsRequest::Criteria(criterias) => criterias.iter().any(|criteria| criteria.name() == point.name),
+        }
+    }
+    ///
+    /// Returns `true` if the `name` is the `owner` service or the it's child,
+    /// leading `/` is not significant
+    pub fn is_owner(owner: &str, name: &str) -> bool {
+        let owner = owner.trim_start_matches('/');
+        let name = name.trim_start_matches('/');
+        match name.strip_prefix(owner) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+}
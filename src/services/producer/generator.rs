@@ -0,0 +1,249 @@
+use std::{f64::consts::PI, fs, time::Duration};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::services::conf::{ConfTree, ConfTreeGet};
+///
+/// The value produced by the [Generator]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeneratorValue {
+    Number(f64),
+    String(String),
+}
+///
+/// Produces the value of the point on the each cycle
+/// - Time based generators use `step * cycle` as the time, so the values do not depend on the cycle jitter
+///
+/// ```yaml
+/// point Point.Const:
+///     type: 'Int'
+///     generator: const
+///     value: 3                # number, bool or string
+/// point Point.Ramp:
+///     type: 'Int'
+///     generator: ramp         # from, from + step, ..., to, from, ...
+///     from: 0                 # default 0
+///     to: 100
+///     step: 1                 # default 1, negative for descending ramp
+/// point Point.Sine:
+///     type: 'Real'
+///     generator: sine         # offset + amplitude * sin(2 * PI * t / period + phase)
+///     amplitude: 10.0         # default 1
+///     offset: 0.0             # default 0
+///     period: 10 s
+///     phase: 90               # degrees, default 0
+/// point Point.Square:
+///     type: 'Bool'
+///     generator: square       # high during duty * period, then low
+///     low: 0                  # default 0
+///     high: 1                 # default 1
+///     period: 2 s
+///     duty: 0.5               # 0.0..1.0, default 0.5
+/// point Point.Random:
+///     type: 'Double'
+///     generator: random-walk  # previous value + random in -step..=step, limited by min / max
+///     start: 0.0              # default 0
+///     step: 0.5               # default 1, not negative
+///     min: -10.0              # optional
+///     max: 10.0               # optional, not less than min
+///     seed: 42                # optional, the same seed produces the same sequence
+/// point Point.Csv:
+///     type: 'Real'
+///     generator: csv          # next row of the column on the each cycle
+///     path: ./data/drive.csv  # comma separated, first row is the header
+///     column: Speed
+///     repeat: true            # start from the first row when finished, default true
+/// ```
+#[derive(Debug)]
+pub enum Generator {
+    Const {
+        value: GeneratorValue,
+    },
+    Ramp {
+        from: f64,
+        step: f64,
+        len: u64,
+    },
+    Sine {
+        amplitude: f64,
+        offset: f64,
+        period: Duration,
+        phase: f64,
+    },
+    Square {
+        low: f64,
+        high: f64,
+        period: Duration,
+        duty: f64,
+    },
+    RandomWalk {
+        value: f64,
+        step: f64,
+        min: f64,
+        max: f64,
+        rng: StdRng,
+    },
+    Csv {
+        values: Vec<GeneratorValue>,
+        repeat: bool,
+    },
+}
+//
+//
+impl Generator {
+    ///
+    /// Returns [Generator] parsed from the point config node
+    pub fn new(parent: impl Into<String>, conf: &ConfTree) -> Result<Self, Error> {
+        let dbg = Dbg::new(parent, "Generator");
+        let error = Error::new(&dbg, "new");
+        let kind: Option<String> = ConfTreeGet::<String>::get(conf, "generator");
+        match kind.as_deref() {
+            Some("const") => {
+                let value = match ConfTreeGet::<serde_yaml::Value>::get(conf, "value") {
+                    Some(value) => Self::value(&value).ok_or_else(|| error.err(format!("'{}' - invalid 'value': {:?}", conf.key, value)))?,
+                    None => return Err(error.err(format!("'{}' - 'value' not found", conf.key))),
+                };
+                Ok(Self::Const { value })
+            }
+            Some("ramp") => {
+                let from = Self::f64_or(conf, "from", 0.0).map_err(|err| error.pass(err))?;
+                let to = conf.as_f64("to").map_err(|err| error.pass(err))?;
+                let step = Self::f64_or(conf, "step", 1.0).map_err(|err| error.pass(err))?;
+                if step == 0.0 || (to - from) / step < 0.0 {
+                    return Err(error.err(format!("'{}' - 'step' {} can't reach 'to' {} from {}", conf.key, step, to, from)));
+                }
+                Ok(Self::Ramp { from, step, len: ((to - from) / step).floor() as u64 + 1 })
+            }
+            Some("sine") => Ok(Self::Sine {
+                amplitude: Self::f64_or(conf, "amplitude", 1.0).map_err(|err| error.pass(err))?,
+                offset: Self::f64_or(conf, "offset", 0.0).map_err(|err| error.pass(err))?,
+                period: Self::period(conf).map_err(|err| error.pass(err))?,
+                phase: Self::f64_or(conf, "phase", 0.0).map_err(|err| error.pass(err))?.to_radians(),
+            }),
+            Some("square") => {
+                let duty = Self::f64_or(conf, "duty", 0.5).map_err(|err| error.pass(err))?;
+                if !(0.0..=1.0).contains(&duty) {
+                    return Err(error.err(format!("'{}' - 'duty' {} out of range 0.0..1.0", conf.key, duty)));
+                }
+                Ok(Self::Square {
+                    low: Self::f64_or(conf, "low", 0.0).map_err(|err| error.pass(err))?,
+                    high: Self::f64_or(conf, "high", 1.0).map_err(|err| error.pass(err))?,
+                    period: Self::period(conf).map_err(|err| error.pass(err))?,
+                    duty,
+                })
+            }
+            Some("random-walk") => {
+                let seed = match ConfTreeGet::<serde_yaml::Value>::get(conf, "seed") {
+                    Some(seed) => seed.as_u64().ok_or_else(|| error.err(format!("'{}' - invalid 'seed': {:?}", conf.key, seed)))?,
+                    None => rand::rng().random(),
+                };
+                let step = Self::f64_or(conf, "step", 1.0).map_err(|err| error.pass(err))?;
+                if !step.is_finite() || step < 0.0 {
+                    return Err(error.err(format!("'{}' - 'step' {} must be finite and not negative", conf.key, step)));
+                }
+                let min = Self::f64_or(conf, "min", f64::MIN).map_err(|err| error.pass(err))?;
+                let max = Self::f64_or(conf, "max", f64::MAX).map_err(|err| error.pass(err))?;
+                if min.is_nan() || max.is_nan() || min > max {
+                    return Err(error.err(format!("'{}' - 'min' {} must not be greater than 'max' {}", conf.key, min, max)));
+                }
+                Ok(Self::RandomWalk {
+                    value: Self::f64_or(conf, "start", 0.0).map_err(|err| error.pass(err))?,
+                    step,
+                    min,
+                    max,
+                    rng: StdRng::seed_from_u64(seed),
+                })
+            }
+            Some("csv") => {
+                let path = conf.as_str("path").map_err(|err| error.pass(err))?;
+                let column = conf.as_str("column").map_err(|err| error.pass(err))?;
+                let repeat: Option<bool> = ConfTreeGet::<bool>::get(conf, "repeat");
+                let values = Self::csv(path, column).map_err(|err| error.pass(err))?;
+                log::debug!("{}.new | '{}' - {} rows loaded from '{}'", dbg, conf.key, values.len(), path);
+                Ok(Self::Csv { values, repeat: repeat.unwrap_or(true) })
+            }
+            Some(kind) => Err(error.err(format!("'{}' - unknown generator '{}', expected: const / ramp / sine / square / random-walk / csv", conf.key, kind))),
+            None => Err(error.err(format!("'{}' - 'generator' not found", conf.key))),
+        }
+    }
+    ///
+    /// Returns the value for the `step`, or `None` if generator is finished
+    /// - `step` - the number of the cycle, starting from 0
+    /// - `cycle` - the cycle interval of the producer
+    pub fn next(&mut self, step: u64, cycle: Duration) -> Option<GeneratorValue> {
+        let time = cycle.as_secs_f64() * step as f64;
+        match self {
+            Self::Const { value } => Some(value.clone()),
+            Self::Ramp { from, step: ramp_step, len } => Some(GeneratorValue::Number(*from + *ramp_step * (step % *len) as f64)),
+            Self::Sine { amplitude, offset, period, phase } => {
+                Some(GeneratorValue::Number(*offset + *amplitude * (2.0 * PI * time / period.as_secs_f64() + *phase).sin()))
+            }
+            Self::Square { low, high, period, duty } => {
+                let position = (time % period.as_secs_f64()) / period.as_secs_f64();
+                Some(GeneratorValue::Number(if position < *duty { *high } else { *low }))
+            }
+            Self::RandomWalk { value, step: walk_step, min, max, rng } => {
+                let current = *value;
+                if *walk_step > 0.0 {
+                    *value = (*value + rng.random_range(-*walk_step..=*walk_step)).clamp(*min, *max);
+                }
+                Some(GeneratorValue::Number(current))
+            }
+            Self::Csv { values, repeat } => {
+                let index = if *repeat { step as usize % values.len() } else { step as usize };
+                values.get(index).cloned()
+            }
+        }
+    }
+    ///
+    /// Returns the value of the `const` generator
+    fn value(value: &serde_yaml::Value) -> Option<GeneratorValue> {
+        match value {
+            serde_yaml::Value::Bool(value) => Some(GeneratorValue::Number(if *value { 1.0 } else { 0.0 })),
+            serde_yaml::Value::Number(value) => value.as_f64().map(GeneratorValue::Number),
+            serde_yaml::Value::String(value) => Some(GeneratorValue::String(value.clone())),
+            _ => None,
+        }
+    }
+    ///
+    /// Returns the number by `key`, or `default` if `key` is not specified
+    fn f64_or(conf: &ConfTree, key: &str, default: f64) -> Result<f64, Error> {
+        match ConfTreeGet::<serde_yaml::Value>::get(conf, key) {
+            Some(_) => conf.as_f64(key),
+            None => Ok(default),
+        }
+    }
+    ///
+    /// Returns non zero `period`
+    fn period(conf: &ConfTree) -> Result<Duration, Error> {
+        let period = conf.get_duration("period")?;
+        if period.is_zero() {
+            return Err(Error::new("Generator", "period").err(format!("'{}' - 'period' can't be zero", conf.key)));
+        }
+        Ok(period)
+    }
+    ///
+    /// Returns the values of the `column` from the CSV file
+    /// - Comma separated, first row is the header
+    /// - Numbers are parsed as numbers, another values are kept as strings
+    fn csv(path: &str, column: &str) -> Result<Vec<GeneratorValue>, Error> {
+        let error = Error::new("Generator", "csv");
+        let text = fs::read_to_string(path).map_err(|err| error.pass_with(format!("File '{}' reading error", path), err.to_string()))?;
+        let mut rows = text.lines().filter(|row| !row.trim().is_empty());
+        let header = rows.next().ok_or_else(|| error.err(format!("File '{}' - is empty", path)))?;
+        let index = header.split(',').position(|name| name.trim() == column)
+            .ok_or_else(|| error.err(format!("File '{}' - column '{}' not found in the header: '{}'", path, column, header)))?;
+        let values: Vec<GeneratorValue> = rows.enumerate().map(|(row, line)| {
+            match line.split(',').nth(index).map(|value| value.trim()) {
+                Some(value) => Ok(match value.parse::<f64>() {
+                    Ok(number) => GeneratorValue::Number(number),
+                    Err(_) => GeneratorValue::String(value.to_owned()),
+                }),
+                None => Err(error.err(format!("File '{}' - column '{}' not found in the row {}: '{}'", path, column, row + 2, line))),
+            }
+        }).collect::<Result<_, _>>()?;
+        if values.is_empty() {
+            return Err(error.err(format!("File '{}' - no rows found", path)));
+        }
+        Ok(values)
+    }
+}
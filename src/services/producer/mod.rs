@@ -0,0 +1,16 @@
+//!
+//! # Producer of the generated points
+//! 
+//! - Publishes configured points with the fixed cycle
+//! - Generators: const, ramp, sine, square, random walk, CSV replay
+//! - Used for commissioning and integration testing without real devices
+//! 
+mod generator;
+mod producer_conf;
+mod producer_point;
+mod producer_service;
+
+pub use generator::*;
+pub use producer_conf::*;
+pub use producer_point::*;
+pub use producer_service::*;
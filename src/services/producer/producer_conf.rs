@@ -0,0 +1,79 @@
+use std::{str::FromStr, time::Duration};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::services::{
+    conf::{ConfTree, ConfTreeGet},
+    entity::{Cot, Name, Status},
+    service::LinkName,
+    task::functions::{FnConfKeywd, FnConfKindName},
+};
+use super::producer_point::ProducerPoint;
+///
+/// Configuration of the `ProducerService`
+///
+/// ```yaml
+/// service ProducerService Producer:
+///     cycle: 100 ms                   # publishing interval of the all points
+///     send-to: /App/MultiQueue.in-queue
+///     cot: Inf                        # optional, Cot of the points, default Inf
+///     status: Ok                      # optional, Status of the points, default Ok
+///     point Drive.Speed:
+///         type: 'Real'
+///         generator: sine
+///         amplitude: 100.0
+///         period: 10 s
+///     point Drive.State:
+///         type: 'Int'
+///         status: Invalid             # overrides the status of the service
+///         generator: const
+///         value: 1
+/// ```
+#[derive(Debug)]
+pub struct ProducerConf {
+    pub(crate) name: Name,
+    pub(crate) cycle: Duration,
+    pub(crate) send_to: LinkName,
+    pub(crate) points: Vec<ProducerPoint>,
+}
+//
+//
+impl ProducerConf {
+    ///
+    /// Returns [ProducerConf] parsed from the service config node
+    /// - `parent` - the name of the application
+    pub fn new(parent: impl Into<String>, conf: ConfTree) -> Result<Self, Error> {
+        let me = conf.sufix_or(conf.name()?);
+        let name = Name::new(parent, &me);
+        let dbg = Dbg::new(name.parent(), format!("ProducerConf({})", me));
+        let error = Error::new(&dbg, "new");
+        log::trace!("{}.new | conf: {:?}", dbg, conf);
+        let cycle = conf.get_duration("cycle").map_err(|err| error.pass_with("Parameter 'cycle' - invalid or not found", err.to_string()))?;
+        if cycle.is_zero() {
+            return Err(error.err("Parameter 'cycle' - can't be zero"));
+        }
+        let send_to: Option<String> = ConfTreeGet::<String>::get(&conf, "send-to");
+        let send_to = send_to
+            .ok_or_else(|| error.err("Parameter 'send-to' - String expected"))
+            .and_then(|send_to| LinkName::from_str(&send_to).map_err(|err| error.err(err)))?;
+        let cot = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "cot") {
+            Some(_) => conf.parse::<Cot>("cot").map_err(|err| error.pass(err))?,
+            None => Cot::Inf,
+        };
+        let status = match ConfTreeGet::<String>::get(&conf, "status") {
+            Some(value) => Status::from_str(&value).map_err(|err| error.err(err))?,
+            None => Status::Ok,
+        };
+        let mut points = vec![];
+        for node in conf.sub_nodes().into_iter().flatten() {
+            if let Ok(keyword) = FnConfKeywd::from_str(&node.key) {
+                if keyword.kind() == FnConfKindName::Point {
+                    points.push(ProducerPoint::new(name.join(), &node, cot, status).map_err(|err| error.pass(err))?);
+                }
+            }
+        }
+        if points.is_empty() {
+            log::warn!("{}.new | No points configured", dbg);
+        }
+        log::debug!("{}.new | cycle: {:?}, send-to: {}, points: {}", dbg, cycle, send_to, points.len());
+        Ok(Self { name, cycle, send_to, points })
+    }
+}
@@ -0,0 +1,89 @@
+use std::{str::FromStr, time::Duration};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    kernel::clock::GlobalClock,
+    services::{
+        conf::{ConfTree, ConfTreeGet},
+        entity::{Cot, Point, PointConfig, PointConfigType, PointHlr, Status},
+        types::Bool,
+    },
+};
+use super::generator::{Generator, GeneratorValue};
+///
+/// The point published by the `ProducerService`
+///
+/// ```yaml
+/// point Drive.Speed:
+///     type: 'Real'
+///     cot: Act            # optional, overrides the `cot` of the service
+///     status: Invalid     # optional, overrides the `status` of the service
+///     generator: sine     # see [Generator]
+///     period: 10 s
+/// ```
+#[derive(Debug)]
+pub struct ProducerPoint {
+    pub(crate) conf: PointConfig,
+    pub(crate) cot: Cot,
+    pub(crate) status: Status,
+    generator: Generator,
+}
+//
+//
+impl ProducerPoint {
+    ///
+    /// Returns [ProducerPoint] parsed from the `point` node
+    /// - `parent` - the name of the producer, parent of the point name
+    /// - `cot`, `status` - defaults of the producer
+    pub fn new(parent: impl Into<String>, conf: &ConfTree, cot: Cot, status: Status) -> Result<Self, Error> {
+        let parent = parent.into();
+        let dbg = Dbg::new(&parent, "ProducerPoint");
+        let error = Error::new(&dbg, "new");
+        let point_conf = PointConfig::new(&parent, conf);
+        if point_conf.type_ == PointConfigType::Json {
+            return Err(error.err(format!("'{}' - type Json is not supported", point_conf.name)));
+        }
+        let cot = match ConfTreeGet::<serde_yaml::Value>::get(conf, "cot") {
+            Some(_) => conf.parse::<Cot>("cot").map_err(|err| error.pass(err))?,
+            None => cot,
+        };
+        let status = match ConfTreeGet::<String>::get(conf, "status") {
+            Some(value) => Status::from_str(&value).map_err(|err| error.err(format!("'{}' - {}", point_conf.name, err)))?,
+            None => status,
+        };
+        let generator = Generator::new(&dbg, conf).map_err(|err| error.pass(err))?;
+        log::debug!("{}.new | '{}' ({:?}, {:?}, {:?}): {:?}", dbg, point_conf.name, point_conf.type_, cot, status, generator);
+        Ok(Self { conf: point_conf, cot, status, generator })
+    }
+    ///
+    /// Returns the point generated for the `step`, or `None` if the generator is finished
+    /// - `tx_id` - the producer of the point
+    /// - `step` - the number of the cycle, starting from 0
+    /// - `cycle` - the cycle interval of the producer
+    pub fn next(&mut self, tx_id: usize, step: u64, cycle: Duration) -> Option<Point> {
+        let value = self.generator.next(step, cycle)?;
+        let name = &self.conf.name;
+        let timestamp = GlobalClock::utc();
+        let number = |value: &GeneratorValue| match value {
+            GeneratorValue::Number(value) => *value,
+            GeneratorValue::String(value) => value.parse().unwrap_or_else(|_| {
+                log::warn!("ProducerPoint.next | '{}' - number expected, but found: '{}'", name, value);
+                0.0
+            }),
+        };
+        let point = match self.conf.type_ {
+            PointConfigType::Bool => Point::Bool(PointHlr::new(tx_id, name, Bool(number(&value) != 0.0), self.status, self.cot, timestamp)),
+            PointConfigType::Int => Point::Int(PointHlr::new(tx_id, name, number(&value).round() as i64, self.status, self.cot, timestamp)),
+            PointConfigType::Real => Point::Real(PointHlr::new(tx_id, name, number(&value) as f32, self.status, self.cot, timestamp)),
+            PointConfigType::Double => Point::Double(PointHlr::new(tx_id, name, number(&value), self.status, self.cot, timestamp)),
+            PointConfigType::String => {
+                let value = match value {
+                    GeneratorValue::Number(value) => value.to_string(),
+                    GeneratorValue::String(value) => value,
+                };
+                Point::String(PointHlr::new(tx_id, name, value, self.status, self.cot, timestamp))
+            }
+            PointConfigType::Json => return None,
+        };
+        Some(point)
+    }
+}
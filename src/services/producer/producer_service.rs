@@ -0,0 +1,201 @@
+use std::{
+    sync::{atomic::{AtomicBool, AtomicU64, Ordering}, Arc},
+    time::Duration,
+};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    kernel::clock::GlobalClock,
+    services::{
+        entity::{Name, Object, Point, PointConfig, PointTxId},
+        service::{LinkName, Service, ServiceCycle, ServiceError},
+        services::Services,
+    },
+    sync::{channel::Sender, Handles, Mutex, RwLock},
+    thread_pool::Scheduler,
+};
+use super::{producer_conf::ProducerConf, producer_point::ProducerPoint};
+///
+/// ### Publishes generated points with the fixed cycle
+/// - Each configured point has its own [Generator](super::Generator):
+///   const, ramp, sine, square, random walk or replay from the CSV file
+/// - Points are sent to the `send-to` on the each cycle
+/// - Cot and status of the points are configurable per service and per point
+/// - Cycles and timestamps are taken from the clock of the `Services`
+/// - The `send-to` link can be replaced by `relink` while running, if the linked service is restarted
+/// - Generated values depend on the cycle number only (and on the `seed` for the random walk),
+///   so the same config always produces the same sequence
+///
+/// Used for commissioning of the HMI and for integration tests, when the real devices are not available
+pub struct ProducerService {
+    dbg: Dbg,
+    name: Name,
+    cycle: Duration,
+    send_to: LinkName,
+    send: Arc<RwLock<Option<Sender<Point>>>>,
+    points: Mutex<Option<Vec<ProducerPoint>>>,
+    point_confs: Vec<PointConfig>,
+    services: Arc<Services>,
+    scheduler: Option<Scheduler>,
+    steps: Arc<AtomicU64>,
+    handles: Handles<()>,
+    exit: Arc<AtomicBool>,
+}
+//
+//
+impl ProducerService {
+    ///
+    /// Returns [ProducerService] new instance
+    pub fn new(conf: ProducerConf, services: Arc<Services>, scheduler: Option<Scheduler>) -> Self {
+        let dbg = Dbg::new(conf.name.parent(), conf.name.me());
+        Self {
+            name: conf.name,
+            cycle: conf.cycle,
+            send_to: conf.send_to,
+            send: Arc::new(RwLock::new(None)),
+            point_confs: conf.points.iter().map(|point| point.conf.clone()).collect(),
+            points: Mutex::new(Some(conf.points)),
+            services,
+            scheduler,
+            steps: Arc::new(AtomicU64::new(0)),
+            handles: Handles::new(&dbg),
+            exit: Arc::new(AtomicBool::new(false)),
+            dbg,
+        }
+    }
+    ///
+    /// Returns the number of the cycles done
+    pub fn steps(&self) -> u64 {
+        self.steps.load(Ordering::SeqCst)
+    }
+    ///
+    /// Main loop
+    /// - `send` - the `send-to` link, read once per cycle, might be replaced by `relink`
+    fn run_(dbg: Dbg, name: Name, interval: Duration, mut points: Vec<ProducerPoint>, send: Arc<RwLock<Option<Sender<Point>>>>, steps: Arc<AtomicU64>, exit: Arc<AtomicBool>) {
+        log::info!("{}.run | Preparing thread - ok", dbg);
+        let tx_id = PointTxId::from_str(&name.join());
+        let mut cycle = ServiceCycle::new(&dbg, interval);
+        let mut step = 0;
+        loop {
+            cycle.start();
+            let Some(send) = send.read().clone() else {
+                log::error!("{}.run | Send-to link - not resolved", dbg);
+                break;
+            };
+            for point in points.iter_mut() {
+                if let Some(point) = point.next(tx_id, step, interval) {
+                    log::trace!("{}.run | sending: {:?}", dbg, point);
+                    if let Err(err) = send.send(point) {
+                        log::error!("{}.run | Send error: {:?}", dbg, err);
+                    }
+                }
+            }
+            step += 1;
+            steps.store(step, Ordering::SeqCst);
+            if exit.load(Ordering::SeqCst) {
+                break;
+            }
+            cycle.wait();
+            if exit.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+        log::info!("{}.run | Exit", dbg);
+    }
+}
+//
+//
+impl Object for ProducerService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+//
+impl std::fmt::Debug for ProducerService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("ProducerService")
+            .field("id", &self.dbg)
+            .finish()
+    }
+}
+//
+//
+impl Service for ProducerService {
+    //
+    //
+    fn send_to(&self) -> Vec<LinkName> {
+        vec![self.send_to.clone()]
+    }
+    //
+    //
+    fn points(&self) -> Vec<PointConfig> {
+        self.point_confs.clone()
+    }
+    //
+    //
+    fn relink(&self, link: &LinkName, send: Sender<Point>) -> Result<(), ServiceError> {
+        if *link != self.send_to {
+            return Err(ServiceError::link_not_found(self.name.join(), link.name()));
+        }
+        log::debug!("{}.relink | '{}' - replaced", self.dbg, link);
+        *self.send.write() = Some(send);
+        Ok(())
+    }
+    //
+    //
+    fn supports_relink(&self) -> bool {
+        true
+    }
+    //
+    //
+    fn run(&self) -> Result<(), Error> {
+        log::info!("{}.run | Starting...", self.dbg);
+        let error = Error::new(&self.dbg, "run");
+        let dbg = self.dbg.clone();
+        let name = self.name.clone();
+        let interval = self.cycle;
+        let send = self.services.get_link(&self.send_to)
+            .map_err(|err| error.pass_with(format!("Invalid send-to '{}'", self.send_to), err.to_string()))?;
+        let points = self.points.lock().take().ok_or_else(|| error.err("Already started"))?;
+        *self.send.write() = Some(send);
+        let send = self.send.clone();
+        let steps = self.steps.clone();
+        let clock = self.services.clock();
+        let exit = self.exit.clone();
+        match &self.scheduler {
+            Some(scheduler) => {
+                let handle = scheduler.spawn(move || {
+                    let _clock = GlobalClock::local(clock);
+                    Self::run_(dbg, name, interval, points, send, steps, exit);
+                    Ok(())
+                }).map_err(|err| error.pass_with("Start failed on Scheduler", err.to_string()))?;
+                self.handles.push(handle);
+            }
+            None => {
+                let handle = std::thread::Builder::new().name(format!("{}.run", dbg)).spawn(move || {
+                    let _clock = GlobalClock::local(clock);
+                    Self::run_(dbg, name, interval, points, send, steps, exit);
+                }).map_err(|err| error.pass_with("Start failed on std::thread", err.to_string()))?;
+                self.handles.push(handle);
+            }
+        };
+        log::info!("{}.run | Started", self.dbg);
+        Ok(())
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::SeqCst);
+    }
+}
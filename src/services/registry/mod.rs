@@ -0,0 +1,9 @@
+//!
+//! # Building services from configuration
+//! 
+//! - Factory per service kind, registered by the service name (`Task`, `TcpClient`, ...)
+//! - Loading all `service` / `task` nodes of the root configuration
+//! 
+mod service_registry;
+
+pub use service_registry::*;
@@ -0,0 +1,244 @@
+use std::{str::FromStr, sync::Arc};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    collections::FxIndexMap,
+    services::{
+        cache::{CacheConf, CacheService},
+        conf::{ConfKeywd, ConfTree},
+        history::{HistoryConf, HistoryService},
+        entity::Object,
+        metrics::{MetricsConf, MetricsService},
+        multi_queue::{MultiQueue, MultiQueueConf},
+        producer::{ProducerConf, ProducerService},
+        service::{Reconfigured, Service},
+        services::Services,
+        tcp::{TcpClient, TcpConf, TcpServer},
+    },
+    thread_pool::Scheduler,
+};
+///
+/// Creates the service from its configuration node
+/// - `conf` - the node of the service, for example `service MultiQueue: ...`
+/// - `services` - the `Services` the service will be inserted into,
+///   `services.name().parent()` is the parent name for the service
+/// - `scheduler` - optional thread pool for the service threads
+pub type ServiceFactory = fn(ConfTree, Arc<Services>, Option<Scheduler>) -> Result<Arc<dyn Service>, Error>;
+///
+/// Holds factories of the services by the service name
+/// 
+/// **Example**
+/// ```ignore
+/// let mut registry = ServiceRegistry::new(dbg);
+/// registry.register(Services::TASK, |conf, services, scheduler| {
+///     let conf = TaskConfig::new(services.name().parent(), conf);
+///     Ok(Arc::new(Task::new(conf, services, scheduler)))
+/// });
+/// let names = registry.load(&conf, &services, Some(thread_pool.scheduler()))?;
+/// ```
+/// 
+/// `MultiQueue`, `CacheService`, `HistoryService`, `ProducerService`, `TcpServer`, `TcpClient` and `MetricsService` are registered by default
+pub struct ServiceRegistry {
+    dbg: Dbg,
+    factories: FxIndexMap<String, ServiceFactory>,
+}
+//
+//
+impl ServiceRegistry {
+    ///
+    /// Returns [ServiceRegistry] new instance with factories of the services defined in this crate
+    pub fn new(parent: impl Into<String>) -> Self {
+        let mut registry = Self::empty(parent);
+        registry.register(Services::MULTI_QUEUE, Self::multi_queue);
+        registry.register(Services::CACHE_SERVICE, Self::cache_service);
+        registry.register(Services::HISTORY_SERVICE, Self::history_service);
+        registry.register(Services::PRODUCER_SERVICE, Self::producer_service);
+        registry.register(Services::TCP_SERVER, Self::tcp_server);
+        registry.register(Services::TCP_CLIENT, Self::tcp_client);
+        registry.register(Services::METRICS_SERVICE, Self::metrics_service);
+        registry
+    }
+    ///
+    /// Returns [ServiceRegistry] new instance without any factories
+    pub fn empty(parent: impl Into<String>) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "ServiceRegistry"),
+            factories: FxIndexMap::default(),
+        }
+    }
+    ///
+    /// Registers the factory for the service `name`, replaces existing one
+    /// - `name` - the name of the service in the config keyword, for example `TcpClient` in `service TcpClient Ied01`
+    pub fn register(&mut self, name: impl Into<String>, factory: ServiceFactory) {
+        let name = name.into();
+        if self.factories.insert(name.clone(), factory).is_some() {
+            log::warn!("{}.register | Factory of '{}' - replaced", self.dbg, name);
+        }
+    }
+    ///
+    /// Returns `true` if the factory for the service `name` is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+    ///
+    /// Returns names of the all registered services
+    pub fn names(&self) -> Vec<String> {
+        self.factories.keys().cloned().collect()
+    }
+    ///
+    /// Creates the service from the configuration node by the registered factory
+    pub fn build(&self, conf: ConfTree, services: Arc<Services>, scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        let error = Error::new(&self.dbg, "build");
+        let keywd = ConfKeywd::from_str(&conf.key).map_err(|err| error.pass(err))?;
+        match self.factories.get(&keywd.name()) {
+            Some(factory) => factory(conf, services, scheduler).map_err(|err| error.pass(err)),
+            None => Err(error.err(format!("Unknown service '{}' in '{}', registered: {:?}", keywd.name(), conf.key, self.names()))),
+        }
+    }
+    ///
+    /// Creates and inserts into the `services` all `service` and `task` nodes of the root `conf`
+    /// - Another nodes are ignored
+    /// - Services are inserted in the config order
+    /// 
+    /// Returns names of the inserted services,
+    /// or single error listing all unknown services, failed factories and duplicated names
+    pub fn load(&self, conf: &ConfTree, services: &Arc<Services>, scheduler: Option<Scheduler>) -> Result<Vec<String>, Error> {
+        let error = Error::new(&self.dbg, "load");
+        let mut names = vec![];
+        let mut errors = vec![];
+        for node in conf.sub_nodes().into_iter().flatten() {
+            let keywd = match ConfKeywd::from_str(&node.key) {
+                Ok(keywd) if keywd.kind() == "service" || keywd.kind() == "task" => keywd,
+                _ => {
+                    log::trace!("{}.load | Skipped '{}'", self.dbg, node.key);
+                    continue;
+                }
+            };
+            let Some(factory) = self.factories.get(&keywd.name()) else {
+                errors.push(format!("'{}': unknown service '{}'", node.key, keywd.name()));
+                continue;
+            };
+            log::debug!("{}.load | Building '{}'...", self.dbg, node.key);
+            let key = node.key.clone();
+            match factory(node.clone(), services.clone(), scheduler.clone()) {
+                Ok(service) => {
+                    let name = service.name().join();
+                    match services.try_insert(service) {
+                        Ok(_) => {
+                            log::debug!("{}.load | Building '{}' - ok: {}", self.dbg, key, name);
+                            services.set_conf(&name, node);
+                            names.push(name);
+                        }
+                        Err(err) => errors.push(format!("'{}': {}", key, err)),
+                    }
+                }
+                Err(err) => errors.push(format!("'{}': {}", key, err)),
+            }
+        }
+        if errors.is_empty() {
+            log::info!("{}.load | Loaded services: {:#?}", self.dbg, names);
+            Ok(names)
+        } else {
+            Err(error.err(format!("Services - not loaded: \n\t{}\nregistered: {:?}", errors.join("\n\t"), self.names())))
+        }
+    }
+    ///
+    /// Applies changed root `conf` to the services loaded by `load`
+    /// - Unchanged services keep running
+    /// - Changed services are reconfigured or restarted, see `Services::reconfigure`
+    /// - Services removed from the `conf` keep running
+    /// 
+    /// Returns the result for the each `service` / `task` node,
+    /// or single error listing all failed services and nodes not loaded before
+    pub fn reconfigure(&self, conf: &ConfTree, services: &Arc<Services>, scheduler: Option<Scheduler>) -> Result<Vec<(String, Reconfigured)>, Error> {
+        let error = Error::new(&self.dbg, "reconfigure");
+        let loaded: Vec<(String, String)> = services.all().into_keys()
+            .filter_map(|name| services.conf(&name).map(|conf| (conf.key, name)))
+            .collect();
+        let mut results = vec![];
+        let mut errors = vec![];
+        let mut keys = vec![];
+        for node in conf.sub_nodes().into_iter().flatten() {
+            let keywd = match ConfKeywd::from_str(&node.key) {
+                Ok(keywd) if keywd.kind() == "service" || keywd.kind() == "task" => keywd,
+                _ => continue,
+            };
+            keys.push(node.key.clone());
+            let Some((_, name)) = loaded.iter().find(|(key, _)| *key == node.key) else {
+                errors.push(format!("'{}': not loaded, can't be reconfigured", node.key));
+                continue;
+            };
+            let Some(factory) = self.factories.get(&keywd.name()) else {
+                errors.push(format!("'{}': unknown service '{}'", node.key, keywd.name()));
+                continue;
+            };
+            let key = node.key.clone();
+            match services.reconfigure(name, node, |conf| factory(conf, services.clone(), scheduler.clone())) {
+                Ok(result) => results.push((name.clone(), result)),
+                Err(err) => errors.push(format!("'{}': {}", key, err)),
+            }
+        }
+        for (key, name) in &loaded {
+            if !keys.contains(key) {
+                log::warn!("{}.reconfigure | '{}' removed from config, keeps running: {}", self.dbg, key, name);
+            }
+        }
+        if errors.is_empty() {
+            log::info!("{}.reconfigure | Reconfigured services: {:#?}", self.dbg, results);
+            Ok(results)
+        } else {
+            Err(error.err(format!("Services - not reconfigured: \n\t{}", errors.join("\n\t"))))
+        }
+    }
+    ///
+    /// Factory of the [MultiQueue]
+    fn multi_queue(conf: ConfTree, services: Arc<Services>, scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        let conf = MultiQueueConf::new(services.name().parent(), conf);
+        Ok(Arc::new(MultiQueue::new(conf, services, scheduler)))
+    }
+    ///
+    /// Factory of the [CacheService]
+    fn cache_service(conf: ConfTree, services: Arc<Services>, scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        let conf = CacheConf::new(services.name().parent(), conf)?;
+        Ok(Arc::new(CacheService::new(conf, services, scheduler)))
+    }
+    ///
+    /// Factory of the [HistoryService]
+    fn history_service(conf: ConfTree, services: Arc<Services>, scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        let conf = HistoryConf::new(services.name().parent(), conf)?;
+        Ok(Arc::new(HistoryService::new(conf, services, scheduler)))
+    }
+    ///
+    /// Factory of the [ProducerService]
+    fn producer_service(conf: ConfTree, services: Arc<Services>, scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        let conf = ProducerConf::new(services.name().parent(), conf)?;
+        Ok(Arc::new(ProducerService::new(conf, services, scheduler)))
+    }
+    ///
+    /// Factory of the [TcpServer]
+    fn tcp_server(conf: ConfTree, services: Arc<Services>, scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        let conf = TcpConf::new(services.name().parent(), conf)?;
+        Ok(Arc::new(TcpServer::new(conf, services, scheduler)))
+    }
+    ///
+    /// Factory of the [TcpClient]
+    fn tcp_client(conf: ConfTree, services: Arc<Services>, scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        let conf = TcpConf::new(services.name().parent(), conf)?;
+        Ok(Arc::new(TcpClient::new(conf, services, scheduler)))
+    }
+    ///
+    /// Factory of the [MetricsService]
+    fn metrics_service(conf: ConfTree, services: Arc<Services>, scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        let conf = MetricsConf::new(services.name().parent(), conf)?;
+        Ok(Arc::new(MetricsService::new(conf, services, scheduler)))
+    }
+}
+//
+//
+impl std::fmt::Debug for ServiceRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceRegistry")
+            .field("dbg", &self.dbg)
+            .field("factories", &self.names())
+            .finish()
+    }
+}
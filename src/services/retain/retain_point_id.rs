@@ -1,4 +1,4 @@
-use crate::{collections::FxHashMap, services::entity::{PointConfig, PointConfigType}};
+use crate::{collections::FxHashMap, kernel::metrics::{Counter, Gauge, Metrics}, services::entity::{PointConfig, PointConfigType}};
 use std::{collections::HashMap, env, ffi::OsStr, fs, hash::BuildHasherDefault, path::{Path, PathBuf}};
 use api_tools::{api::reply::api_reply::ApiReply, client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::ApiRequest}};
 use dashmap::DashMap;
@@ -13,12 +13,16 @@ type RetainedCahe = FxHashMap<String, FxHashMap<String, RetainedPointConfig>>;
 /// Stores unique Point ID
 /// - In the json file
 /// - In the database, if `api` is specified
+/// - Cached points, writes and failed database requests are reported into the `Metrics::global()`
 #[derive(Debug)]
 pub struct RetainPointId {
     id: String,
     cache: DashMap<String, Vec<PointConfig>>,
     path: PathBuf,
     conf: RetainConf,
+    points_metric: Gauge,
+    writes_metric: Counter,
+    errors_metric: Counter,
 }
 //
 //
@@ -35,7 +39,11 @@ impl RetainPointId {
             Ok(path) => path,
             Err(err) => panic!("{}.new | Error: {:#?}", id, err),
         };
+        let labels = [("retain", id.as_str())];
         Self {
+            points_metric: Metrics::global().gauge("sal_retain_points", "Points cached by the RetainPointId", &labels),
+            writes_metric: Metrics::global().counter("sal_retain_point_writes_total", "Updates of the retained point id's", &labels),
+            errors_metric: Metrics::global().counter("sal_retain_point_errors_total", "Failed database requests of the RetainPointId", &labels),
             id,
             cache: DashMap::new(),
             path,
@@ -77,7 +85,9 @@ impl RetainPointId {
                 .or_insert(vec![])
                 .push(point.clone());
         }
+        self.points_metric.set(self.cache.iter().map(|r| r.value().len()).sum::<usize>() as f64);
         if update_retained {
+            self.writes_metric.inc();
             self.write(&self.path, &retained).unwrap();
             self.sql_write(&retained)
         }
@@ -187,11 +197,15 @@ impl RetainPointId {
                     api_keep_alive,
                     false,
                 );
-                _ = self.sql_request(&mut request, "truncate public.tags;", api_keep_alive);
+                if self.sql_request(&mut request, "truncate public.tags;", api_keep_alive).is_err() {
+                    self.errors_metric.inc();
+                }
                 for (_owner, points) in retained {
                     for point in points.values() {
                         let sql = format!("insert into public.tags (id, type, name) values ({},'{:?}','{}');", point.id, point._type, point.name);
-                        _ = self.sql_request(&mut request, &sql, api_keep_alive);
+                        if self.sql_request(&mut request, &sql, api_keep_alive).is_err() {
+                            self.errors_metric.inc();
+                        }
                     }
                 }
             }
@@ -17,10 +17,24 @@
 //!
 use std::time::Duration;
 mod link_name;
+mod reconfigured;
 mod service_cycle;
+mod service_cycle_mode;
+mod service_cycle_stats;
+mod service_deps;
+mod service_error;
+mod service_state;
+mod service_states;
 mod service;
 pub const RECV_TIMEOUT: Duration = Duration::from_millis(100);
 
 pub use link_name::*;
+pub use reconfigured::*;
 pub use service_cycle::*;
+pub use service_cycle_mode::*;
+pub use service_cycle_stats::*;
+pub use service_deps::*;
+pub use service_error::*;
+pub use service_state::*;
+pub use service_states::*;
 pub use service::*;
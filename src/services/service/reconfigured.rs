@@ -0,0 +1,11 @@
+///
+/// Result of the [Services::reconfigure](crate::services::Services::reconfigure)
+/// - `Unchanged` - configuration is not changed, service keeps running
+/// - `Applied` - configuration applied by the service itself, service keeps running
+/// - `Restarted` - service does not supports reconfiguration, so it was restarted with the new configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reconfigured {
+    Unchanged,
+    Applied,
+    Restarted,
+}
@@ -1,6 +1,7 @@
 use sal_core::error::Error;
 use crate::{services::{
-    entity::{Object, Point, PointConfig}, future::Future, subscription::SubscriptionCriteria
+    conf::ConfTree, entity::{Object, Point, PointConfig}, future::Future, service::{LinkName, ServiceError, ServiceState}, subscription::SubscriptionCriteria,
+    topology::TopologySubscription,
 }, sync::channel::{Receiver, Sender}};
 ///
 /// Interface for application service
@@ -11,27 +12,136 @@ pub trait Service: Object + std::fmt::Debug + Send + Sync {
     // fn id(&self) -> &str;
     ///
     /// Returns copy of the Sender - service's incoming queue
-    #[allow(unused_variables)]
+    /// 
+    /// ## Panics
+    /// - If `try_get_link` returned an error
     fn get_link(&self, name: &str) -> Sender<Point> {
-        panic!("{}.get_link | Does not supported", self.name())
+        match self.try_get_link(name) {
+            Ok(send) => send,
+            Err(err) => panic!("{}.get_link | {}", self.name(), err),
+        }
     }
     ///
-    /// Returns Receiver
+    /// Returns copy of the Sender - service's incoming queue
+    /// - `LinkNotFound` if service has no incoming queue with the `name`
+    /// - `Unsupported` if not implemented, services having incoming queues should implement this
     #[allow(unused_variables)]
+    fn try_get_link(&self, name: &str) -> Result<Sender<Point>, ServiceError> {
+        Err(ServiceError::unsupported(self.name().join(), "get_link"))
+    }
+    ///
+    /// Returns Receiver
+    /// 
+    /// ## Panics
+    /// - If `try_subscribe` returned an error
     fn subscribe(&self, receiver_id: &str, points: &[SubscriptionCriteria]) -> (Sender<Point>, Receiver<Point>) {
-        panic!("{}.subscribe | Does not supported", self.name())
+        match self.try_subscribe(receiver_id, points) {
+            Ok(subscription) => subscription,
+            Err(err) => panic!("{}.subscribe | {}", self.name(), err),
+        }
     }
     ///
-    /// Extends the sucessfully with additiuonal points
+    /// Returns Receiver
+    /// - `Unsupported` if not implemented, services supporting subscriptions should implement this
     #[allow(unused_variables)]
+    fn try_subscribe(&self, receiver_id: &str, points: &[SubscriptionCriteria]) -> Result<(Sender<Point>, Receiver<Point>), ServiceError> {
+        Err(ServiceError::unsupported(self.name().join(), "subscribe"))
+    }
+    ///
+    /// Re-creates the subscription using existing `send` - Sender of the receiver's channel
+    /// 
+    /// Used to restore subscriptions on the restarted [Service],
+    /// so the receiver keeps receiving from the same channel
+    #[allow(unused_variables)]
+    fn resubscribe(&self, receiver_name: &str, points: &[SubscriptionCriteria], send: Sender<Point>) -> Result<(), Error> {
+        Err(Error::new(self.name().join(), "resubscribe").err("Does not supported"))
+    }
+    ///
+    /// Replaces the Sender of the `send-to` link with the `send` - incoming queue of the restarted [Service]
+    /// 
+    /// Used by `Services::reconfigure` to re-resolve the links held by this [Service],
+    /// so points sent to the restarted [Service] are not lost in the queue of the finished one
+    /// - `LinkNotFound` if the [Service] has no `send-to` with the `link`
+    /// - `Unsupported` if not implemented, the linked [Service] can not be restarted then
+    /// 
+    /// Services implementing `relink` must return `true` from `supports_relink`
+    #[allow(unused_variables)]
+    fn relink(&self, link: &LinkName, send: Sender<Point>) -> Result<(), ServiceError> {
+        Err(ServiceError::unsupported(self.name().join(), "relink"))
+    }
+    ///
+    /// Returns `true` if the [Service] implements `relink`
+    /// 
+    /// Checked by `Services::reconfigure` before the linked [Service] is restarted,
+    /// so restart is refused without touching the links
+    fn supports_relink(&self) -> bool {
+        false
+    }
+    ///
+    /// Extends the sucessfully with additiuonal points
+    /// 
+    /// ## Panics
+    /// - If `try_extend_subscription` returned `Unsupported`, other errors are returned
     fn extend_subscription(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), Error> {
-        panic!("{}.extend_subscription | Does not supported", self.name())
+        match self.try_extend_subscription(receiver_name, points) {
+            Ok(_) => Ok(()),
+            Err(err @ ServiceError::Unsupported { .. }) => panic!("{}.extend_subscription | {}", self.name(), err),
+            Err(err) => Err(err.into()),
+        }
     }
     ///
-    /// Canceling the subsciption
+    /// Extends the sucessfully with additiuonal points
+    /// - `Unsupported` if not implemented, services supporting subscriptions should implement this
     #[allow(unused_variables)]
+    fn try_extend_subscription(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), ServiceError> {
+        Err(ServiceError::unsupported(self.name().join(), "extend_subscription"))
+    }
+    ///
+    /// Canceling the subsciption
+    /// 
+    /// ## Panics
+    /// - If `try_unsubscribe` returned `Unsupported`, other errors are returned
     fn unsubscribe(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), Error> {
-        panic!("{}.unsubscribe | Does not supported", self.name())
+        match self.try_unsubscribe(receiver_name, points) {
+            Ok(_) => Ok(()),
+            Err(err @ ServiceError::Unsupported { .. }) => panic!("{}.unsubscribe | {}", self.name(), err),
+            Err(err) => Err(err.into()),
+        }
+    }
+    ///
+    /// Canceling the subsciption
+    /// - `Unsupported` if not implemented, services supporting subscriptions should implement this
+    #[allow(unused_variables)]
+    fn try_unsubscribe(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), ServiceError> {
+        Err(ServiceError::unsupported(self.name().join(), "unsubscribe"))
+    }
+    ///
+    /// Returns the links to the another services, configured by `send-to`
+    /// 
+    /// Used to build the dependency graph of the services
+    fn send_to(&self) -> Vec<LinkName> {
+        vec![]
+    }
+    ///
+    /// Returns the names of the services [Service] is subscribed on, configured by `subscribe`
+    /// 
+    /// Used to build the dependency graph of the services
+    fn subscribed_to(&self) -> Vec<String> {
+        vec![]
+    }
+    ///
+    /// Returns the names of the incoming queues of the [Service], configured by `in queue`
+    /// 
+    /// Used to export the topology of the services
+    fn in_queues(&self) -> Vec<String> {
+        vec![]
+    }
+    ///
+    /// Returns the subscriptions registered on the [Service] by receivers
+    /// 
+    /// Used to export the topology of the services
+    fn subscriptions(&self) -> Vec<TopologySubscription> {
+        vec![]
     }
     ///
     /// Starts service's main loop in the individual thread
@@ -43,21 +153,36 @@ pub trait Service: Object + std::fmt::Debug + Send + Sync {
     }
     ///
     /// Returns `Future<Point>`, where will be pushed all points by subscription
-    fn gi(&self, _receiver_name: &str, _points: &[SubscriptionCriteria]) -> Future<Vec<Point>> {
-        panic!("{}.gi | Does not supported", self.name())
+    /// 
+    /// ## Panics
+    /// - If `try_gi` returned an error
+    fn gi(&self, receiver_name: &str, points: &[SubscriptionCriteria]) -> Future<Vec<Point>> {
+        match self.try_gi(receiver_name, points) {
+            Ok(future) => future,
+            Err(err) => panic!("{}.gi | {}", self.name(), err),
+        }
+    }
+    ///
+    /// Returns `Future<Point>`, where will be pushed all points by subscription
+    /// - `Unsupported` if not implemented, services supporting General Interogation should implement this
+    fn try_gi(&self, _receiver_name: &str, _points: &[SubscriptionCriteria]) -> Result<Future<Vec<Point>>, ServiceError> {
+        Err(ServiceError::unsupported(self.name().join(), "gi"))
     }
     ///
     /// Waits for the [Service] to finish.
     ///
     /// Returns immediately if the [Service] has already finished.
     /// 
+    /// Returns `ServiceError::Unsupported` if not implemented for associated [Service],
+    /// `Services::exit_all` and `Services::shutdown` report such [Service] as can not be joined,
+    /// see `ServiceError::is_unsupported`
+    /// 
     /// ## Panics
-    /// - If not implemented for associated [Service]
     /// - If specific implementation may panics internally,
     ///   like `std::thread::JoinHandle` - may panic on some platforms 
     ///   if a thread attempts to join itself or otherwise may create a deadlock with joining threads.
     fn wait(&self) -> Result<(), Error> {
-        panic!("{}.wait | Does not supported", self.name())
+        Err(ServiceError::unsupported(self.name().join(), "wait").into())
     }
     ///
     /// Checks if the [Service] has finished running.
@@ -65,6 +190,28 @@ pub trait Service: Object + std::fmt::Debug + Send + Sync {
     /// To finish the [Service] call exit
     fn is_finished(&self) -> bool;
     ///
+    /// Returns current lifecycle state of the [Service]
+    /// 
+    /// Default implementation is based on `is_finished`,
+    /// override it to report `Starting`, `Degraded`, `Stopping` and `Failed` states
+    fn state(&self) -> ServiceState {
+        if self.is_finished() {
+            ServiceState::Stopped
+        } else {
+            ServiceState::Running
+        }
+    }
+    ///
+    /// Applies changed configuration to the running [Service] without restart
+    /// - `conf` - new configuration node of the service
+    /// 
+    /// Returns `Unsupported` if not implemented,
+    /// in this case `Services::reconfigure` will restart the [Service] with the new configuration
+    #[allow(unused_variables)]
+    fn reconfigure(&self, conf: &ConfTree) -> Result<(), ServiceError> {
+        Err(ServiceError::unsupported(self.name().join(), "reconfigure"))
+    }
+    ///
     /// Sends "exit" signal to the service's thread
     fn exit(&self);
 }
\ No newline at end of file
@@ -1,66 +1,170 @@
-use std::{time::{Duration, Instant}, thread};
+use std::{sync::Arc, time::{Duration, Instant}};
 
 use sal_core::dbg::Dbg;
+use crate::kernel::{clock::{Clock, GlobalClock}, metrics::{Counter, Histogram, Metrics}};
+use super::{service_cycle_mode::ServiceCycleMode, service_cycle_stats::ServiceCycleStats};
 ///
-/// ServiceCycle - provides exact time interval in ms / us (future posible implementation)
+/// ServiceCycle - provides exact time interval in ms / us
 ///  - creates with Duration of interval
 ///  - method start() - begins countdown
 ///  - method wait() - awaiting remainder of the specified interval if not elapsed
+///  - time is taken from the [Clock], so cycles can be simulated by the `VirtualClock`
+///  - [ServiceCycleMode] - relative (default) or drift-free scheduling against the absolute deadlines
+///  - timing statistics are collected on the each cycle, returned by `stats()`,
+///    execution time, overruns and skipped cycles are also reported into the `Metrics::global()`
+///  - sub-millisecond intervals - `with_spin`, the last stretch of the wait is spinned instead of sleeping,
+///    for the best precision the thread can be scheduled by the `RealTime`
+/// 
+/// **Example**
+/// ```ignore
+/// let mut cycle = ServiceCycle::new(&dbg, Duration::from_millis(1)).with_mode(ServiceCycleMode::AbsoluteSkip);
+/// loop {
+///     cycle.start();
+///     ...
+///     cycle.wait();
+/// }
+/// log::info!("{}", cycle.stats());
+/// ```
 /// 
 /// [How to sleep for a few microseconds](https://stackoverflow.com/questions/4986818/how-to-sleep-for-a-few-microseconds)
 pub struct ServiceCycle {
     dbg: Dbg,
+    clock: Arc<dyn Clock>,
+    mode: ServiceCycleMode,
     instant: Instant,
+    deadline: Instant,
+    next: Option<Instant>,
     interval: Duration,
+    spin: Duration,
     warn_exceed: Duration,
     err_exceed: Duration,
+    stats: ServiceCycleStats,
+    exec_total: Duration,
+    jitter_total: Duration,
+    jitter_count: u64,
+    exec_metric: Histogram,
+    overruns_metric: Counter,
+    skipped_metric: Counter,
 }
 //
 // 
 impl ServiceCycle {
     ///
-    /// Creates ServiceCycle with Duration of interval
+    /// Creates ServiceCycle with Duration of interval, using the current [GlobalClock]
     pub fn new(parent: impl Into<String>, interval: Duration) -> Self {
+        Self::with_clock(parent, interval, GlobalClock::get())
+    }
+    ///
+    /// Creates ServiceCycle with Duration of interval, using the specified `clock`
+    pub fn with_clock(parent: impl Into<String>, interval: Duration, clock: Arc<dyn Clock>) -> Self {
+        let instant = clock.now();
+        let dbg = Dbg::new(parent.into(), "ServiceCycle");
+        let cycle = dbg.to_string();
+        let labels = [("cycle", cycle.as_str())];
         Self {
-            dbg: Dbg::new(parent.into(), "ServiceCycle"),
-            instant: Instant::now(),
+            exec_metric: Metrics::global().histogram("sal_service_cycle_exec_seconds", "Execution time of the ServiceCycle", &labels, Histogram::DEFAULT_BOUNDS),
+            overruns_metric: Metrics::global().counter("sal_service_cycle_overruns_total", "Cycles exceeded the ServiceCycle interval", &labels),
+            skipped_metric: Metrics::global().counter("sal_service_cycle_skipped_total", "Cycles skipped by the ServiceCycle", &labels),
+            dbg,
+            clock,
+            mode: ServiceCycleMode::default(),
+            instant,
+            deadline: instant + interval,
+            next: None,
             interval,
+            spin: Duration::ZERO,
             warn_exceed: interval / 10,
             err_exceed: interval / 4,
+            stats: ServiceCycleStats::default(),
+            exec_total: Duration::ZERO,
+            jitter_total: Duration::ZERO,
+            jitter_count: 0,
         }
     }
     ///
+    /// Returns ServiceCycle with the specified scheduling `mode`
+    pub fn with_mode(self, mode: ServiceCycleMode) -> Self {
+        Self { mode, ..self }
+    }
+    ///
+    /// Returns ServiceCycle waiting with the hybrid sleep
+    /// - The thread sleeps until `spin` remains to the deadline, then yields and spins
+    /// - Costs CPU time of the `spin` on the each cycle, `spin` about 100 us is enough on the stock Linux
+    pub fn with_spin(self, spin: Duration) -> Self {
+        Self { spin, ..self }
+    }
+    ///
     /// Returns the specified cycle interval
     #[allow(unused)]
     pub fn interval(&self) -> Duration {
         self.interval
     }
     ///
+    /// Returns the scheduling mode
+    pub fn mode(&self) -> ServiceCycleMode {
+        self.mode
+    }
+    ///
     /// Starts new timer
+    /// - In the absolute modes the deadline is counted from the scheduled start, not from now
     pub fn start(&mut self) {
-        self.instant = Instant::now();
+        let now = self.clock.now();
+        if let Some(next) = self.next {
+            let jitter = if now >= next { now - next } else { next - now };
+            self.jitter_total += jitter;
+            self.jitter_count += 1;
+            self.stats.jitter_max = self.stats.jitter_max.max(jitter);
+            self.stats.jitter_avg = self.jitter_total / self.jitter_count.min(u32::MAX as u64) as u32;
+        }
+        self.instant = now;
+        self.deadline = match (self.mode, self.next) {
+            (ServiceCycleMode::Absolute | ServiceCycleMode::AbsoluteSkip, Some(next)) => next + self.interval,
+            _ => now + self.interval,
+        };
     }
     ///
     /// Waits for the remaining time,
     /// If the time elapsed since the start
     /// less then the specified cycle interval
-    pub fn wait(&self) {
-        let elapsed = self.instant.elapsed();
-        if elapsed <= self.interval {
-            let remainder = self.interval - elapsed;
+    /// - Overrun cycle is not waiting, in the [ServiceCycleMode::AbsoluteSkip]
+    ///   it waits for the next deadline, skipping the missed cycles
+    pub fn wait(&mut self) {
+        let now = self.clock.now();
+        let elapsed = now.saturating_duration_since(self.instant);
+        self.update_exec(elapsed);
+        if now <= self.deadline {
+            let remainder = self.deadline - now;
             log::trace!("{}.wait | waiting: {:?}", self.dbg, remainder);
-            thread::sleep(remainder);
+            self.next = Some(self.deadline);
+            self.sleep(remainder);
         } else {
-            let exceed = elapsed - self.interval;
+            let exceed = now - self.deadline;
+            self.stats.overruns += 1;
+            self.overruns_metric.inc();
+            self.stats.overrun_max = self.stats.overrun_max.max(exceed);
             match exceed {
                 e if e >= self.err_exceed => {
-                    log::error!("{}.wait | exceeded {:?} by {:?}, elapsed {:?}", self.dbg, self.interval, elapsed - self.interval, elapsed);
+                    log::error!("{}.wait | exceeded {:?} by {:?}, elapsed {:?}", self.dbg, self.interval, exceed, elapsed);
                 }
                 e if e >= self.warn_exceed => {
-                    log::warn!("{}.wait | exceeded {:?} by {:?}, elapsed {:?}", self.dbg, self.interval, elapsed - self.interval, elapsed);
+                    log::warn!("{}.wait | exceeded {:?} by {:?}, elapsed {:?}", self.dbg, self.interval, exceed, elapsed);
                 }
                 _ => {
-                    log::debug!("{}.wait | exceeded {:?} by {:?}, elapsed {:?}", self.dbg, self.interval, elapsed - self.interval, elapsed);
+                    log::debug!("{}.wait | exceeded {:?} by {:?}, elapsed {:?}", self.dbg, self.interval, exceed, elapsed);
+                }
+            }
+            match self.mode {
+                ServiceCycleMode::Relative => self.next = Some(now),
+                ServiceCycleMode::Absolute => self.next = Some(self.deadline),
+                ServiceCycleMode::AbsoluteSkip => {
+                    let interval = self.interval.as_nanos().max(1);
+                    let missed = exceed.as_nanos().div_ceil(interval);
+                    let next = self.deadline + Duration::from_nanos((interval * missed).min(u64::MAX as u128) as u64);
+                    log::debug!("{}.wait | skipped {} cycles", self.dbg, missed);
+                    self.stats.skipped += missed.min(u64::MAX as u128) as u64;
+                    self.skipped_metric.add(missed.min(u64::MAX as u128) as u64);
+                    self.next = Some(next);
+                    self.sleep(next.saturating_duration_since(now));
                 }
             }
         }
@@ -68,6 +172,39 @@ impl ServiceCycle {
     ///
     /// Returns current elapsed time
     pub fn elapsed(&mut self) -> Duration {
-        self.instant.elapsed()
+        self.clock.elapsed(self.instant)
     }
-}
\ No newline at end of file
+    ///
+    /// Returns timing statistics collected since the creation or the last `reset_stats`
+    pub fn stats(&self) -> ServiceCycleStats {
+        self.stats
+    }
+    ///
+    /// Clears collected timing statistics
+    pub fn reset_stats(&mut self) {
+        self.stats = ServiceCycleStats::default();
+        self.exec_total = Duration::ZERO;
+        self.jitter_total = Duration::ZERO;
+        self.jitter_count = 0;
+    }
+    ///
+    /// Sleeps for the `duration`, hybrid if `spin` is specified
+    fn sleep(&self, duration: Duration) {
+        if self.spin.is_zero() {
+            self.clock.sleep(duration);
+        } else {
+            self.clock.sleep_precise(duration, self.spin);
+        }
+    }
+    ///
+    /// Updates execution time statistics by the just finished cycle
+    fn update_exec(&mut self, elapsed: Duration) {
+        let stats = &mut self.stats;
+        stats.min = if stats.cycles == 0 { elapsed } else { stats.min.min(elapsed) };
+        stats.max = stats.max.max(elapsed);
+        stats.cycles += 1;
+        self.exec_total += elapsed;
+        stats.avg = self.exec_total / stats.cycles.min(u32::MAX as u64) as u32;
+        self.exec_metric.observe_duration(elapsed);
+    }
+}
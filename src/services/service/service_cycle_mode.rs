@@ -0,0 +1,18 @@
+///
+/// How [ServiceCycle](super::ServiceCycle) schedules the next cycle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ServiceCycleMode {
+    ///
+    /// The interval is counted from the each `start`,
+    /// cycles are drifting by the time spent between `wait` and `start`
+    #[default]
+    Relative,
+    ///
+    /// Cycles are scheduled against the absolute deadlines, no drift,
+    /// missed cycles are executed one after another without waiting until the schedule is caught up
+    Absolute,
+    ///
+    /// Cycles are scheduled against the absolute deadlines, no drift,
+    /// missed cycles are skipped, the next cycle is started on the next deadline
+    AbsoluteSkip,
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+use crate::{
+    collections::FxIndexMap,
+    services::{
+        conf::DiagKeywd,
+        entity::{Point, PointConfig, PointHlr},
+    },
+};
+///
+/// Running timing statistics of the [ServiceCycle](super::ServiceCycle)
+/// - `cycles` - number of the finished cycles
+/// - `min`, `avg`, `max` - execution time, from the `start` to the `wait`
+/// - `jitter_avg`, `jitter_max` - deviation of the actual start of the cycle from the scheduled one
+/// - `overruns` - number of the cycles executed longer then the interval
+/// - `overrun_max` - longest overrun
+/// - `skipped` - number of the cycles skipped in the [ServiceCycleMode::AbsoluteSkip](super::ServiceCycleMode::AbsoluteSkip)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ServiceCycleStats {
+    pub cycles: u64,
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+    pub jitter_avg: Duration,
+    pub jitter_max: Duration,
+    pub overruns: u64,
+    pub overrun_max: Duration,
+    pub skipped: u64,
+}
+//
+//
+impl ServiceCycleStats {
+    ///
+    /// Returns the configured `Cycle...` diagnosis points with the current values
+    /// - Durations are reported in microseconds
+    /// - `tx_id` - the producer of the points
+    /// - `diagnosis` - configured diagnosis points of the service
+    pub fn to_points(&self, tx_id: usize, diagnosis: &FxIndexMap<DiagKeywd, PointConfig>) -> Vec<Point> {
+        let us = |duration: Duration| duration.as_micros().min(i64::MAX as u128) as i64;
+        diagnosis.iter().filter_map(|(keywd, conf)| {
+            let value = match keywd {
+                DiagKeywd::CycleMin => us(self.min),
+                DiagKeywd::CycleAvg => us(self.avg),
+                DiagKeywd::CycleMax => us(self.max),
+                DiagKeywd::CycleJitter => us(self.jitter_max),
+                DiagKeywd::CycleOverruns => self.overruns.min(i64::MAX as u64) as i64,
+                DiagKeywd::CycleOverrunMax => us(self.overrun_max),
+                _ => return None,
+            };
+            Some(Point::Int(PointHlr::new_int(tx_id, &conf.name, value)))
+        }).collect()
+    }
+}
+//
+//
+impl std::fmt::Display for ServiceCycleStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cycles: {}, exec min / avg / max: {:?} / {:?} / {:?}, jitter avg / max: {:?} / {:?}, overruns: {} (max {:?}), skipped: {}",
+            self.cycles, self.min, self.avg, self.max, self.jitter_avg, self.jitter_max, self.overruns, self.overrun_max, self.skipped,
+        )
+    }
+}
@@ -0,0 +1,95 @@
+use std::{collections::{BTreeMap, BTreeSet}, sync::Arc};
+use sal_core::{dbg::Dbg, error::Error};
+use super::service::Service;
+///
+/// Dependency graph of the services
+/// - Service depends on the services it sends to (`send-to`)
+/// - Service depends on the services it subscribed on (`subscribe`)
+/// - Dependencies must be started before dependent service and stopped after it
+#[derive(Debug, Clone)]
+pub struct ServiceDeps {
+    dbg: Dbg,
+    deps: BTreeMap<String, BTreeSet<String>>,
+}
+//
+//
+impl ServiceDeps {
+    ///
+    /// Returns [ServiceDeps] new instance built from the links of the given services
+    /// - `services` - pairs of the service name and service reference
+    pub fn new(parent: impl Into<String>, services: &[(String, Arc<dyn Service>)]) -> Self {
+        let dbg = Dbg::new(parent, "ServiceDeps");
+        let mut deps = BTreeMap::new();
+        for (name, service) in services {
+            let service_deps = service.send_to()
+                .into_iter()
+                .map(|link| link.service())
+                .chain(service.subscribed_to())
+                .filter(|dep| {
+                    if dep == name {
+                        log::trace!("{}.new | Service '{}' linked to itself - ignored", dbg, name);
+                        false
+                    } else {
+                        true
+                    }
+                })
+                .collect::<BTreeSet<String>>();
+            log::trace!("{}.new | '{}' depends on: {:?}", dbg, name, service_deps);
+            deps.insert(name.clone(), service_deps);
+        }
+        Self { dbg, deps }
+    }
+    ///
+    /// Returns names of the services the `service` depends on
+    pub fn depends_on(&self, service: &str) -> Vec<String> {
+        self.deps
+            .get(service)
+            .map(|deps| deps.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+    ///
+    /// Returns names of the services in the starting order,
+    /// each service follows all its dependencies
+    ///
+    /// Returns error if
+    /// - some dependency is not registered
+    /// - dependencies having cycle
+    pub fn order(&self) -> Result<Vec<String>, Error> {
+        let error = Error::new(&self.dbg, "order");
+        let unknown: Vec<String> = self.deps.iter().flat_map(|(service, deps)| {
+            deps.iter()
+                .filter(|dep| !self.deps.contains_key(*dep))
+                .map(move |dep| format!("'{}' -> '{}'", service, dep))
+        }).collect();
+        if !unknown.is_empty() {
+            return Err(error.err(format!("Linked services - not found: {}", unknown.join(", "))));
+        }
+        let mut remaining = self.deps.clone();
+        let mut order = vec![];
+        loop {
+            let ready: Vec<String> = remaining
+                .iter()
+                .filter(|(_, deps)| deps.is_empty())
+                .map(|(service, _)| service.clone())
+                .collect();
+            if ready.is_empty() {
+                break;
+            }
+            for service in ready {
+                remaining.remove(&service);
+                for deps in remaining.values_mut() {
+                    deps.remove(&service);
+                }
+                order.push(service);
+            }
+        }
+        if remaining.is_empty() {
+            Ok(order)
+        } else {
+            let cycle: Vec<String> = remaining.iter().map(|(service, deps)| {
+                format!("'{}' -> {:?}", service, deps)
+            }).collect();
+            Err(error.err(format!("Dependency cycle detected between services: \n\t{}", cycle.join("\n\t"))))
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use sal_core::error::Error;
+///
+/// Typed errors of the [Service](crate::services::Service) and [Services](crate::services::Services) API
+/// - `NotFound` - service with the name is not registered
+/// - `Duplicate` - service with the name is already registered
+/// - `LinkNotFound` - service has no incoming queue with the name
+/// - `Unsupported` - method is not implemented by the service
+/// - `Failed` - method is implemented, but returned an error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceError {
+    NotFound {
+        service: String,
+    },
+    Duplicate {
+        service: String,
+    },
+    LinkNotFound {
+        service: String,
+        link: String,
+    },
+    Unsupported {
+        service: String,
+        method: String,
+    },
+    Failed {
+        service: String,
+        error: String,
+    },
+}
+//
+//
+impl ServiceError {
+    ///
+    /// Returns [ServiceError::NotFound]
+    pub fn not_found(service: impl Into<String>) -> Self {
+        Self::NotFound { service: service.into() }
+    }
+    ///
+    /// Returns [ServiceError::Duplicate]
+    pub fn duplicate(service: impl Into<String>) -> Self {
+        Self::Duplicate { service: service.into() }
+    }
+    ///
+    /// Returns [ServiceError::LinkNotFound]
+    pub fn link_not_found(service: impl Into<String>, link: impl Into<String>) -> Self {
+        Self::LinkNotFound { service: service.into(), link: link.into() }
+    }
+    ///
+    /// Returns [ServiceError::Unsupported]
+    pub fn unsupported(service: impl Into<String>, method: impl Into<String>) -> Self {
+        Self::Unsupported { service: service.into(), method: method.into() }
+    }
+    ///
+    /// Returns [ServiceError::Failed]
+    pub fn failed(service: impl Into<String>, error: impl std::fmt::Display) -> Self {
+        Self::Failed { service: service.into(), error: error.to_string() }
+    }
+    ///
+    /// Returns `true` if the `err` is [ServiceError::Unsupported] of the `method` of the `service`,
+    /// converted into the [Error], like returned by the default `Service::wait`
+    pub fn is_unsupported(err: &Error, service: &str, method: &str) -> bool {
+        err.to_string().contains(&Self::unsupported(service, method).to_string())
+    }
+    ///
+    /// Returns the name of the service the error related to
+    pub fn service(&self) -> &str {
+        match self {
+            Self::NotFound { service } => service,
+            Self::Duplicate { service } => service,
+            Self::LinkNotFound { service, .. } => service,
+            Self::Unsupported { service, .. } => service,
+            Self::Failed { service, .. } => service,
+        }
+    }
+}
+//
+//
+impl std::fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound { service } => write!(f, "Service '{}' - not found", service),
+            Self::Duplicate { service } => write!(f, "Service '{}' - duplicated name", service),
+            Self::LinkNotFound { service, link } => write!(f, "Service '{}' link '{}' - not found", service, link),
+            Self::Unsupported { service, method } => write!(f, "Service '{}' method '{}' - does not supported", service, method),
+            Self::Failed { service, error } => write!(f, "Service '{}' error: {}", service, error),
+        }
+    }
+}
+//
+//
+impl std::error::Error for ServiceError {}
+//
+//
+impl From<ServiceError> for Error {
+    fn from(err: ServiceError) -> Self {
+        Error::new(err.service(), "ServiceError").err(err.to_string())
+    }
+}
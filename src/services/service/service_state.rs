@@ -0,0 +1,66 @@
+use std::fmt::Display;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+///
+/// Lifecycle state of the [Service](crate::services::Service)
+/// - `Starting` - `run` was called, service is preparing its threads / connections
+/// - `Running` - service is operating normally
+/// - `Degraded` - service is operating, but some of its functions are not available (connection lost, etc.)
+/// - `Stopping` - `exit` was called, service is finishing its threads
+/// - `Stopped` - service is finished
+/// - `Failed` - service is finished with an error or panic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ServiceState {
+    Starting,
+    Running,
+    Degraded,
+    Stopping,
+    Stopped,
+    Failed,
+}
+//
+//
+impl ServiceState {
+    ///
+    /// Returns `true` if the service is executing, `Starting`, `Running` or `Degraded`
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Starting | Self::Running | Self::Degraded)
+    }
+    ///
+    /// Returns `true` if the service is finished, `Stopped` or `Failed`
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Stopped | Self::Failed)
+    }
+    ///
+    /// Returns string representation of the state
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Starting => "Starting",
+            Self::Running => "Running",
+            Self::Degraded => "Degraded",
+            Self::Stopping => "Stopping",
+            Self::Stopped => "Stopped",
+            Self::Failed => "Failed",
+        }
+    }
+}
+//
+//
+impl Display for ServiceState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+///
+/// Notification about the state transition of the [Service](crate::services::Service)
+/// - `service` - the name of the service
+/// - `prev` - previous state, `None` if service just registered
+/// - `state` - new state
+/// - `timestamp` - when the transition was detected
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceStateChanged {
+    pub service: String,
+    pub prev: Option<ServiceState>,
+    pub state: ServiceState,
+    pub timestamp: DateTime<chrono::Utc>,
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use dashmap::DashMap;
+use sal_core::dbg::Dbg;
+use crate::{kernel::clock::GlobalClock, sync::channel::{self, Receiver, Sender}};
+use super::service_state::{ServiceState, ServiceStateChanged};
+///
+/// Holds last known [ServiceState] of the each service by its name
+/// - Thread safe
+/// - Notifies all observers on every state transition
+pub struct ServiceStates {
+    dbg: Dbg,
+    states: DashMap<String, ServiceState>,
+    observers: DashMap<String, Sender<ServiceStateChanged>>,
+}
+//
+//
+impl ServiceStates {
+    ///
+    /// Returns [ServiceStates] new instance
+    pub fn new(parent: impl Into<String>) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "ServiceStates"),
+            states: DashMap::new(),
+            observers: DashMap::new(),
+        }
+    }
+    ///
+    /// Returns last known state of the service
+    pub fn get(&self, service: &str) -> Option<ServiceState> {
+        self.states.get(service).map(|r| *r.value())
+    }
+    ///
+    /// Returns last known states of all services
+    pub fn all(&self) -> HashMap<String, ServiceState> {
+        HashMap::from_iter(
+            self.states.iter().map(|r| (r.key().clone(), *r.value()))
+        )
+    }
+    ///
+    /// Stores new state of the service
+    /// - Observers are notified if the state is changed
+    /// - Returns `true` if the state is changed
+    pub fn update(&self, service: &str, state: ServiceState) -> bool {
+        let prev = self.states.insert(service.to_owned(), state);
+        if prev == Some(state) {
+            return false;
+        }
+        log::debug!("{}.update | '{}': {:?} -> {}", self.dbg, service, prev, state);
+        self.notify(ServiceStateChanged {
+            service: service.to_owned(),
+            prev,
+            state,
+            timestamp: GlobalClock::utc(),
+        });
+        true
+    }
+    ///
+    /// Removes the service, observers are not notified
+    pub fn remove(&self, service: &str) -> Option<ServiceState> {
+        self.states.remove(service).map(|(_, state)| state)
+    }
+    ///
+    /// Returns Receiver of the all state transitions
+    /// - `receiver_name` - unique name of the observer
+    ///
+    /// Only transitions passed to `update` are observed,
+    /// if states are sampled periodically, transitions between two samples are lost
+    pub fn subscribe(&self, receiver_name: &str) -> Receiver<ServiceStateChanged> {
+        let (send, recv) = channel::unbounded();
+        self.observers.insert(receiver_name.to_owned(), send);
+        recv
+    }
+    ///
+    /// Removes the observer
+    pub fn unsubscribe(&self, receiver_name: &str) {
+        self.observers.remove(receiver_name);
+    }
+    ///
+    /// Sends the event to the all observers,
+    /// observers having closed channel are removed
+    fn notify(&self, event: ServiceStateChanged) {
+        let mut closed = vec![];
+        for r in self.observers.iter() {
+            if let Err(err) = r.value().send(event.clone()) {
+                log::warn!("{}.notify | Observer '{}' - send error: {:?}", self.dbg, r.key(), err);
+                closed.push(r.key().clone());
+            }
+        }
+        for receiver_name in closed {
+            self.observers.remove(&receiver_name);
+        }
+    }
+}
+//
+//
+impl std::fmt::Debug for ServiceStates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceStates")
+            .field("dbg", &self.dbg)
+            .field("states", &self.states)
+            .finish()
+    }
+}
@@ -1,21 +1,30 @@
 use crate::{
-    kernel::state::ChangeNotify,
+    kernel::{clock::{Clock, GlobalClock}, metrics::Metrics, state::ChangeNotify},
     services::{
-        conf::ServicesConf,
+        conf::{ConfTree, ConfTreeGet, ServicesConf},
         entity::{Name, Object, Point, PointConfig},
-        future::{Future, Sink}, retain::{RetainConf, RetainPointId},
-        service::{LinkName, Service, ServiceCycle},
-        subscription::SubscriptionCriteria,
-    }, sync::{channel::{Receiver, Sender}, Handles}, thread_pool::Scheduler,
+        future::Future, points_request::PointsRequest, retain::{RetainConf, RetainPointId},
+        service::{LinkName, Reconfigured, Service, ServiceDeps, ServiceError, ServiceState, ServiceStateChanged, ServiceStates},
+        shutdown::{ShutdownReport, ShutdownSignal},
+        subscription::{SubscriptionCriteria, SubscriptionRecords},
+        supervisor::{RestartPolicy, ServiceBuild, Supervisor},
+        topology::{Topology, TopologyService},
+    }, sync::{channel::{self, AsyncReceiver, AsyncSender, Receiver, RecvTimeoutError, Sender}, Handles}, thread_pool::Scheduler,
 };
 use std::{
-    collections::HashMap, fmt::Debug, sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Duration
+    collections::HashMap, fmt::Debug, panic::AssertUnwindSafe, sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Duration
 };
-use coco::Stack;
 use concat_string::concat_string;
-use dashmap::DashMap;
+use dashmap::{mapref::entry::Entry, DashMap};
+use indexmap::IndexMap;
 use sal_core::{dbg::Dbg, error::Error};
 ///
+/// Replies requested point configurations
+type PointsReply = Box<dyn FnOnce(Vec<PointConfig>) + Send>;
+///
+/// Points request: (requester name, request, reply)
+type PointsRequestMsg = (String, PointsRequest, PointsReply);
+///
 /// Holds a map of the all services in app by there names
 pub struct Services {
     dbg: Dbg,
@@ -23,8 +32,14 @@ pub struct Services {
     map: Arc<DashMap<String, Arc<dyn Service>>>,
     conf: ServicesConf,
     retain_point_id: Option<Arc<RetainPointId>>,
-    points_request: Arc<Stack<(String, Sink<Vec<PointConfig>>)>>,
+    points_request: Sender<PointsRequestMsg>,
+    points_recv: Receiver<PointsRequestMsg>,
+    states: Arc<ServiceStates>,
+    confs: DashMap<String, ConfTree>,
+    subscriptions: Arc<SubscriptionRecords>,
+    supervisor: Supervisor,
     scheduler: Option<Scheduler>,
+    clock: Arc<dyn Clock>,
     handles: Handles<()>,
     exit: Arc<AtomicBool>,
 }
@@ -39,24 +54,47 @@ impl Services {
     pub const TCP_SERVER: &'static str = "TcpServer";
     pub const PRODUCER_SERVICE: &'static str = "ProducerService";
     pub const CACHE_SERVICE: &'static str = "CacheService";
+    pub const HISTORY_SERVICE: &'static str = "HistoryService";
+    pub const METRICS_SERVICE: &'static str = "MetricsService";
     pub const SLMP_CLIENT: &'static str = "SlmpClient";
     ///
+    /// Polling interval of the startup and shutdown, real time
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+    ///
+    /// Interval of the polling of the services states by the main loop
+    const STATES_INTERVAL: Duration = Duration::from_millis(10);
+    ///
     /// Creates new instance of the Services
+    /// - The current [GlobalClock] is captured and used by the main loop, startup and shutdown timeouts
     pub fn new(parent: impl Into<String>, conf: ServicesConf, scheduler: Option<Scheduler>) -> Self {
         let parent = parent.into();
         let name = Name::new(&parent, "Services");
         let name_str = name.join();
         let dbg = Dbg::new(parent, "Services");
+        let map = Arc::new(DashMap::new());
+        let states = Arc::new(ServiceStates::new(&dbg));
+        let subscriptions = Arc::new(SubscriptionRecords::new(&dbg));
+        let (points_request, points_recv) = channel::unbounded();
+        let map_ref = Arc::downgrade(&map);
+        Metrics::global().gauge_fn("sal_services_count", "Number of the services holding by the Services", &[("services", &dbg.to_string())], move || {
+            map_ref.upgrade().map(|map: Arc<DashMap<String, Arc<dyn Service>>>| map.len() as f64)
+        });
         Self {
             name,
-            map: Arc::new(DashMap::new()),
+            supervisor: Supervisor::new(&dbg, map.clone(), states.clone(), subscriptions.clone(), scheduler.clone()),
+            map,
             retain_point_id: match &conf.retain.point {
                 Some(_) => Some(Arc::new(RetainPointId::new(&name_str, conf.retain.clone()))),
                 None => None,
             },
             conf: conf,
-            points_request: Arc::new(Stack::new()),
+            points_request,
+            points_recv,
+            states,
+            confs: DashMap::new(),
+            subscriptions,
             scheduler,
+            clock: GlobalClock::get(),
             handles: Handles::new(&dbg),
             dbg,
             exit: Arc::new(AtomicBool::new(false)),
@@ -92,16 +130,18 @@ impl Services {
     pub fn run(&self) -> Result<(), Error> {
         log::info!("{}.run | Starting...", self.dbg);
         let dbg = self.dbg.clone();
-        let name = self.name.clone();
-        let points_request = self.points_request.clone();
+        let points_request = self.points_recv.clone();
         let retain_point_id = self.retain_point_id.clone();
         let services = self.map.clone();
+        let states = self.states.clone();
+        let clock = self.clock.clone();
         let exit = self.exit.clone();
         match &self.scheduler {
             Some(scheduler) => {
                 log::debug!("{}.run | Starting scheduler::thread...", dbg);
                 let handle = scheduler.spawn(move || {
-                    Self::run_(dbg, name, points_request, retain_point_id, services, exit);
+                    let _clock = GlobalClock::local(clock);
+                    Self::run_(dbg, points_request, retain_point_id, services, states, exit);
                     Ok(())
                 })?;
                 self.handles.push(handle);
@@ -109,7 +149,8 @@ impl Services {
             None => {
                 log::debug!("{}.run | Starting std::thread...", dbg);
                 let handle = std::thread::Builder::new().name(format!("{}.run", dbg)).spawn(move || {
-                    Self::run_(dbg, name, points_request, retain_point_id, services, exit);
+                    let _clock = GlobalClock::local(clock);
+                    Self::run_(dbg, points_request, retain_point_id, services, states, exit);
                 }).map_err(|err| Error::new(&self.dbg, "run").err(err.to_string()))?;
                 self.handles.push(handle);
             }
@@ -120,12 +161,14 @@ impl Services {
     }
     ///
     /// Main loop
+    /// - Points requests are served as soon as received, all pending at once
+    /// - States of the services are polled every [Services::STATES_INTERVAL]
     fn run_(
         dbg: Dbg,
-        name: Name,
-        points_request: Arc<Stack<(String, Sink<Vec<PointConfig>>)>>,
+        points_request: Receiver<PointsRequestMsg>,
         retain_point_id: Option<Arc<RetainPointId>>,
         services: Arc<DashMap<String, Arc<dyn Service + 'static>>>,
+        states: Arc<ServiceStates>,
         exit: Arc<AtomicBool>,
     ) {
         log::info!("{}.run | Preparing thread - ok", dbg);
@@ -138,51 +181,75 @@ impl Services {
                 (NotifyState::Warn,   Box::new(|message| log::warn!("{}", message))),
                 (NotifyState::RetainPointNotConfiguredWarn,   Box::new(|message| log::warn!("{}", message))),
                 (NotifyState::Error,  Box::new(|message| log::error!("{}", message))),
-                (NotifyState::PointsRequestsAccessError,  Box::new(|message| log::error!("{}", message))),
-                (NotifyState::PointsRequestsIsEmpty,  Box::new(|message| log::error!("{}", message))),
             ],
         );
         Self::prepare_point_ids(&dbg, &mut notify, &retain_point_id, &services);
-        let mut cycle = ServiceCycle::new(&name.join(), Duration::from_millis(10));
+        let clock = GlobalClock::get();
+        let requests = Metrics::global().counter("sal_services_points_requests_total", "Points requests served by the Services", &[("services", &dbg.to_string())]);
+        let mut states_updated = clock.now();
+        Self::update_states(&services, &states);
         loop {
-            cycle.start();
-            if !points_request.is_empty() {
-                match points_request.pop() {
-                    Some((requester_name, sink)) => {
-                        log::debug!("{}.run | Points requested from: '{}'", dbg, requester_name);
-                        match &retain_point_id {
-                            Some(retain_point_id) => {
-                                let points = retain_point_id.points()
-                                .into_iter().filter_map(|(owner, points)| {
-                                    if *owner != requester_name {
-                                        Some(points)
-                                    } else {
-                                        None
-                                    }
-                                }).flatten().collect();
-                                sink.add(points);
-                                log::debug!("{}.run | Points requested from: '{}' - Ok", dbg, requester_name);
-                            }
-                            None => {
-                                notify.add(NotifyState::RetainPointNotConfiguredWarn, format!("{}.run | Retain->Point - not configured", dbg));
-                                sink.add(vec![]);
-                            }
-                        }
+            match points_request.recv_timeout(Self::STATES_INTERVAL) {
+                Ok(request) => {
+                    let mut served = 1;
+                    Self::serve_points(&dbg, &mut notify, &retain_point_id, request);
+                    while let Ok(Some(request)) = points_request.try_recv() {
+                        served += 1;
+                        Self::serve_points(&dbg, &mut notify, &retain_point_id, request);
                     }
-                    None => notify.add(NotifyState::PointsRequestsIsEmpty, format!("{}.run | Points requests is empty", dbg)),
+                    requests.add(served);
+                    log::debug!("{}.run | Points requests served: {}", dbg, served);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(err) => {
+                    log::debug!("{}.run | Points requests closed: {:?}", dbg, err);
+                    break;
                 }
             }
             if exit.load(Ordering::SeqCst) {
                 break;
             }
-            cycle.wait();
-            if exit.load(Ordering::SeqCst) {
-                break;
+            if clock.elapsed(states_updated) >= Self::STATES_INTERVAL {
+                states_updated = clock.now();
+                Self::update_states(&services, &states);
             }
         }
         log::info!("{}.run | Exit", dbg);
     }
     ///
+    /// Replies the point configurations requested by the `request`
+    /// - Retained points if `retain.point` is configured
+    /// - Empty list otherwise, points without retained id's can not be addressed by id
+    fn serve_points(
+        dbg: &Dbg,
+        notify: &mut ChangeNotify<NotifyState, String>,
+        retain_point_id: &Option<Arc<RetainPointId>>,
+        request: PointsRequestMsg,
+    ) {
+        let (requester, request, reply) = request;
+        log::debug!("{}.run | Points requested from: '{}': {:?}", dbg, requester, request);
+        let owned: Vec<(String, Vec<PointConfig>)> = match retain_point_id {
+            Some(retain_point_id) => retain_point_id.points().into_iter().collect(),
+            None => {
+                notify.add(NotifyState::RetainPointNotConfiguredWarn, format!("{}.run | Retain->Point - not configured, points are not served", dbg));
+                vec![]
+            }
+        };
+        let points: Vec<PointConfig> = owned.into_iter().flat_map(|(owner, points)| {
+            points.into_iter().filter(|point| request.matches(&requester, &owner, point)).collect::<Vec<_>>()
+        }).collect();
+        log::debug!("{}.run | Points requested from: '{}' - Ok, points: {}", dbg, requester, points.len());
+        reply(points);
+    }
+    ///
+    /// Polls the state of the each service, observers will be notified on changes
+    fn update_states(services: &Arc<DashMap<String, Arc<dyn Service>>>, states: &ServiceStates) {
+        let services: Vec<(String, Arc<dyn Service>)> = services.iter().map(|r| (r.key().clone(), r.value().clone())).collect();
+        for (name, service) in services {
+            states.update(&name, service.state());
+        }
+    }
+    ///
     /// Returns all holding services in the map<service id, service reference>
     pub fn all(&self) -> HashMap<String, Arc<dyn Service>> {
         HashMap::from_iter(
@@ -190,13 +257,437 @@ impl Services {
         )
     }
     ///
+    /// Returns the dependency graph of the all holding services,
+    /// built from `send-to` and `subscribe` links
+    pub fn deps(&self) -> ServiceDeps {
+        let mut services: Vec<(String, Arc<dyn Service>)> = self.map.iter().map(|r| (r.key().clone(), r.value().clone())).collect();
+        services.sort_by(|(a, _), (b, _)| a.cmp(b));
+        ServiceDeps::new(&self.dbg, &services)
+    }
+    ///
+    /// Returns names of the all holding services in the starting order,
+    /// each service follows all the services it is linked to
+    pub fn order(&self) -> Result<Vec<String>, Error> {
+        self.deps().order()
+    }
+    ///
+    /// Starts all holding services in the dependency order
+    /// - Each next service started only after previous reported `Running` or `Degraded` state
+    /// - `timeout` - maximum time to wait for the each service being started
+    /// 
+    /// Returns error if dependencies having cycle, some linked service is not registered
+    /// or some service failed to start
+    pub fn run_all(&self, timeout: Duration) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "run_all");
+        let order = self.order().map_err(|err| error.pass(err))?;
+        log::info!("{}.run_all | Starting services in order: {:#?}", self.dbg, order);
+        for name in order {
+            let service = self.get(&name).ok_or_else(|| error.err(format!("Service '{}' - not found", name)))?;
+            log::debug!("{}.run_all | Starting '{}'...", self.dbg, name);
+            self.states.update(&name, ServiceState::Starting);
+            if let Err(err) = service.run() {
+                self.states.update(&name, ServiceState::Failed);
+                return Err(error.pass_with(format!("Service '{}' start failed", name), err.to_string()));
+            }
+            self.wait_ready(&name, &service, timeout).map_err(|err| error.pass(err))?;
+            log::debug!("{}.run_all | Starting '{}' - ok", self.dbg, name);
+        }
+        log::info!("{}.run_all | All services started", self.dbg);
+        Ok(())
+    }
+    ///
+    /// Waits until service reports `Running` or `Degraded` state
+    fn wait_ready(&self, name: &str, service: &Arc<dyn Service>, timeout: Duration) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "wait_ready");
+        let time = self.clock.now();
+        loop {
+            let state = service.state();
+            self.states.update(name, state);
+            match state {
+                ServiceState::Running | ServiceState::Degraded => return Ok(()),
+                ServiceState::Starting => {}
+                _ => return Err(error.err(format!("Service '{}' - not started, state: {}", name, state))),
+            }
+            if self.clock.elapsed(time) >= timeout {
+                return Err(error.err(format!("Service '{}' - not started in {:?}, state: {}", name, timeout, state)));
+            }
+            std::thread::sleep(Self::POLL_INTERVAL);
+        }
+    }
+    ///
+    /// Stops all holding services in the reverse dependency order
+    /// - Each next service stopped only after previous is finished
+    /// - Service not supporting `wait` can not be joined, it is only signaled to exit,
+    ///   it's state is taken from `Service::state`
+    /// 
+    /// Returns error if dependencies having cycle or some service finished with error
+    pub fn exit_all(&self) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "exit_all");
+        let order = self.order().map_err(|err| error.pass(err))?;
+        log::info!("{}.exit_all | Stopping services...", self.dbg);
+        let mut errors = vec![];
+        for name in order.into_iter().rev() {
+            if let Some(service) = self.get(&name) {
+                log::debug!("{}.exit_all | Stopping '{}'...", self.dbg, name);
+                match self.stop(&name, service) {
+                    Ok(_) => log::debug!("{}.exit_all | Stopping '{}' - ok", self.dbg, name),
+                    Err(err) => {
+                        log::warn!("{}.exit_all | Stopping '{}' error: {:?}", self.dbg, name, err);
+                        errors.push(format!("'{}': {}", name, err));
+                    }
+                }
+            }
+        }
+        log::info!("{}.exit_all | Stopping services - ok", self.dbg);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(error.err(errors.join("\n")))
+        }
+    }
+    ///
+    /// Stops the service and waits until it is finished, supervised service will not be restarted
+    fn stop(&self, name: &str, service: Arc<dyn Service>) -> Result<(), Error> {
+        self.states.update(name, ServiceState::Stopping);
+        let service = self.current(name, service);
+        let result = if self.supervisor.is_supervised(name) {
+            self.supervisor.exit(name);
+            service.exit();
+            self.supervisor.wait(name)
+        } else {
+            service.exit();
+            service.wait()
+        };
+        match result {
+            Ok(_) => {
+                self.states.update(name, ServiceState::Stopped);
+                Ok(())
+            }
+            Err(err) if ServiceError::is_unsupported(&err, name, "wait") => {
+                let state = service.state();
+                log::warn!("{}.stop | Service '{}' - can not be joined, `wait` is not supported, state: {}", self.dbg, name, state);
+                self.states.update(name, state);
+                Ok(())
+            }
+            Err(err) => {
+                self.states.update(name, ServiceState::Failed);
+                Err(err)
+            }
+        }
+    }
+    ///
+    /// Applies changed configuration to the running service
+    /// - Does nothing if `conf` is equal to the stored one, see `set_conf`
+    /// - Calls `Service::reconfigure` if supported by the service
+    /// - Otherwise builds new instance of the service by `build`, stops the running one,
+    ///   restores subscriptions made through the `Services` on the new instance and runs it
+    /// - `send-to` links to the restarted service held by another services are replaced by `Service::relink`,
+    ///   restart is refused if some of them does not supports `relink`
+    /// 
+    /// Supervision of the restarted service is finished and has to be applied again by `supervise`.
+    /// 
+    /// Old instance keeps running if `reconfigure` or `build` failed or restart is refused
+    pub fn reconfigure(
+        &self,
+        name: &str,
+        conf: ConfTree,
+        build: impl FnOnce(ConfTree) -> Result<Arc<dyn Service>, Error>,
+    ) -> Result<Reconfigured, Error> {
+        let error = Error::new(&self.dbg, "reconfigure");
+        let service = self.try_get(name).map_err(|err| error.pass(err.to_string()))?;
+        let changed = match self.confs.get(name) {
+            Some(old) => Self::changed_keys(old.value(), &conf),
+            None => vec![conf.key.clone()],
+        };
+        if changed.is_empty() {
+            log::debug!("{}.reconfigure | '{}' - not changed", self.dbg, name);
+            return Ok(Reconfigured::Unchanged);
+        }
+        log::info!("{}.reconfigure | '{}' changed: {:?}", self.dbg, name, changed);
+        match service.reconfigure(&conf) {
+            Ok(_) => {
+                self.confs.insert(name.to_owned(), conf);
+                log::info!("{}.reconfigure | '{}' - reconfigured", self.dbg, name);
+                Ok(Reconfigured::Applied)
+            }
+            Err(ServiceError::Unsupported { .. }) => {
+                log::debug!("{}.reconfigure | '{}' does not supports reconfiguration, restarting...", self.dbg, name);
+                let inbound = self.inbound(name);
+                // Links are checked on the old instance, so restart is refused before anything is stopped
+                for (upstream, link) in &inbound {
+                    self.try_get_link(link).map_err(|err| error.pass(err.to_string()))?;
+                    if !upstream.supports_relink() {
+                        return Err(error.pass_with(
+                            format!("Service '{}' can't be restarted, link '{}' of '{}' can't be replaced", name, link, upstream.name()),
+                            ServiceError::unsupported(upstream.name().join(), "relink").to_string(),
+                        ));
+                    }
+                }
+                let restarted = build(conf.clone()).map_err(|err| error.pass_with("Build failed", err.to_string()))?;
+                let restarted_name = restarted.name().join();
+                if restarted_name != name {
+                    return Err(error.err(format!("Built service '{}' does not match reconfigured '{}'", restarted_name, name)));
+                }
+                if let Err(err) = self.stop(name, service) {
+                    log::warn!("{}.reconfigure | Stopping '{}' error: {:?}", self.dbg, name, err);
+                }
+                self.map.insert(name.to_owned(), restarted.clone());
+                self.confs.insert(name.to_owned(), conf);
+                if let Err(err) = self.subscriptions.restore(name, &restarted) {
+                    log::warn!("{}.reconfigure | {:?}", self.dbg, err);
+                }
+                for (upstream, link) in &inbound {
+                    let relinked = self.try_get_link(link).and_then(|send| upstream.relink(link, send));
+                    if let Err(err) = relinked {
+                        log::warn!("{}.reconfigure | Link '{}' of '{}' - not replaced: {}", self.dbg, link, upstream.name(), err);
+                    }
+                }
+                self.states.update(name, ServiceState::Starting);
+                restarted.run().map_err(|err| {
+                    self.states.update(name, ServiceState::Failed);
+                    error.pass_with(format!("Service '{}' start failed", name), err.to_string())
+                })?;
+                log::info!("{}.reconfigure | '{}' - restarted", self.dbg, name);
+                Ok(Reconfigured::Restarted)
+            }
+            Err(err) => Err(error.pass(err.to_string())),
+        }
+    }
+    ///
+    /// Returns the services having `send-to` links to the service `name`, with the links
+    fn inbound(&self, name: &str) -> Vec<(Arc<dyn Service>, LinkName)> {
+        let services: Vec<(String, Arc<dyn Service>)> = self.map.iter().map(|r| (r.key().clone(), r.value().clone())).collect();
+        services.into_iter()
+            .filter(|(upstream, _)| upstream != name)
+            .flat_map(|(_, service)| {
+                service.send_to().into_iter()
+                    .filter(|link| link.service() == name)
+                    .map(|link| (service.clone(), link))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+    ///
+    /// Returns the top level keys of the service config, added, removed or changed in the `new`
+    fn changed_keys(old: &ConfTree, new: &ConfTree) -> Vec<String> {
+        if old.key != new.key {
+            return vec![new.key.clone()];
+        }
+        let old_keys = old.keys(&[] as &[&str]);
+        let new_keys = new.keys(&[] as &[&str]);
+        let mut changed: Vec<String> = old_keys.iter()
+            .filter(|key| !new_keys.contains(key))
+            .cloned()
+            .collect();
+        for key in new_keys {
+            let old_value: Option<serde_yaml::Value> = ConfTreeGet::get(old, &key);
+            let new_value: Option<serde_yaml::Value> = ConfTreeGet::get(new, &key);
+            if old_value != new_value {
+                changed.push(key);
+            }
+        }
+        if changed.is_empty() && old.conf != new.conf {
+            changed.push(new.key.clone());
+        }
+        changed
+    }
+    ///
+    /// Returns the live graph of the all holding services, sorted by the service name
+    pub fn topology(&self) -> Topology {
+        let mut services: Vec<(String, Arc<dyn Service>)> = self.map.iter().map(|r| (r.key().clone(), r.value().clone())).collect();
+        services.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Topology {
+            services: services.into_iter().map(|(name, service)| TopologyService {
+                state: self.states.get(&name),
+                in_queues: service.in_queues(),
+                send_to: service.send_to().iter().map(|link| link.name()).collect(),
+                subscribed_to: service.subscribed_to(),
+                subscriptions: service.subscriptions(),
+                points: service.points().into_iter().map(|point| point.name).collect(),
+                name,
+            }).collect(),
+        }
+    }
+    ///
+    /// Stores the configuration the service is built from, used by `reconfigure` to detect changes
+    pub fn set_conf(&self, name: &str, conf: ConfTree) {
+        self.confs.insert(name.to_owned(), conf);
+    }
+    ///
+    /// Returns the configuration the service is built from
+    pub fn conf(&self, name: &str) -> Option<ConfTree> {
+        self.confs.get(name).map(|r| r.value().clone())
+    }
+    ///
+    /// Stops the `Services` loop and all holding services, waits for them until `deadline`
+    /// - All services are signaled to exit at once, in the reverse dependency order if available
+    /// - Supervised services will not be restarted any more, they are waited through the [Supervisor]
+    /// - Services are waited concurrently, each in the separate thread,
+    ///   threads of the services not finished until `deadline` are left detached
+    ///
+    /// - Services not supporting `Service::wait` can not be joined, they are reported as `unjoined`
+    ///
+    /// Returns the report of stopped, failed, unjoined and timed out services
+    pub fn shutdown(&self, deadline: Duration) -> ShutdownReport {
+        let time = self.clock.now();
+        let order = match self.order() {
+            Ok(order) => order.into_iter().rev().collect(),
+            Err(err) => {
+                log::warn!("{}.shutdown | Dependency order - not available: {:?}", self.dbg, err);
+                let mut order: Vec<String> = self.map.iter().map(|r| r.key().clone()).collect();
+                order.sort();
+                order
+            }
+        };
+        log::info!("{}.shutdown | Stopping services, deadline: {:?}...", self.dbg, deadline);
+        let mut report = ShutdownReport::default();
+        // The Services loop checks exit flag every cycle, stopped first to keep the states of the stopping services
+        self.exit();
+        if let Err(err) = self.handles.wait() {
+            report.failed.push((self.name.join(), err.to_string()));
+        }
+        let (send, recv) = channel::unbounded();
+        let mut pending = vec![];
+        for name in order {
+            let Some(service) = self.get(&name) else {
+                continue;
+            };
+            self.states.update(&name, ServiceState::Stopping);
+            // Supervised service is waited by its watcher only, like in `stop`
+            self.supervisor.exit(&name);
+            let wait: Box<dyn FnOnce() -> Result<(), Error> + Send> = match self.supervisor.take(&name) {
+                Some(watcher) => {
+                    self.current(&name, service).exit();
+                    Box::new(move || watcher.wait())
+                }
+                None => {
+                    service.exit();
+                    Box::new(move || service.wait())
+                }
+            };
+            let send = send.clone();
+            let thread_name = name.clone();
+            let spawned = std::thread::Builder::new().name(format!("{}.shutdown", self.dbg)).spawn(move || {
+                let result = wait();
+                let result = match result {
+                    Ok(_) => Ok(None),
+                    Err(err) if ServiceError::is_unsupported(&err, &thread_name, "wait") => Ok(Some(err.to_string())),
+                    Err(err) => Err(err.to_string()),
+                };
+                if let Err(err) = send.send((thread_name, result)) {
+                    log::trace!("shutdown | Send result error: {:?}", err);
+                }
+            });
+            match spawned {
+                Ok(_) => pending.push(name),
+                Err(err) => log::warn!("{}.shutdown | Waiting thread for '{}' - not started: {:?}", self.dbg, name, err),
+            }
+        }
+        drop(send);
+        while !pending.is_empty() {
+            let remaining = deadline.saturating_sub(self.clock.elapsed(time));
+            if remaining.is_zero() {
+                break;
+            }
+            match recv.recv_timeout(remaining.min(Self::POLL_INTERVAL)) {
+                Ok((name, result)) => {
+                    pending.retain(|pending| *pending != name);
+                    match result {
+                        Ok(None) => {
+                            self.states.update(&name, ServiceState::Stopped);
+                            report.stopped.push(name);
+                        }
+                        Ok(Some(err)) => {
+                            log::warn!("{}.shutdown | Service '{}' - can not be joined: {}", self.dbg, name, err);
+                            if let Some(service) = self.get(&name) {
+                                self.states.update(&name, service.state());
+                            }
+                            report.unjoined.push(name);
+                        }
+                        Err(err) => {
+                            self.states.update(&name, ServiceState::Failed);
+                            report.failed.push((name, err));
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(_) => break,
+            }
+        }
+        report.timed_out = pending;
+        report.elapsed = self.clock.elapsed(time);
+        if report.is_ok() {
+            log::info!("{}.shutdown | Stopping services - ok: {}", self.dbg, report);
+        } else {
+            log::warn!("{}.shutdown | Stopping services - not complete: {}", self.dbg, report);
+        }
+        report
+    }
+    ///
+    /// Blocks until SIGTERM or SIGINT received, then calls [Services::shutdown] with the `deadline`
+    ///
+    /// Intended to be called at the end of the `main`
+    pub fn shutdown_on_signal(&self, deadline: Duration) -> Result<ShutdownReport, Error> {
+        let mut signal = ShutdownSignal::new(&self.dbg)?;
+        signal.wait();
+        Ok(self.shutdown(deadline))
+    }
+    ///
+    /// Returns the service currently registered by the `name`,
+    /// supervised service might be replaced after restart
+    fn current(&self, name: &str, service: Arc<dyn Service>) -> Arc<dyn Service> {
+        self.map.get(name).map(|r| r.value().clone()).unwrap_or(service)
+    }
+    ///
+    /// Starts supervising of the running service
+    /// - `name` - the name of the service
+    /// - `policy` - when and how to restart the service, usually parsed from service config by `RestartPolicy::new`
+    /// - `build` - creates new instance of the service on restart
+    /// 
+    /// Subscriptions made on the service through the `Services` will be restored after restart
+    pub fn supervise(
+        &self,
+        name: &str,
+        policy: RestartPolicy,
+        build: impl Fn() -> Result<Arc<dyn Service>, Error> + Send + Sync + 'static,
+    ) -> Result<(), Error> {
+        let build: ServiceBuild = Arc::new(build);
+        self.supervisor.supervise(name, policy, build)
+    }
+    ///
+    /// Returns number of restarts of the supervised service
+    pub fn restarts(&self, name: &str) -> Option<usize> {
+        self.supervisor.restarts(name)
+    }
+    ///
+    /// Returns number of restarts of the all supervised services
+    pub fn all_restarts(&self) -> HashMap<String, usize> {
+        self.supervisor.all_restarts()
+    }
+    ///
     /// Inserts a new service into the collection
+    /// 
+    /// ## Panics
+    /// - If service with the same name already inserted, use `try_insert` to get an error instead
     pub fn insert(&self, service: Arc<dyn Service>) {
+        if let Err(err) = self.try_insert(service) {
+            panic!("{}.insert | {}", self.dbg, err);
+        }
+    }
+    ///
+    /// Inserts a new service into the collection
+    /// - Returns `ServiceError::Duplicate` if service with the same name already inserted
+    pub fn try_insert(&self, service: Arc<dyn Service>) -> Result<(), ServiceError> {
         let name = service.name().join();
-        if self.map.contains_key(&name) {
-            panic!("{}.insert | Duplicated service name '{:?}'", self.dbg, name);
+        let state = service.state();
+        match self.map.entry(name) {
+            Entry::Occupied(entry) => Err(ServiceError::duplicate(entry.key())),
+            Entry::Vacant(entry) => {
+                self.states.update(entry.key(), state);
+                entry.insert(service);
+                Ok(())
+            }
         }
-        self.map.insert(name, service);
     }
     ///
     /// Returns Service
@@ -210,63 +701,251 @@ impl Services {
         }
     }
     ///
+    /// Returns Service
+    /// - Returns `ServiceError::NotFound` if service is not inserted
+    pub fn try_get(&self, name: &str) -> Result<Arc<dyn Service>, ServiceError> {
+        match self.map.get(name) {
+            Some(r) => Ok(r.value().clone()),
+            None => Err(ServiceError::not_found(name)),
+        }
+    }
+    ///
     /// Returns copy of the Sender - service's incoming queue by service link name (Service.link)
     pub fn get_link(&self, name: &LinkName) -> Result<Sender<Point>, Error> {
+        let error = Error::new(&self.dbg, "get_link");
+        self.try_get_link(name).map_err(|err| error.pass(err.to_string()))
+    }
+    ///
+    /// Returns copy of the Sender - service's incoming queue by service link name (Service.link)
+    /// - `ServiceError::NotFound` if service is not inserted
+    /// - `ServiceError::LinkNotFound` if service has no incoming queue with the name
+    /// - `ServiceError::Unsupported` if service has no incoming queues
+    /// 
+    /// Falls back to `Service::get_link` for the services implementing only it
+    pub fn try_get_link(&self, name: &LinkName) -> Result<Sender<Point>, ServiceError> {
         let (service, queue) = name.split();
-        match self.get(&service) {
-            Some(srvc) => Ok(srvc.get_link(&queue)),
-            None => Err(Error::new(&self.dbg, "get_link").err(format!("service '{:?}' - not found", name))),
-        }
+        let srvc = self.try_get(&service)?;
+        Self::fallback(srvc.try_get_link(&queue), || Ok(srvc.get_link(&queue)))
+    }
+    ///
+    /// Returns the link as async Sender, usable from the async runtime
+    /// - Shares the channel with the sync link, no bridging thread
+    pub fn get_link_async(&self, name: &LinkName) -> Result<AsyncSender<Point>, Error> {
+        self.get_link(name).map(channel::to_async_sender)
     }
     ///
     /// Returns Receiver
     /// - service - the name of the service to subscribe on
+    /// 
+    /// ## Panics
+    /// - If service is not found, use `try_subscribe` to get an error instead
     pub fn subscribe(&self, service: &str, receiver_name: &str, points: &[SubscriptionCriteria]) -> (Sender<Point>, Receiver<Point>) {
         match self.get(service) {
             Some(srvc) => {
                 let r = srvc.subscribe(receiver_name, points);
+                self.subscriptions.insert(service, receiver_name, points, r.0.clone());
                 r
             }
             None => panic!("{}.subscribe | service '{:?}' - not found", self.dbg, service),
         }
     }
     ///
+    /// Returns Receiver
+    /// - service - the name of the service to subscribe on
+    /// - `ServiceError::NotFound` if service is not inserted
+    /// - `ServiceError::Unsupported` if service does not supports subscriptions
+    /// 
+    /// Falls back to `Service::subscribe` for the services implementing only it
+    pub fn try_subscribe(&self, service: &str, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(Sender<Point>, Receiver<Point>), ServiceError> {
+        let srvc = self.try_get(service)?;
+        let r = Self::fallback(srvc.try_subscribe(receiver_name, points), || Ok(srvc.subscribe(receiver_name, points)))?;
+        self.subscriptions.insert(service, receiver_name, points, r.0.clone());
+        Ok(r)
+    }
+    ///
+    /// Returns Sender and async Receiver of the subscription, usable from the async runtime
+    /// - service - the name of the service to subscribe on
+    /// 
+    /// ## Panics
+    /// - If service is not found, use `try_subscribe_async` to get an error instead
+    pub fn subscribe_async(&self, service: &str, receiver_name: &str, points: &[SubscriptionCriteria]) -> (Sender<Point>, AsyncReceiver<Point>) {
+        let (send, recv) = self.subscribe(service, receiver_name, points);
+        (send, channel::to_async_receiver(recv))
+    }
+    ///
+    /// Returns Sender and async Receiver of the subscription, usable from the async runtime
+    /// - service - the name of the service to subscribe on
+    /// - The Receiver shares the channel with the sync subscription, no bridging thread
+    pub fn try_subscribe_async(&self, service: &str, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(Sender<Point>, AsyncReceiver<Point>), ServiceError> {
+        self.try_subscribe(service, receiver_name, points).map(|(send, recv)| (send, channel::to_async_receiver(recv)))
+    }
+    ///
     /// Returns ok if subscription extended sucessfully
     /// - service - the name of the service to extend subscribtion on
+    /// 
+    /// ## Panics
+    /// - If service is not found, use `try_extend_subscription` to get an error instead
     pub fn extend_subscription(&self, service: &str, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), Error> {
         // panic!("{}.extend_subscription | Not implemented yet", self.id);
         match self.get(service) {
             Some(srvc) => {
                 let r = srvc.extend_subscription(receiver_name, points);
+                if r.is_ok() {
+                    self.subscriptions.extend(service, receiver_name, points);
+                }
                 r
             }
             None => panic!("{}.extend_suscription | service '{:?}' - not found", self.dbg, service),
         }
     }
     ///
+    /// Returns ok if subscription extended sucessfully
+    /// - service - the name of the service to extend subscribtion on
+    /// - `ServiceError::NotFound` if service is not inserted
+    /// - `ServiceError::Unsupported` if service does not supports subscriptions
+    /// - `ServiceError::Failed` if service returned an error
+    /// 
+    /// Falls back to `Service::extend_subscription` for the services implementing only it
+    pub fn try_extend_subscription(&self, service: &str, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), ServiceError> {
+        let srvc = self.try_get(service)?;
+        Self::fallback(srvc.try_extend_subscription(receiver_name, points), || {
+            srvc.extend_subscription(receiver_name, points).map_err(|err| ServiceError::failed(service, err))
+        })?;
+        self.subscriptions.extend(service, receiver_name, points);
+        Ok(())
+    }
+    ///
     /// Returns ok if subscription removed sucessfully
     /// - service - the name of the service to unsubscribe on
+    /// 
+    /// ## Panics
+    /// - If service is not found, use `try_unsubscribe` to get an error instead
     pub fn unsubscribe(&self, service: &str, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), Error> {
         match self.get(service) {
             Some(srvc) => {
                 let r = srvc.unsubscribe(receiver_name, points);
+                if r.is_ok() {
+                    self.subscriptions.remove(service, receiver_name, points);
+                }
                 r
             }
             None => panic!("{}.unsubscribe | service '{:?}' - not found", self.dbg, service),
         }
     }
     ///
-    /// Returns list of point configurations over the all services
+    /// Returns ok if subscription removed sucessfully
+    /// - service - the name of the service to unsubscribe on
+    /// - `ServiceError::NotFound` if service is not inserted
+    /// - `ServiceError::Unsupported` if service does not supports subscriptions
+    /// - `ServiceError::Failed` if service returned an error
+    /// 
+    /// Falls back to `Service::unsubscribe` for the services implementing only it
+    pub fn try_unsubscribe(&self, service: &str, receiver_name: &str, points: &[SubscriptionCriteria]) -> Result<(), ServiceError> {
+        let srvc = self.try_get(service)?;
+        Self::fallback(srvc.try_unsubscribe(receiver_name, points), || {
+            srvc.unsubscribe(receiver_name, points).map_err(|err| ServiceError::failed(service, err))
+        })?;
+        self.subscriptions.remove(service, receiver_name, points);
+        Ok(())
+    }
+    ///
+    /// Returns list of point configurations over the all services, except the points of the requester
     ///  - requester_name - Service name !!!
+    ///  - Empty list if `retain.point` is not configured
     pub fn points(&self, requester_name: impl Into<String>) -> Future<Vec<PointConfig>> {
+        self.request_points(requester_name, PointsRequest::All)
+    }
+    ///
+    /// Returns point configurations of the specified kind, with the retained id's
+    /// - `requester_name` - the name of the requesting service
+    /// - Empty list if `retain.point` is not configured
+    /// - Served by the main loop as soon as received, `run` must be called
+    pub fn request_points(&self, requester_name: impl Into<String>, request: PointsRequest) -> Future<Vec<PointConfig>> {
+        let (future, sink) = Future::new();
+        self.send_points_request(requester_name.into(), request, Box::new(move |points| sink.add(points)));
+        future
+    }
+    ///
+    /// Returns retained id's of the points of the specified kind, in the map<point name, id>
+    /// - `requester_name` - the name of the requesting service
+    /// - Empty map if `retain.point` is not configured
+    /// - Served by the main loop as soon as received, `run` must be called
+    pub fn point_ids(&self, requester_name: impl Into<String>, request: PointsRequest) -> Future<IndexMap<String, usize>> {
         let (future, sink) = Future::new();
-        self.points_request.push((requester_name.into(), sink));
+        self.send_points_request(requester_name.into(), request, Box::new(move |points| {
+            sink.add(points.into_iter().map(|point| (point.name, point.id)).collect())
+        }));
         future
     }
     ///
-    /// Sends the General Interogation request to all services
-    pub fn gi(&self, _service: &str, _points: &[SubscriptionCriteria]) -> Future<Vec<Point>> {
-        panic!("{}.gi | Not implemented yet", self.dbg);
+    /// Sends the points request to the main loop
+    fn send_points_request(&self, requester_name: String, request: PointsRequest, reply: PointsReply) {
+        if let Err(err) = self.points_request.send((requester_name, request, reply)) {
+            log::error!("{}.send_points_request | Send error: {:?}", self.dbg, err);
+        }
+    }
+    ///
+    /// Sends the General Interogation request to the `service`
+    /// 
+    /// ## Panics
+    /// - If service is not found or does not supports General Interogation, use `try_gi` to get an error instead
+    pub fn gi(&self, service: &str, points: &[SubscriptionCriteria]) -> Future<Vec<Point>> {
+        match self.try_gi(service, points) {
+            Ok(future) => future,
+            Err(err) => panic!("{}.gi | {}", self.dbg, err),
+        }
+    }
+    ///
+    /// Sends the General Interogation request to the `service`
+    /// - `ServiceError::NotFound` if service is not inserted
+    /// - `ServiceError::Unsupported` if service does not supports General Interogation
+    /// 
+    /// Falls back to `Service::gi` for the services implementing only it
+    pub fn try_gi(&self, service: &str, points: &[SubscriptionCriteria]) -> Result<Future<Vec<Point>>, ServiceError> {
+        let srvc = self.try_get(service)?;
+        let receiver_name = self.name.join();
+        Self::fallback(srvc.try_gi(&receiver_name, points), || Ok(srvc.gi(&receiver_name, points)))
+    }
+    ///
+    /// Calls the `method` - panicking version of the [Service] method, if `try_*` one returned `Unsupported`
+    /// - Services implementing the panicking methods only are still supported
+    /// - Panic of the `method` means it is not implemented as well, the `Unsupported` is returned then
+    fn fallback<T>(result: Result<T, ServiceError>, method: impl FnOnce() -> Result<T, ServiceError>) -> Result<T, ServiceError> {
+        match result {
+            Err(err @ ServiceError::Unsupported { .. }) => match std::panic::catch_unwind(AssertUnwindSafe(method)) {
+                Ok(result) => result,
+                Err(_) => Err(err),
+            },
+            result => result,
+        }
+    }
+    ///
+    /// Returns last known lifecycle state of the service
+    pub fn state(&self, service: &str) -> Option<ServiceState> {
+        self.states.get(service)
+    }
+    ///
+    /// Returns last known lifecycle states of the all services
+    pub fn states(&self) -> HashMap<String, ServiceState> {
+        self.states.all()
+    }
+    ///
+    /// Returns Receiver of the lifecycle state transitions of the all services
+    /// - `receiver_name` - unique name of the observer
+    /// 
+    /// States are polled by the `Services` main loop, so `run` has to be called
+    ///
+    /// **Limitation:** states are sampled every 10 ms,
+    /// transitions made by the service between two polls are not observed,
+    /// for example `Running -> Failed -> Running` faster than the interval is not reported at all.
+    /// Transitions made by the `Services` itself (start, stop, restart, reconfigure) are always reported
+    pub fn subscribe_states(&self, receiver_name: &str) -> Receiver<ServiceStateChanged> {
+        self.states.subscribe(receiver_name)
+    }
+    ///
+    /// Cancels the subscription on the lifecycle state transitions
+    pub fn unsubscribe_states(&self, receiver_name: &str) {
+        self.states.unsubscribe(receiver_name)
     }
     ///
     /// Returns Retain configuration
@@ -274,6 +953,12 @@ impl Services {
         self.conf.retain.clone()
     }
     ///
+    /// Returns the [Clock] of the application, captured on creation
+    /// - Services use it for the cycles and timestamps, so the whole application can run on the `VirtualClock`
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+    ///
     /// Returns [Ok] when all [Service]'s are finished
     pub fn wait(&self) -> Result<(), Error> {
         self.handles.wait()
@@ -319,6 +1004,4 @@ enum NotifyState {
     Warn,
     RetainPointNotConfiguredWarn,
     Error,
-    PointsRequestsIsEmpty,
-    PointsRequestsAccessError,
 }
@@ -0,0 +1,11 @@
+//!
+//! # Graceful shutdown of the services
+//! 
+//! - Stopping all services with the deadline
+//! - Handling SIGTERM / SIGINT
+//! 
+mod shutdown_report;
+mod shutdown_signal;
+
+pub use shutdown_report::*;
+pub use shutdown_signal::*;
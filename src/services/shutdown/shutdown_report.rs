@@ -0,0 +1,48 @@
+use std::time::Duration;
+///
+/// Result of the [Services::shutdown](crate::services::Services::shutdown)
+/// - `stopped` - services finished in time without error
+/// - `failed` - services finished in time with error, pairs of the service name and error
+/// - `unjoined` - services signaled to exit, but not supporting `Service::wait`,
+///   they can not be joined and may still be running
+/// - `timed_out` - services not finished until deadline,
+///   their threads are left detached, process exit will terminate them
+/// - `elapsed` - total time of the shutdown
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShutdownReport {
+    pub stopped: Vec<String>,
+    pub failed: Vec<(String, String)>,
+    pub unjoined: Vec<String>,
+    pub timed_out: Vec<String>,
+    pub elapsed: Duration,
+}
+//
+//
+impl ShutdownReport {
+    ///
+    /// Returns `true` if all services are stopped in time without errors,
+    /// `unjoined` services are not taken into account
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty() && self.timed_out.is_empty()
+    }
+}
+//
+//
+impl std::fmt::Display for ShutdownReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f, "stopped: {}, failed: {}, unjoined: {}, timed out: {}, elapsed: {:?}",
+            self.stopped.len(), self.failed.len(), self.unjoined.len(), self.timed_out.len(), self.elapsed,
+        )?;
+        for (service, err) in &self.failed {
+            write!(f, "\n\tfailed '{}': {}", service, err)?;
+        }
+        for service in &self.unjoined {
+            write!(f, "\n\tunjoined '{}'", service)?;
+        }
+        for service in &self.timed_out {
+            write!(f, "\n\ttimed out '{}'", service)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use sal_core::{dbg::Dbg, error::Error};
+use signal_hook::{consts::{SIGINT, SIGTERM}, iterator::{Handle, Signals}};
+///
+/// Listens for the termination signals of the process (SIGTERM, SIGINT)
+/// 
+/// **Example**
+/// ```ignore
+/// let services = Arc::new(Services::new(dbg, conf, None));
+/// services.run()?;
+/// services.run_all(Duration::from_secs(3))?;
+/// let mut signal = ShutdownSignal::new(dbg)?;
+/// if let Some(signal) = signal.wait() {
+///     log::info!("main | Received signal {}", signal);
+/// }
+/// let report = services.shutdown(Duration::from_secs(10));
+/// ```
+pub struct ShutdownSignal {
+    dbg: Dbg,
+    signals: Signals,
+}
+//
+//
+impl ShutdownSignal {
+    ///
+    /// Returns [ShutdownSignal] new instance, registers SIGTERM and SIGINT handlers
+    pub fn new(parent: impl Into<String>) -> Result<Self, Error> {
+        let dbg = Dbg::new(parent, "ShutdownSignal");
+        let signals = Signals::new([SIGTERM, SIGINT])
+            .map_err(|err| Error::new(&dbg, "new").pass_with("Signal handlers registration failed", err.to_string()))?;
+        Ok(Self { dbg, signals })
+    }
+    ///
+    /// Blocks until SIGTERM or SIGINT received, returns the received signal
+    /// - Returns `None` if listening is closed by the [Handle]
+    pub fn wait(&mut self) -> Option<i32> {
+        let signal = self.signals.forever().next();
+        match signal {
+            Some(signal) => log::info!("{}.wait | Received signal: {}", self.dbg, signal),
+            None => log::debug!("{}.wait | Closed", self.dbg),
+        }
+        signal
+    }
+    ///
+    /// Returns the handle, allows to close listening from another thread
+    pub fn handle(&self) -> Handle {
+        self.signals.handle()
+    }
+}
+//
+//
+impl std::fmt::Debug for ShutdownSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShutdownSignal")
+            .field("dbg", &self.dbg)
+            .finish()
+    }
+}
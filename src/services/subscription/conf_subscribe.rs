@@ -19,7 +19,7 @@ use crate::services::{
 /// ------------------------------------------------------------------------------------------
 /// subscribe: 
 ///     MultiQueue:                     # - multicast subscription to the MultiQueue
-///         {cot: Inf, history: r}: []  #   - on all points having Cot::Inf and history::read
+///         {cot: Inf, history: r}: []  #   - on all points having Cot::Inf and history::read or history::readwrite
 /// ------------------------------------------------------------------------------------------
 /// subscribe: 
 ///     MultiQueue:                     # - multicast subscription to the MultiQueue
@@ -198,7 +198,7 @@ impl Criterias {
     ///
     /// Returns true if point_config is accepted by the options:
     ///     - alarm
-    ///     - history
+    ///     - history, `r` / `w` also accepts points having `history: rw`
     fn accept(self_id: &str, point_conf: &PointConfig, history: &Option<PointConfigHistory>, alarm: &Option<u64>) -> bool {
         trace!("{}.accept | history: {:?}\t point.history: {:?}", self_id, history, point_conf.history);
         let mut accepted = true;
@@ -207,10 +207,10 @@ impl Criterias {
             match history {
                 PointConfigHistory::None => {}
                 PointConfigHistory::Read => {
-                    accepted &= point_conf.history == PointConfigHistory::Read
+                    accepted &= matches!(point_conf.history, PointConfigHistory::Read | PointConfigHistory::ReadWrite)
                 }
                 PointConfigHistory::Write => {
-                    accepted &= point_conf.history == PointConfigHistory::Write
+                    accepted &= matches!(point_conf.history, PointConfigHistory::Write | PointConfigHistory::ReadWrite)
                 }
                 PointConfigHistory::ReadWrite => {
                     trace!("{}.accept | point_conf.history != PointConfigHistory::None: {}", self_id, point_conf.history != PointConfigHistory::None);
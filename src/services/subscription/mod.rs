@@ -4,7 +4,11 @@
 mod subscription_criteria;
 mod conf_subscribe;
 mod subscriptions;
+mod subscription_records;
+mod service_subscription;
 
 pub use subscription_criteria::*;
 pub use conf_subscribe::*;
 pub use subscriptions::*;
+pub use subscription_records::*;
+pub use service_subscription::*;
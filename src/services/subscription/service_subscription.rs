@@ -0,0 +1,123 @@
+use std::{sync::Arc, time::{Duration, Instant}};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    services::{
+        entity::{Name, Point, PointConfig},
+        services::Services,
+    },
+    sync::channel::Receiver,
+};
+use super::{conf_subscribe::ConfSubscribe, subscription_criteria::SubscriptionCriteria};
+///
+/// Subscriptions of the service configured by `subscribe`, used by TCP, cache and history services
+/// - Single or multiple services, points from all of them are received by `recv`
+pub struct ServiceSubscription {
+    dbg: Dbg,
+    receiver_name: String,
+    subscriptions: Vec<(String, Vec<SubscriptionCriteria>)>,
+    receivers: Vec<Receiver<Point>>,
+    services: Arc<Services>,
+}
+//
+//
+impl ServiceSubscription {
+    const POLL_INTERVAL: Duration = Duration::from_millis(1);
+    ///
+    /// Returns the names of the services to be subscribed on
+    /// - `name` - the name of the subscribing service
+    pub fn services(name: &Name, conf: &ConfSubscribe) -> Vec<String> {
+        if conf.is_empty() {
+            return vec![];
+        }
+        let mut services: Vec<String> = conf.with(&[]).into_keys().map(|service| name.resolve(&service)).collect();
+        services.sort();
+        services
+    }
+    ///
+    /// Subscribes the service `name` on the all services configured by `conf`
+    pub fn new(name: &Name, conf: &ConfSubscribe, services: Arc<Services>) -> Result<Self, Error> {
+        let subscriptions = match conf.is_empty() {
+            true => vec![],
+            false => conf.with(&[]).into_iter().map(|(service, criterias)| (service, criterias.unwrap_or_default())).collect(),
+        };
+        Self::subscribe(name, subscriptions, services)
+    }
+    ///
+    /// Subscribes the service `name` on the services configured by `conf`,
+    /// `history` and `alarm` criterias are resolved over the `points`
+    /// - Services having no matching points are not subscribed
+    pub fn with_points(name: &Name, conf: &ConfSubscribe, points: &[PointConfig], services: Arc<Services>) -> Result<Self, Error> {
+        let dbg = Dbg::new(name.join(), "ServiceSubscription");
+        let subscriptions = match conf.is_empty() {
+            true => vec![],
+            false => conf.with(points).into_iter().filter_map(|(service, criterias)| match criterias {
+                Some(criterias) => Some((service, criterias)),
+                None => {
+                    log::warn!("{}.with_points | No matching points on '{}', not subscribed", dbg, service);
+                    None
+                }
+            }).collect(),
+        };
+        Self::subscribe(name, subscriptions, services)
+    }
+    ///
+    /// Subscribes the service `name` on the `subscriptions` - pairs of the service and the points
+    fn subscribe(name: &Name, subscriptions: Vec<(String, Vec<SubscriptionCriteria>)>, services: Arc<Services>) -> Result<Self, Error> {
+        let dbg = Dbg::new(name.join(), "ServiceSubscription");
+        let error = Error::new(&dbg, "subscribe");
+        let receiver_name = name.join();
+        let mut subscribed = vec![];
+        let mut receivers = vec![];
+        for (service, points) in subscriptions {
+            let service = name.resolve(&service);
+            let (_, recv) = services.try_subscribe(&service, &receiver_name, &points)
+                .map_err(|err| error.pass_with(format!("Subscription on '{}' failed", service), err.to_string()))?;
+            log::debug!("{}.subscribe | Subscribed on '{}', points: {}", dbg, service, points.len());
+            subscribed.push((service, points));
+            receivers.push(recv);
+        }
+        Ok(Self { dbg, receiver_name, subscriptions: subscribed, receivers, services })
+    }
+    ///
+    /// Returns the point received from the any subscription,
+    /// or `None` if nothing is received during `timeout`
+    pub fn recv(&self, timeout: Duration) -> Option<Point> {
+        match self.receivers.as_slice() {
+            [] => {
+                std::thread::sleep(timeout);
+                None
+            }
+            [recv] => recv.recv_timeout(timeout).ok(),
+            receivers => {
+                let time = Instant::now();
+                loop {
+                    for recv in receivers {
+                        if let Ok(Some(point)) = recv.try_recv() {
+                            return Some(point);
+                        }
+                    }
+                    if time.elapsed() >= timeout {
+                        return None;
+                    }
+                    std::thread::sleep(Self::POLL_INTERVAL);
+                }
+            }
+        }
+    }
+    ///
+    /// Drops all points already received
+    pub fn clear(&self) {
+        for recv in &self.receivers {
+            while let Ok(Some(_)) = recv.try_recv() {}
+        }
+    }
+    ///
+    /// Cancels all subscriptions
+    pub fn unsubscribe(&self) {
+        for (service, points) in &self.subscriptions {
+            if let Err(err) = self.services.try_unsubscribe(service, &self.receiver_name, points) {
+                log::warn!("{}.unsubscribe | Unsubscribe from '{}' error: {}", self.dbg, service, err);
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+use dashmap::DashMap;
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    services::{entity::Point, service::Service, subscription::SubscriptionCriteria},
+    sync::channel::Sender,
+};
+///
+/// Subscription made through the `Services`
+/// - `points` - subscribed criterias, empty for broadcast
+/// - `send` - Sender of the receiver's channel
+#[derive(Debug, Clone)]
+pub struct SubscriptionRecord {
+    pub receiver: String,
+    pub points: Vec<SubscriptionCriteria>,
+    pub send: Sender<Point>,
+}
+///
+/// Keeps all subscriptions made through the `Services` by the service and receiver names
+/// - Used to re-create subscriptions on the restarted service,
+///   so receivers keep their existing channels
+pub struct SubscriptionRecords {
+    dbg: Dbg,
+    records: DashMap<(String, String), SubscriptionRecord>,
+}
+//
+//
+impl SubscriptionRecords {
+    ///
+    /// Returns [SubscriptionRecords] new instance
+    pub fn new(parent: impl Into<String>) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "SubscriptionRecords"),
+            records: DashMap::new(),
+        }
+    }
+    ///
+    /// Stores new subscription of the `receiver` on the `service`, replaces existing one
+    pub fn insert(&self, service: &str, receiver: &str, points: &[SubscriptionCriteria], send: Sender<Point>) {
+        self.records.insert(
+            (service.to_owned(), receiver.to_owned()),
+            SubscriptionRecord { receiver: receiver.to_owned(), points: points.to_vec(), send },
+        );
+    }
+    ///
+    /// Appends `points` to the existing subscription
+    pub fn extend(&self, service: &str, receiver: &str, points: &[SubscriptionCriteria]) {
+        if let Some(mut record) = self.records.get_mut(&(service.to_owned(), receiver.to_owned())) {
+            record.points.extend_from_slice(points);
+        }
+    }
+    ///
+    /// Removes `points` from the existing subscription,
+    /// whole subscription removed if `points` is empty or no more points left
+    pub fn remove(&self, service: &str, receiver: &str, points: &[SubscriptionCriteria]) {
+        let key = (service.to_owned(), receiver.to_owned());
+        if points.is_empty() {
+            self.records.remove(&key);
+        } else {
+            let is_empty = match self.records.get_mut(&key) {
+                Some(mut record) => {
+                    record.points.retain(|criteria| !points.contains(criteria));
+                    record.points.is_empty()
+                }
+                None => false,
+            };
+            if is_empty {
+                self.records.remove(&key);
+            }
+        }
+    }
+    ///
+    /// Returns all subscriptions on the `service`
+    pub fn get(&self, service: &str) -> Vec<SubscriptionRecord> {
+        self.records
+            .iter()
+            .filter(|r| r.key().0 == service)
+            .map(|r| r.value().clone())
+            .collect()
+    }
+    ///
+    /// Re-creates all stored subscriptions on the `service` instance
+    pub fn restore(&self, name: &str, service: &Arc<dyn Service>) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "restore");
+        let mut errors = vec![];
+        for record in self.get(name) {
+            match service.resubscribe(&record.receiver, &record.points, record.send.clone()) {
+                Ok(_) => log::debug!("{}.restore | '{}' subscription of '{}' - restored", self.dbg, name, record.receiver),
+                Err(err) => errors.push(format!("'{}': {}", record.receiver, err)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(error.err(format!("Subscriptions on '{}' - not restored: \n\t{}", name, errors.join("\n\t"))))
+        }
+    }
+}
+//
+//
+impl std::fmt::Debug for SubscriptionRecords {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubscriptionRecords")
+            .field("dbg", &self.dbg)
+            .finish()
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt::Debug, hash::BuildHasherDefault};
+use std::{collections::HashMap, fmt::Debug, hash::BuildHasherDefault};
 use hashers::fx_hash::FxHasher;
 use sal_core::error::Error;
 use crate::{collections::FxDashMap, services::entity::Point, sync::channel::Sender};
@@ -134,6 +134,25 @@ impl Subscriptions {
         }
     }
     ///
+    /// Returns receivers of the broadcast subscriptions
+    pub fn broadcast_receivers(&self) -> Vec<ReceiverId> {
+        self.broadcast.iter().map(|r| *r.key()).collect()
+    }
+    ///
+    /// Returns destinations of the multicast subscriptions by receivers
+    pub fn multicast_receivers(&self) -> HashMap<ReceiverId, Vec<PointDest>> {
+        let mut receivers: HashMap<ReceiverId, Vec<PointDest>> = HashMap::new();
+        for r in self.multicast.iter() {
+            for receiver in r.value().iter() {
+                receivers.entry(*receiver.key()).or_default().push(r.key().clone());
+            }
+        }
+        for destinations in receivers.values_mut() {
+            destinations.sort();
+        }
+        receivers
+    }
+    ///
     /// Removes all subscriptions
     pub fn exit(&self) {
         self.broadcast.clear();
@@ -0,0 +1,11 @@
+//!
+//! # Supervision of the services
+//! 
+//! - Watches the services running in the separate threads
+//! - Restarts finished / failed services according to the restart policy
+//! 
+mod restart_policy;
+mod supervisor;
+
+pub use restart_policy::*;
+pub use supervisor::*;
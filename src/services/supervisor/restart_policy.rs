@@ -0,0 +1,125 @@
+use std::time::Duration;
+use sal_core::{dbg::Dbg, error::Error};
+use crate::services::conf::{ConfTree, ConfTreeGet};
+///
+/// Restart policy of the supervised [Service](crate::services::Service)
+/// - `Never` - service will not be restarted
+/// - `Always` - service will be restarted every time it is finished, except requested exit
+/// - `OnFailure` - service will be restarted only if finished with error / panic, at most `max_retries` times
+///
+/// Delay before each next restart is doubled, starting from `backoff` up to `max_backoff`,
+/// retries and delay are reset if the service was running at least `reset-after` before finished
+///
+/// **Example**
+/// ```yaml
+/// service TcpClient Ied01:
+///     restart:
+///         policy: on-failure      # never (default) / always / on-failure
+///         max-retries: 5          # on-failure only, default 3
+///         backoff: 100 ms         # delay before the first restart, default 1 s
+///         max-backoff: 30 s       # maximum delay, default 30 s
+///         reset-after: 60 s       # stable running time to reset retries, default 60 s
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestartPolicy {
+    Never,
+    Always {
+        backoff: Duration,
+        max_backoff: Duration,
+        reset_after: Duration,
+    },
+    OnFailure {
+        max_retries: usize,
+        backoff: Duration,
+        max_backoff: Duration,
+        reset_after: Duration,
+    },
+}
+//
+//
+impl RestartPolicy {
+    const DEFAULT_MAX_RETRIES: usize = 3;
+    const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+    const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+    const DEFAULT_RESET_AFTER: Duration = Duration::from_secs(60);
+    ///
+    /// Returns [RestartPolicy] parsed from the `restart` node of the service config
+    /// - Returns `RestartPolicy::Never` if `restart` is not specified
+    /// - Returns error if `policy` is unknown or some of the parameters are invalid
+    pub fn new(parent: impl Into<String>, conf: &ConfTree) -> Result<Self, Error> {
+        let dbg = Dbg::new(parent, "RestartPolicy");
+        let error = Error::new(&dbg, "new");
+        let conf: ConfTree = match ConfTreeGet::<ConfTree>::get(conf, "restart") {
+            Some(conf) => conf,
+            None => return Ok(Self::Never),
+        };
+        log::trace!("{}.new | conf: {:?}", dbg, conf);
+        let backoff = Self::duration(&conf, "backoff", Self::DEFAULT_BACKOFF).map_err(|err| error.pass(err))?;
+        let max_backoff = Self::duration(&conf, "max-backoff", Self::DEFAULT_MAX_BACKOFF).map_err(|err| error.pass(err))?;
+        let reset_after = Self::duration(&conf, "reset-after", Self::DEFAULT_RESET_AFTER).map_err(|err| error.pass(err))?;
+        let policy: Option<String> = ConfTreeGet::<String>::get(&conf, "policy");
+        match policy.as_deref() {
+            None | Some("never") => Ok(Self::Never),
+            Some("always") => Ok(Self::Always { backoff, max_backoff, reset_after }),
+            Some("on-failure") => {
+                let max_retries = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "max-retries") {
+                    Some(value) => match value.as_u64().and_then(|value| usize::try_from(value).ok()) {
+                        Some(max_retries) => max_retries,
+                        None => return Err(error.err(format!("Invalid 'max-retries': {:?}, expected non-negative integer", value))),
+                    },
+                    None => Self::DEFAULT_MAX_RETRIES,
+                };
+                Ok(Self::OnFailure { max_retries, backoff, max_backoff, reset_after })
+            }
+            Some(policy) => Err(error.err(format!("Unknown restart policy '{}', expected: never / always / on-failure", policy))),
+        }
+    }
+    ///
+    /// Returns the duration by `key`, or `default` if `key` is not specified
+    fn duration(conf: &ConfTree, key: &str, default: Duration) -> Result<Duration, Error> {
+        match ConfTreeGet::<serde_yaml::Value>::get(conf, key) {
+            Some(_) => conf.get_duration(key),
+            None => Ok(default),
+        }
+    }
+    ///
+    /// Returns the running time of the service, after which its retries are reset,
+    /// `None` if service must not be restarted
+    pub fn reset_after(&self) -> Option<Duration> {
+        match self {
+            Self::Never => None,
+            Self::Always { reset_after, .. } => Some(*reset_after),
+            Self::OnFailure { reset_after, .. } => Some(*reset_after),
+        }
+    }
+    ///
+    /// Returns the delay before the next restart, or `None` if service must not be restarted
+    /// - `retries` - number of restarts already done
+    /// - `failed` - service finished with error / panic
+    pub fn delay(&self, retries: usize, failed: bool) -> Option<Duration> {
+        match self {
+            Self::Never => None,
+            Self::Always { backoff, max_backoff, .. } => Some(Self::backoff(*backoff, *max_backoff, retries)),
+            Self::OnFailure { max_retries, backoff, max_backoff, .. } => {
+                if failed && retries < *max_retries {
+                    Some(Self::backoff(*backoff, *max_backoff, retries))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+    ///
+    /// Returns `backoff` doubled `retries` times, limited by `max_backoff`
+    fn backoff(backoff: Duration, max_backoff: Duration, retries: usize) -> Duration {
+        let factor = 2u32.saturating_pow(retries.min(31) as u32);
+        backoff.checked_mul(factor).unwrap_or(max_backoff).min(max_backoff)
+    }
+}
+//
+//
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::Never
+    }
+}
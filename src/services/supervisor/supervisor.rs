@@ -0,0 +1,300 @@
+use std::{
+    collections::HashMap,
+    sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc},
+    time::Duration,
+};
+use dashmap::DashMap;
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    kernel::clock::{Clock, GlobalClock},
+    services::{
+        service::{Service, ServiceError, ServiceState, ServiceStates},
+        subscription::SubscriptionRecords,
+    },
+    sync::Handles,
+    thread_pool::Scheduler,
+};
+use super::restart_policy::RestartPolicy;
+///
+/// Creates new instance of the supervised service to replace the finished one
+pub type ServiceBuild = Arc<dyn Fn() -> Result<Arc<dyn Service>, Error> + Send + Sync>;
+///
+/// Supervised service
+/// - `exit` - exit requested, service must not be restarted
+/// - `restarts` - number of restarts done
+/// - `handles` - watcher thread
+struct Supervised {
+    exit: Arc<AtomicBool>,
+    restarts: Arc<AtomicUsize>,
+    handles: Handles<()>,
+}
+///
+/// Watches supervised services and restarts them according to their [RestartPolicy]
+/// - Service is watched in the separate thread, blocked on `Service::wait`
+/// - Restarted service replaces the finished one in the `Services`
+/// - Subscriptions made on the finished service through the `Services` are restored on the restarted one
+///
+/// **Note:** `send-to` links (`Service::get_link`) of the finished service
+/// held by another services are not restored, such services have to be supervised as well
+pub struct Supervisor {
+    dbg: Dbg,
+    services: Arc<DashMap<String, Arc<dyn Service>>>,
+    states: Arc<ServiceStates>,
+    subscriptions: Arc<SubscriptionRecords>,
+    supervised: DashMap<String, Supervised>,
+    scheduler: Option<Scheduler>,
+    clock: Arc<dyn Clock>,
+}
+//
+//
+impl Supervisor {
+    ///
+    /// Returns [Supervisor] new instance
+    /// - Restart delays are measured by the current [GlobalClock]
+    pub fn new(
+        parent: impl Into<String>,
+        services: Arc<DashMap<String, Arc<dyn Service>>>,
+        states: Arc<ServiceStates>,
+        subscriptions: Arc<SubscriptionRecords>,
+        scheduler: Option<Scheduler>,
+    ) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "Supervisor"),
+            services,
+            states,
+            subscriptions,
+            supervised: DashMap::new(),
+            scheduler,
+            clock: GlobalClock::get(),
+        }
+    }
+    ///
+    /// Starts watching the service, it has to be already running
+    /// - `name` - the name of the service
+    /// - `policy` - when and how to restart the service
+    /// - `build` - creates new instance of the service on restart
+    pub fn supervise(&self, name: &str, policy: RestartPolicy, build: ServiceBuild) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "supervise");
+        if self.supervised.contains_key(name) {
+            return Err(error.err(format!("Service '{}' - already supervised", name)));
+        }
+        let service = match self.services.get(name) {
+            Some(r) => r.value().clone(),
+            None => return Err(error.err(format!("Service '{}' - not found", name))),
+        };
+        log::info!("{}.supervise | '{}' supervised with policy: {:?}", self.dbg, name, policy);
+        let supervised = Supervised {
+            exit: Arc::new(AtomicBool::new(false)),
+            restarts: Arc::new(AtomicUsize::new(0)),
+            handles: Handles::new(&self.dbg),
+        };
+        let watcher = Watcher {
+            dbg: Dbg::new(&self.dbg, format!("Watcher({})", name)),
+            name: name.to_owned(),
+            policy,
+            build,
+            services: self.services.clone(),
+            states: self.states.clone(),
+            subscriptions: self.subscriptions.clone(),
+            restarts: supervised.restarts.clone(),
+            exit: supervised.exit.clone(),
+            clock: self.clock.clone(),
+        };
+        match &self.scheduler {
+            Some(scheduler) => {
+                let handle = scheduler.spawn_named(name, move || {
+                    watcher.run(service);
+                    Ok(())
+                }).map_err(|err| error.pass_with("Start failed on Scheduler", err.to_string()))?;
+                supervised.handles.push(handle);
+            }
+            None => {
+                let handle = std::thread::Builder::new().name(format!("{}.supervise", watcher.dbg)).spawn(move || {
+                    watcher.run(service);
+                }).map_err(|err| error.pass_with("Start failed on std::thread", err.to_string()))?;
+                supervised.handles.push(handle);
+            }
+        }
+        self.supervised.insert(name.to_owned(), supervised);
+        Ok(())
+    }
+    ///
+    /// Returns `true` if the service is supervised
+    pub fn is_supervised(&self, name: &str) -> bool {
+        self.supervised.contains_key(name)
+    }
+    ///
+    /// Returns number of restarts of the supervised service
+    pub fn restarts(&self, name: &str) -> Option<usize> {
+        self.supervised.get(name).map(|r| r.value().restarts.load(Ordering::SeqCst))
+    }
+    ///
+    /// Returns number of restarts of the all supervised services
+    pub fn all_restarts(&self) -> HashMap<String, usize> {
+        HashMap::from_iter(
+            self.supervised.iter().map(|r| (r.key().clone(), r.value().restarts.load(Ordering::SeqCst)))
+        )
+    }
+    ///
+    /// Marks the service as exiting, it will not be restarted any more
+    pub fn exit(&self, name: &str) {
+        if let Some(r) = self.supervised.get(name) {
+            r.value().exit.store(true, Ordering::SeqCst);
+        }
+    }
+    ///
+    /// Waits for the watcher of the service being finished,
+    /// watcher finishes after the service is finished and not restarted
+    pub fn wait(&self, name: &str) -> Result<(), Error> {
+        match self.take(name) {
+            Some(handles) => handles.wait(),
+            None => Ok(()),
+        }
+    }
+    ///
+    /// Finishes supervising of the service, returns the handles of its watcher to be waited later,
+    /// watcher finishes after the service is finished and not restarted
+    /// - The watcher is the only waiter of the supervised service, `Service::wait` must not be called by another thread
+    pub fn take(&self, name: &str) -> Option<Handles<()>> {
+        self.supervised.remove(name).map(|(_, supervised)| supervised.handles)
+    }
+}
+//
+//
+impl std::fmt::Debug for Supervisor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Supervisor")
+            .field("dbg", &self.dbg)
+            .field("restarts", &self.all_restarts())
+            .finish()
+    }
+}
+///
+/// Watches single supervised service in the separate thread
+struct Watcher {
+    dbg: Dbg,
+    name: String,
+    policy: RestartPolicy,
+    build: ServiceBuild,
+    services: Arc<DashMap<String, Arc<dyn Service>>>,
+    states: Arc<ServiceStates>,
+    subscriptions: Arc<SubscriptionRecords>,
+    restarts: Arc<AtomicUsize>,
+    exit: Arc<AtomicBool>,
+    clock: Arc<dyn Clock>,
+}
+//
+//
+impl Watcher {
+    ///
+    /// Interval of polling `Service::is_finished` of the service not supporting `Service::wait`
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+    ///
+    /// Main loop, returns when the service is finished and must not be restarted
+    fn run(self, service: Arc<dyn Service>) {
+        log::debug!("{}.run | Watching...", self.dbg);
+        let mut service = service;
+        let mut retries = 0;
+        let mut started = self.clock.now();
+        loop {
+            let result = self.wait(&service);
+            if self.exit.load(Ordering::SeqCst) {
+                log::debug!("{}.run | Exit requested", self.dbg);
+                break;
+            }
+            // Service was running stable long enough, next failure is handled like the first one
+            if let Some(reset_after) = self.policy.reset_after() {
+                let running = self.clock.elapsed(started);
+                if retries > 0 && running >= reset_after {
+                    log::debug!("{}.run | Retries reset, was running {:?}", self.dbg, running);
+                    retries = 0;
+                }
+            }
+            let mut failed = result.is_err() || service.state() == ServiceState::Failed;
+            match &result {
+                Ok(_) => log::warn!("{}.run | Service finished", self.dbg),
+                Err(err) => log::error!("{}.run | Service failed: {:?}", self.dbg, err),
+            }
+            self.states.update(&self.name, if failed { ServiceState::Failed } else { ServiceState::Stopped });
+            let restarted = loop {
+                let delay = match self.policy.delay(retries, failed) {
+                    Some(delay) => delay,
+                    None => break None,
+                };
+                log::info!("{}.run | Restarting in {:?} (retry {})...", self.dbg, delay, retries + 1);
+                if !self.sleep(delay) {
+                    break None;
+                }
+                retries += 1;
+                self.restarts.fetch_add(1, Ordering::SeqCst);
+                match self.restart() {
+                    Ok(service) => break Some(service),
+                    Err(err) => {
+                        log::error!("{}.run | Restart failed: {:?}", self.dbg, err);
+                        self.states.update(&self.name, ServiceState::Failed);
+                        failed = true;
+                    }
+                }
+            };
+            match restarted {
+                Some(restarted) => {
+                    log::info!("{}.run | Restarted", self.dbg);
+                    service = restarted;
+                    started = self.clock.now();
+                    if self.exit.load(Ordering::SeqCst) {
+                        service.exit();
+                    }
+                }
+                None => {
+                    log::warn!("{}.run | Service will not be restarted", self.dbg);
+                    break;
+                }
+            }
+        }
+        log::debug!("{}.run | Exit", self.dbg);
+    }
+    ///
+    /// Creates, registers and runs new instance of the service
+    fn restart(&self) -> Result<Arc<dyn Service>, Error> {
+        let error = Error::new(&self.dbg, "restart");
+        let service = (self.build)().map_err(|err| error.pass_with("Build failed", err.to_string()))?;
+        let name = service.name().join();
+        if name != self.name {
+            return Err(error.err(format!("Built service '{}' does not match supervised '{}'", name, self.name)));
+        }
+        self.services.insert(name.clone(), service.clone());
+        if let Err(err) = self.subscriptions.restore(&name, &service) {
+            log::warn!("{}.restart | {:?}", self.dbg, err);
+        }
+        self.states.update(&name, ServiceState::Starting);
+        service.run().map_err(|err| error.pass_with("Start failed", err.to_string()))?;
+        Ok(service)
+    }
+    ///
+    /// Waits until the service is finished,
+    /// service not supporting `Service::wait` is polled by `Service::is_finished`
+    fn wait(&self, service: &Arc<dyn Service>) -> Result<(), Error> {
+        match service.wait() {
+            Err(err) if ServiceError::is_unsupported(&err, &self.name, "wait") => {
+                log::debug!("{}.wait | `wait` is not supported, polling `is_finished`...", self.dbg);
+                while !service.is_finished() {
+                    self.clock.sleep(Self::POLL_INTERVAL);
+                }
+                Ok(())
+            }
+            result => result,
+        }
+    }
+    ///
+    /// Sleeps for the `delay`, returns `false` if exit requested
+    fn sleep(&self, delay: Duration) -> bool {
+        let time = self.clock.now();
+        while self.clock.elapsed(time) < delay {
+            if self.exit.load(Ordering::SeqCst) {
+                return false;
+            }
+            self.clock.sleep(delay.saturating_sub(self.clock.elapsed(time)).min(Duration::from_millis(10)));
+        }
+        !self.exit.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+use sal_core::dbg::Dbg;
+use crate::{
+    collections::FxIndexMap,
+    services::{
+        conf::DiagKeywd,
+        entity::{Point, PointConfig, PointHlr, Status},
+    },
+    sync::channel::Sender,
+};
+///
+/// Reports the connection state by the `Connection` diagnosis point
+/// - Current state is stored into the shared `connected` flag
+/// - Ok(0) - connected
+/// - Invalid(10) - not connected
+/// - Point is sent only if the state is changed
+/// - Does nothing if `Connection` diagnosis point is not configured or `send-to` is not specified
+pub struct ConnectionDiag {
+    dbg: Dbg,
+    tx_id: usize,
+    name: Option<String>,
+    send: Option<Sender<Point>>,
+    connected: Arc<AtomicBool>,
+    last: Option<bool>,
+}
+//
+//
+impl ConnectionDiag {
+    ///
+    /// Returns [ConnectionDiag] new instance
+    /// - `tx_id` - the producer of the diagnosis point
+    /// - `diagnosis` - configured diagnosis points of the service
+    /// - `send` - where diagnosis point to be sent
+    /// - `connected` - shared connection state of the service
+    pub fn new(
+        parent: impl Into<String>,
+        tx_id: usize,
+        diagnosis: &FxIndexMap<DiagKeywd, PointConfig>,
+        send: Option<Sender<Point>>,
+        connected: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "ConnectionDiag"),
+            tx_id,
+            name: diagnosis.get(&DiagKeywd::Connection).map(|conf| conf.name.clone()),
+            send,
+            connected,
+            last: None,
+        }
+    }
+    ///
+    /// Stores the connection state, sends the `Connection` diagnosis point if the state is changed
+    pub fn update(&mut self, connected: bool) {
+        self.connected.store(connected, Ordering::SeqCst);
+        if self.last == Some(connected) {
+            return;
+        }
+        self.last = Some(connected);
+        if let (Some(name), Some(send)) = (&self.name, &self.send) {
+            let status = if connected { Status::Ok } else { Status::Invalid };
+            let point = Point::Int(PointHlr::new_int(self.tx_id, name, i64::from(status)));
+            match send.send(point) {
+                Ok(_) => log::debug!("{}.update | '{}': {:?}", self.dbg, name, status),
+                Err(err) => log::warn!("{}.update | '{}' send error: {:?}", self.dbg, name, err),
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//!
+//! # TCP services exchanging points as JSON lines
+//! 
+//! - `TcpServer` - listens for the clients, multiple clients are allowed
+//! - `TcpClient` - connects to the server, reconnects with backoff
+//! - Each point is a single line of JSON, terminated by `\n`
+//! - Points from `subscribe` are written to the socket,
+//!   points read from the socket are sent to `send-to`
+//! - Connection state is reported by the `Connection` diagnosis point
+//! 
+//! ## Configuration example
+//! 
+//! ```yaml
+//! service TcpServer Server:
+//!     address: 0.0.0.0:8080
+//!     subscribe: MultiQueue
+//!     send-to: MultiQueue.in-queue
+//!     diagnosis:
+//!         point Connection:       # Ok(0) / Invalid(10)
+//!             type: 'Int'
+//! service TcpClient Client:
+//!     address: 127.0.0.1:8080
+//!     reconnect: 1 s
+//!     subscribe: MultiQueue
+//!     send-to: MultiQueue.in-queue
+//! ```
+mod connection_diag;
+mod tcp_client;
+mod tcp_conf;
+mod tcp_connection;
+mod tcp_server;
+
+pub use connection_diag::*;
+pub use tcp_client::*;
+pub use tcp_conf::*;
+pub use tcp_connection::*;
+pub use tcp_server::*;
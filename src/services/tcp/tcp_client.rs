@@ -0,0 +1,246 @@
+use std::{
+    net::{SocketAddr, TcpStream, ToSocketAddrs},
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
+    time::Duration,
+};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    kernel::clock::GlobalClock,
+    services::{
+        entity::{Name, Object, Point, PointConfig, PointLines, PointTxId},
+        service::{LinkName, Service, ServiceState},
+        services::Services,
+        subscription::ServiceSubscription,
+    },
+    sync::{channel::Sender, Handles},
+    thread_pool::Scheduler,
+};
+use super::{connection_diag::ConnectionDiag, tcp_conf::TcpConf, tcp_connection::TcpConnection};
+///
+/// ### Exchanges points with the server as JSON lines
+/// - Connects to the `address`, reconnects if connection lost or failed
+/// - Reconnect delay starts from `reconnect`, doubled after each failed attempt up to `max-reconnect`,
+///   measured by the clock of the `Services`
+/// - Points received by `subscribe` are written to the server,
+///   points received while disconnected are dropped
+/// - Points read from the server are sent to the `send-to`
+/// - `Connection` diagnosis point is Ok(0) while connected, otherwise Invalid(10)
+/// - Service state is `Degraded` while not connected
+pub struct TcpClient {
+    dbg: Dbg,
+    name: Name,
+    conf: TcpConf,
+    services: Arc<Services>,
+    scheduler: Option<Scheduler>,
+    connected: Arc<AtomicBool>,
+    handles: Handles<()>,
+    exit: Arc<AtomicBool>,
+}
+//
+//
+impl TcpClient {
+    const CYCLE: Duration = Duration::from_millis(10);
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+    ///
+    /// Returns [TcpClient] new instance
+    pub fn new(conf: TcpConf, services: Arc<Services>, scheduler: Option<Scheduler>) -> Self {
+        let dbg = Dbg::new(conf.name.parent(), conf.name.me());
+        Self {
+            name: conf.name.clone(),
+            conf,
+            services,
+            scheduler,
+            connected: Arc::new(AtomicBool::new(false)),
+            handles: Handles::new(&dbg),
+            exit: Arc::new(AtomicBool::new(false)),
+            dbg,
+        }
+    }
+    ///
+    /// Returns `true` if connected to the server
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+    ///
+    /// Main loop
+    fn run_(
+        dbg: Dbg,
+        conf: TcpConf,
+        tx_id: usize,
+        subscription: ServiceSubscription,
+        send: Option<Sender<Point>>,
+        mut diag: ConnectionDiag,
+        exit: Arc<AtomicBool>,
+    ) {
+        log::info!("{}.run | Preparing thread - ok", dbg);
+        let clock = GlobalClock::get();
+        let mut reconnect = conf.reconnect;
+        diag.update(false);
+        loop {
+            match Self::connect(&conf.address) {
+                Ok(stream) => match TcpConnection::new(&dbg, stream, tx_id, send.clone(), exit.clone()) {
+                    Ok(connection) => {
+                        reconnect = conf.reconnect;
+                        subscription.clear();
+                        diag.update(true);
+                        Self::exchange(&dbg, &connection, &subscription, &exit);
+                        connection.close();
+                        diag.update(false);
+                    }
+                    Err(err) => log::warn!("{}.run | Connection to '{}' failed: {}", dbg, conf.address, err),
+                }
+                Err(err) => log::warn!("{}.run | Connection to '{}' failed: {}", dbg, conf.address, err),
+            }
+            if exit.load(Ordering::SeqCst) {
+                break;
+            }
+            log::debug!("{}.run | Reconnecting in {:?}...", dbg, reconnect);
+            let time = clock.now();
+            while clock.elapsed(time) < reconnect && !exit.load(Ordering::SeqCst) {
+                subscription.clear();
+                clock.sleep(reconnect.saturating_sub(clock.elapsed(time)).min(Self::CYCLE));
+            }
+            if exit.load(Ordering::SeqCst) {
+                break;
+            }
+            reconnect = reconnect.saturating_mul(2).min(conf.max_reconnect);
+        }
+        subscription.unsubscribe();
+        log::info!("{}.run | Exit", dbg);
+    }
+    ///
+    /// Returns the stream connected to the first available address
+    fn connect(address: &str) -> Result<TcpStream, Error> {
+        let error = Error::new("TcpClient", "connect");
+        let addrs: Vec<SocketAddr> = address.to_socket_addrs()
+            .map_err(|err| error.pass_with(format!("Invalid address '{}'", address), err.to_string()))?
+            .collect();
+        let mut errors = vec![];
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, Self::CONNECT_TIMEOUT) {
+                Ok(stream) => return Ok(stream),
+                Err(err) => errors.push(format!("'{}': {}", addr, err)),
+            }
+        }
+        Err(error.err(errors.join(", ")))
+    }
+    ///
+    /// Writes points received by subscription to the server,
+    /// returns if the connection is closed or exit requested
+    fn exchange(dbg: &Dbg, connection: &TcpConnection, subscription: &ServiceSubscription, exit: &AtomicBool) {
+        while !connection.is_closed() && !exit.load(Ordering::SeqCst) {
+            if let Some(point) = subscription.recv(Self::CYCLE) {
+                log::trace!("{}.run | sending: {:?}", dbg, point);
+                match PointLines::encode(&point) {
+                    Ok(line) => if let Err(err) = connection.write(&line) {
+                        log::warn!("{}.run | {}", dbg, err);
+                    }
+                    Err(err) => log::warn!("{}.run | {}", dbg, err),
+                }
+            }
+        }
+    }
+}
+//
+//
+impl Object for TcpClient {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+//
+impl std::fmt::Debug for TcpClient {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("TcpClient")
+            .field("id", &self.dbg)
+            .field("address", &self.conf.address)
+            .finish()
+    }
+}
+//
+//
+impl Service for TcpClient {
+    //
+    //
+    fn send_to(&self) -> Vec<LinkName> {
+        self.conf.send_to.iter().cloned().collect()
+    }
+    //
+    //
+    fn subscribed_to(&self) -> Vec<String> {
+        ServiceSubscription::services(&self.name, &self.conf.subscribe)
+    }
+    //
+    //
+    fn points(&self) -> Vec<PointConfig> {
+        self.conf.diagnosis.values().cloned().collect()
+    }
+    //
+    //
+    fn run(&self) -> Result<(), Error> {
+        log::info!("{}.run | Starting...", self.dbg);
+        let error = Error::new(&self.dbg, "run");
+        let dbg = self.dbg.clone();
+        let conf = self.conf.clone();
+        let send = match &self.conf.send_to {
+            Some(send_to) => Some(
+                self.services.get_link(send_to)
+                    .map_err(|err| error.pass_with(format!("Invalid send-to '{}'", send_to), err.to_string()))?
+            ),
+            None => None,
+        };
+        let tx_id = PointTxId::from_str(&self.name.join());
+        let subscription = ServiceSubscription::new(&self.name, &self.conf.subscribe, self.services.clone())
+            .map_err(|err| error.pass(err))?;
+        let diag = ConnectionDiag::new(&self.dbg, tx_id, &self.conf.diagnosis, send.clone(), self.connected.clone());
+        let clock = self.services.clock();
+        let exit = self.exit.clone();
+        match &self.scheduler {
+            Some(scheduler) => {
+                let handle = scheduler.spawn(move || {
+                    let _clock = GlobalClock::local(clock);
+                    Self::run_(dbg, conf, tx_id, subscription, send, diag, exit);
+                    Ok(())
+                }).map_err(|err| error.pass_with("Start failed on Scheduler", err.to_string()))?;
+                self.handles.push(handle);
+            }
+            None => {
+                let handle = std::thread::Builder::new().name(format!("{}.run", dbg)).spawn(move || {
+                    let _clock = GlobalClock::local(clock);
+                    Self::run_(dbg, conf, tx_id, subscription, send, diag, exit);
+                }).map_err(|err| error.pass_with("Start failed on std::thread", err.to_string()))?;
+                self.handles.push(handle);
+            }
+        };
+        log::info!("{}.run | Started", self.dbg);
+        Ok(())
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    //
+    //
+    fn state(&self) -> ServiceState {
+        if self.is_finished() {
+            ServiceState::Stopped
+        } else if self.is_connected() {
+            ServiceState::Running
+        } else {
+            ServiceState::Degraded
+        }
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    collections::FxIndexMap,
+    services::{
+        conf::{ConfTree, ConfTreeGet, DiagKeywd},
+        entity::{Name, PointConfig},
+        service::LinkName,
+        subscription::ConfSubscribe,
+    },
+};
+///
+/// Configuration of the `TcpServer` / `TcpClient`
+///
+/// ```yaml
+/// service TcpClient Client:
+///     address: 127.0.0.1:8080         # address to connect to (TcpClient) / to listen on (TcpServer)
+///     reconnect: 1 s                  # TcpClient only, delay before the first reconnect, default 1 s
+///     max-reconnect: 30 s             # TcpClient only, reconnect delay is doubled up to, default 30 s
+///     subscribe: MultiQueue           # optional, points to be written to the socket
+///     send-to: MultiQueue.in-queue    # optional, points read from the socket
+///     diagnosis:                      # optional
+///         point Connection:           # Ok(0) / Invalid(10)
+///             type: 'Int'
+/// ```
+///
+/// Service names in `subscribe` and `send-to` without leading `/` are resolved within the application
+#[derive(Debug, Clone)]
+pub struct TcpConf {
+    pub(crate) name: Name,
+    pub(crate) address: String,
+    pub(crate) reconnect: Duration,
+    pub(crate) max_reconnect: Duration,
+    pub(crate) subscribe: ConfSubscribe,
+    pub(crate) send_to: Option<LinkName>,
+    pub(crate) diagnosis: FxIndexMap<DiagKeywd, PointConfig>,
+}
+//
+//
+impl TcpConf {
+    const DEFAULT_RECONNECT: Duration = Duration::from_secs(1);
+    const DEFAULT_MAX_RECONNECT: Duration = Duration::from_secs(30);
+    ///
+    /// Returns [TcpConf] parsed from the service config node
+    /// - `parent` - the name of the application
+    pub fn new(parent: impl Into<String>, conf: ConfTree) -> Result<Self, Error> {
+        let me = conf.sufix_or(conf.name()?);
+        let name = Name::new(parent, &me);
+        let dbg = Dbg::new(name.parent(), format!("TcpConf({})", me));
+        let error = Error::new(&dbg, "new");
+        log::trace!("{}.new | conf: {:?}", dbg, conf);
+        let address = conf.as_str("address").map_err(|err| error.pass_with("Parameter 'address' - not found", err.to_string()))?.to_owned();
+        let reconnect = conf.get_duration("reconnect").unwrap_or(Self::DEFAULT_RECONNECT);
+        let max_reconnect = conf.get_duration("max-reconnect").unwrap_or(Self::DEFAULT_MAX_RECONNECT).max(reconnect);
+        let subscribe = ConfSubscribe::new(ConfTreeGet::<serde_yaml::Value>::get(&conf, "subscribe").unwrap_or_default());
+        let send_to = match ConfTreeGet::<serde_yaml::Value>::get(&conf, "send-to") {
+            Some(serde_yaml::Value::String(send_to)) => match Self::link(&name, &send_to) {
+                Some(send_to) => Some(send_to),
+                None => return Err(error.err(format!("Parameter 'send-to' - 'Service.link' expected, but found: '{}'", send_to))),
+            },
+            Some(send_to) => return Err(error.err(format!("Parameter 'send-to' - String expected, but found: {:?}", send_to))),
+            None => None,
+        };
+        let diagnosis = conf.get_diagnosis(name.join());
+        log::debug!("{}.new | address: {}, subscribe: {:?}, send-to: {:?}", dbg, address, subscribe, send_to);
+        Ok(Self { name, address, reconnect, max_reconnect, subscribe, send_to, diagnosis })
+    }
+    ///
+    /// Returns the link name 'Service.link', service resolved within the application if relative
+    fn link(name: &Name, link: &str) -> Option<LinkName> {
+        link.rsplit_once('.').map(|(service, link)| LinkName::new(name.resolve(service), link))
+    }
+}
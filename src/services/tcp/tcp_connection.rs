@@ -0,0 +1,148 @@
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{Shutdown, SocketAddr, TcpStream},
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
+    time::Duration,
+};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    services::entity::{Point, PointLines},
+    sync::{channel::Sender, Handles},
+};
+///
+/// Single connected socket, exchanging points as JSON lines
+/// - Lines read from the socket in the separate thread, parsed points are sent into `send`
+/// - Points are written to the socket by `write`
+/// - Connection is closed if the peer is disconnected, read / write failed or exit requested
+pub struct TcpConnection {
+    dbg: Dbg,
+    addr: SocketAddr,
+    stream: TcpStream,
+    closed: Arc<AtomicBool>,
+    handles: Handles<()>,
+}
+//
+//
+impl TcpConnection {
+    const READ_TIMEOUT: Duration = Duration::from_millis(100);
+    const WRITE_TIMEOUT: Duration = Duration::from_secs(3);
+    ///
+    /// Returns [TcpConnection] new instance, reading thread is started
+    /// - `tx_id` - the producer of the points read from the socket
+    /// - `send` - where points read from the socket to be sent, points are dropped if `None`
+    /// - `exit` - exit signal of the owning service
+    pub fn new(parent: impl Into<String>, stream: TcpStream, tx_id: usize, send: Option<Sender<Point>>, exit: Arc<AtomicBool>) -> Result<Self, Error> {
+        let addr = stream.peer_addr().map_err(|err| Error::new("TcpConnection", "new").pass_with("Peer address error", err.to_string()))?;
+        let dbg = Dbg::new(parent, format!("TcpConnection({})", addr));
+        let error = Error::new(&dbg, "new");
+        stream.set_nonblocking(false).map_err(|err| error.pass_with("Set blocking error", err.to_string()))?;
+        stream.set_nodelay(true).map_err(|err| error.pass_with("Set nodelay error", err.to_string()))?;
+        stream.set_read_timeout(Some(Self::READ_TIMEOUT)).map_err(|err| error.pass_with("Set read timeout error", err.to_string()))?;
+        stream.set_write_timeout(Some(Self::WRITE_TIMEOUT)).map_err(|err| error.pass_with("Set write timeout error", err.to_string()))?;
+        let reader = stream.try_clone().map_err(|err| error.pass_with("Stream clone error", err.to_string()))?;
+        let closed = Arc::new(AtomicBool::new(false));
+        let handles = Handles::new(&dbg);
+        let handle = std::thread::Builder::new().name(format!("{}.read", dbg)).spawn({
+            let dbg = dbg.clone();
+            let closed = closed.clone();
+            move || {
+                Self::read(&dbg, reader, tx_id, send, &exit);
+                closed.store(true, Ordering::SeqCst);
+            }
+        }).map_err(|err| error.pass_with("Start failed on std::thread", err.to_string()))?;
+        handles.push(handle);
+        log::info!("{}.new | Connected", dbg);
+        Ok(Self { dbg, addr, stream, closed, handles })
+    }
+    ///
+    /// Returns the address of the peer
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+    ///
+    /// Returns `true` if the connection is closed
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+    ///
+    /// Writes encoded JSON line to the socket, connection is closed on error
+    pub fn write(&self, line: &[u8]) -> Result<(), Error> {
+        if let Err(err) = (&self.stream).write_all(line) {
+            self.closed.store(true, Ordering::SeqCst);
+            return Err(Error::new(&self.dbg, "write").pass_with("Write error", err.to_string()));
+        }
+        Ok(())
+    }
+    ///
+    /// Closes the socket and waits for the reading thread
+    pub fn close(self) {
+        self.closed.store(true, Ordering::SeqCst);
+        if let Err(err) = self.stream.shutdown(Shutdown::Both) {
+            log::debug!("{}.close | Shutdown error: {:?}", self.dbg, err);
+        }
+        if let Err(err) = self.handles.wait() {
+            log::warn!("{}.close | Reading thread error: {:?}", self.dbg, err);
+        }
+        log::info!("{}.close | Disconnected", self.dbg);
+    }
+    ///
+    /// Reading loop, returns if the peer is disconnected, read error or exit requested
+    fn read(dbg: &Dbg, stream: TcpStream, tx_id: usize, send: Option<Sender<Point>>, exit: &AtomicBool) {
+        let mut reader = BufReader::new(stream);
+        let mut line = vec![];
+        loop {
+            if exit.load(Ordering::SeqCst) {
+                break;
+            }
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => {
+                    log::debug!("{}.read | Closed by peer", dbg);
+                    break;
+                }
+                Ok(_) => {
+                    // Line without '\n' - the peer is closed in the middle of the line, next read returns 0
+                    if line.ends_with(b"\n") {
+                        Self::parse(dbg, &line, tx_id, send.as_ref());
+                        line.clear();
+                    }
+                }
+                // Bytes read before timeout are kept in the `line`
+                Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted) => {}
+                Err(err) => {
+                    log::warn!("{}.read | Read error: {:?}", dbg, err);
+                    break;
+                }
+            }
+        }
+    }
+    ///
+    /// Sends the point parsed from the `line`, empty and invalid lines are skipped
+    fn parse(dbg: &Dbg, line: &[u8], tx_id: usize, send: Option<&Sender<Point>>) {
+        if line.trim_ascii().is_empty() {
+            return;
+        }
+        match PointLines::decode(line, tx_id) {
+            Ok(point) => {
+                log::trace!("{}.read | received: {:?}", dbg, point);
+                match send {
+                    Some(send) => if let Err(err) = send.send(point) {
+                        log::warn!("{}.read | Send error: {:?}", dbg, err);
+                    }
+                    None => log::trace!("{}.read | 'send-to' is not specified, point dropped", dbg),
+                }
+            }
+            Err(err) => log::warn!("{}.read | {}", dbg, err),
+        }
+    }
+}
+//
+//
+impl std::fmt::Debug for TcpConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpConnection")
+            .field("dbg", &self.dbg)
+            .field("addr", &self.addr)
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    io::ErrorKind,
+    net::{SocketAddr, TcpListener},
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
+    time::Duration,
+};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    services::{
+        entity::{Name, Object, Point, PointConfig, PointLines, PointTxId},
+        service::{LinkName, Service},
+        services::Services,
+        subscription::ServiceSubscription,
+    },
+    sync::{channel::Sender, Handles, Mutex},
+    thread_pool::Scheduler,
+};
+use super::{connection_diag::ConnectionDiag, tcp_conf::TcpConf, tcp_connection::TcpConnection};
+///
+/// ### Exchanges points with the connected clients as JSON lines
+/// - Listens on the `address`, multiple clients are allowed
+/// - Points received by `subscribe` are written to the all connected clients
+/// - Points read from the clients are sent to the `send-to`
+/// - `Connection` diagnosis point is Ok(0) while at least one client connected, otherwise Invalid(10)
+pub struct TcpServer {
+    dbg: Dbg,
+    name: Name,
+    conf: TcpConf,
+    services: Arc<Services>,
+    scheduler: Option<Scheduler>,
+    local_addr: Mutex<Option<SocketAddr>>,
+    connected: Arc<AtomicBool>,
+    handles: Handles<()>,
+    exit: Arc<AtomicBool>,
+}
+//
+//
+impl TcpServer {
+    const CYCLE: Duration = Duration::from_millis(10);
+    ///
+    /// Returns [TcpServer] new instance
+    pub fn new(conf: TcpConf, services: Arc<Services>, scheduler: Option<Scheduler>) -> Self {
+        let dbg = Dbg::new(conf.name.parent(), conf.name.me());
+        Self {
+            name: conf.name.clone(),
+            conf,
+            services,
+            scheduler,
+            local_addr: Mutex::new(None),
+            connected: Arc::new(AtomicBool::new(false)),
+            handles: Handles::new(&dbg),
+            exit: Arc::new(AtomicBool::new(false)),
+            dbg,
+        }
+    }
+    ///
+    /// Returns the address the server is listening on, `None` until started
+    ///
+    /// Useful if configured with port 0, the port assigned by the OS
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        *self.local_addr.lock()
+    }
+    ///
+    /// Returns `true` if at least one client connected
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+    ///
+    /// Main loop
+    fn run_(
+        dbg: Dbg,
+        listener: TcpListener,
+        tx_id: usize,
+        subscription: ServiceSubscription,
+        send: Option<Sender<Point>>,
+        mut diag: ConnectionDiag,
+        exit: Arc<AtomicBool>,
+    ) {
+        log::info!("{}.run | Preparing thread - ok", dbg);
+        let mut clients: Vec<TcpConnection> = vec![];
+        diag.update(false);
+        loop {
+            loop {
+                match listener.accept() {
+                    Ok((stream, addr)) => {
+                        match TcpConnection::new(&dbg, stream, tx_id, send.clone(), exit.clone()) {
+                            Ok(client) => clients.push(client),
+                            Err(err) => log::warn!("{}.run | Client '{}' rejected: {}", dbg, addr, err),
+                        }
+                    }
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                    Err(err) => {
+                        log::warn!("{}.run | Accept error: {:?}", dbg, err);
+                        break;
+                    }
+                }
+            }
+            if let Some(point) = subscription.recv(Self::CYCLE) {
+                log::trace!("{}.run | sending: {:?}", dbg, point);
+                match PointLines::encode(&point) {
+                    Ok(line) => {
+                        for client in &clients {
+                            if let Err(err) = client.write(&line) {
+                                log::warn!("{}.run | Client '{}': {}", dbg, client.addr(), err);
+                            }
+                        }
+                    }
+                    Err(err) => log::warn!("{}.run | {}", dbg, err),
+                }
+            }
+            let (closed, open): (Vec<_>, Vec<_>) = clients.into_iter().partition(|client| client.is_closed());
+            for client in closed {
+                client.close();
+            }
+            clients = open;
+            diag.update(!clients.is_empty());
+            if exit.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+        for client in clients {
+            client.close();
+        }
+        diag.update(false);
+        subscription.unsubscribe();
+        log::info!("{}.run | Exit", dbg);
+    }
+}
+//
+//
+impl Object for TcpServer {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+//
+impl std::fmt::Debug for TcpServer {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("TcpServer")
+            .field("id", &self.dbg)
+            .field("address", &self.conf.address)
+            .finish()
+    }
+}
+//
+//
+impl Service for TcpServer {
+    //
+    //
+    fn send_to(&self) -> Vec<LinkName> {
+        self.conf.send_to.iter().cloned().collect()
+    }
+    //
+    //
+    fn subscribed_to(&self) -> Vec<String> {
+        ServiceSubscription::services(&self.name, &self.conf.subscribe)
+    }
+    //
+    //
+    fn points(&self) -> Vec<PointConfig> {
+        self.conf.diagnosis.values().cloned().collect()
+    }
+    //
+    //
+    fn run(&self) -> Result<(), Error> {
+        log::info!("{}.run | Starting...", self.dbg);
+        let error = Error::new(&self.dbg, "run");
+        let dbg = self.dbg.clone();
+        let listener = TcpListener::bind(&self.conf.address)
+            .map_err(|err| error.pass_with(format!("Bind on '{}' failed", self.conf.address), err.to_string()))?;
+        listener.set_nonblocking(true).map_err(|err| error.pass_with("Set nonblocking error", err.to_string()))?;
+        let local_addr = listener.local_addr().map_err(|err| error.pass_with("Local address error", err.to_string()))?;
+        *self.local_addr.lock() = Some(local_addr);
+        log::info!("{}.run | Listening on '{}'", self.dbg, local_addr);
+        let send = match &self.conf.send_to {
+            Some(send_to) => Some(
+                self.services.get_link(send_to)
+                    .map_err(|err| error.pass_with(format!("Invalid send-to '{}'", send_to), err.to_string()))?
+            ),
+            None => None,
+        };
+        let tx_id = PointTxId::from_str(&self.name.join());
+        let subscription = ServiceSubscription::new(&self.name, &self.conf.subscribe, self.services.clone())
+            .map_err(|err| error.pass(err))?;
+        let diag = ConnectionDiag::new(&self.dbg, tx_id, &self.conf.diagnosis, send.clone(), self.connected.clone());
+        let exit = self.exit.clone();
+        match &self.scheduler {
+            Some(scheduler) => {
+                let handle = scheduler.spawn(move || {
+                    Self::run_(dbg, listener, tx_id, subscription, send, diag, exit);
+                    Ok(())
+                }).map_err(|err| error.pass_with("Start failed on Scheduler", err.to_string()))?;
+                self.handles.push(handle);
+            }
+            None => {
+                let handle = std::thread::Builder::new().name(format!("{}.run", dbg)).spawn(move || {
+                    Self::run_(dbg, listener, tx_id, subscription, send, diag, exit);
+                }).map_err(|err| error.pass_with("Start failed on std::thread", err.to_string()))?;
+                self.handles.push(handle);
+            }
+        };
+        log::info!("{}.run | Started", self.dbg);
+        Ok(())
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::SeqCst);
+    }
+}
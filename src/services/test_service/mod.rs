@@ -0,0 +1,12 @@
+//!
+//! # Test harness service
+//!
+//! - `TestService` - records received points with the arrival time
+//!   and provides waiting assertions for the integration tests
+//!
+//! Available in the crate tests and with the `test-service` cargo feature
+mod test_records;
+mod test_service;
+
+pub use test_records::*;
+pub use test_service::*;
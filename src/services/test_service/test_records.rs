@@ -0,0 +1,89 @@
+use std::time::{Duration, Instant};
+use parking_lot::Condvar;
+use crate::{services::entity::Point, sync::Mutex};
+///
+/// The point received by the `TestService`
+/// - `source` - the link or the service the point was received from
+/// - `arrived` - the time the point was received
+#[derive(Debug, Clone)]
+pub struct TestRecord {
+    pub point: Point,
+    pub source: String,
+    pub arrived: Instant,
+}
+///
+/// Received points in the arrival order, waiting threads are notified on the each new point
+#[derive(Debug, Default)]
+pub struct TestRecords {
+    records: Mutex<Vec<TestRecord>>,
+    changed: Condvar,
+}
+//
+//
+impl TestRecords {
+    ///
+    /// Returns [TestRecords] new empty instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Appends the `point` received from the `source`
+    pub fn push(&self, source: &str, point: Point) {
+        self.records.lock().push(TestRecord { point, source: source.to_owned(), arrived: Instant::now() });
+        self.changed.notify_all();
+    }
+    ///
+    /// Returns all records
+    pub fn all(&self) -> Vec<TestRecord> {
+        self.records.lock().clone()
+    }
+    ///
+    /// Returns the number of the records
+    pub fn len(&self) -> usize {
+        self.records.lock().len()
+    }
+    ///
+    /// Returns `true` if nothing received
+    pub fn is_empty(&self) -> bool {
+        self.records.lock().is_empty()
+    }
+    ///
+    /// Removes all records
+    pub fn clear(&self) {
+        self.records.lock().clear();
+    }
+    ///
+    /// Returns the first record starting from the index `from`, matching the `filter`,
+    /// waits for it up to `timeout`
+    pub fn wait_for(&self, from: usize, timeout: Duration, filter: impl Fn(&Point) -> bool) -> Option<TestRecord> {
+        let deadline = Instant::now() + timeout;
+        let mut records = self.records.lock();
+        let mut checked = from;
+        loop {
+            if let Some(record) = records.iter().skip(checked).find(|record| filter(&record.point)) {
+                return Some(record.clone());
+            }
+            checked = records.len();
+            if self.changed.wait_until(&mut records, deadline).timed_out() {
+                return records.iter().skip(checked).find(|record| filter(&record.point)).cloned();
+            }
+        }
+    }
+    ///
+    /// Returns the first `count` records, waits for them up to `timeout`
+    ///
+    /// Returns all records received, if less then `count` received during `timeout`
+    pub fn wait_count(&self, count: usize, timeout: Duration) -> Result<Vec<TestRecord>, Vec<TestRecord>> {
+        let deadline = Instant::now() + timeout;
+        let mut records = self.records.lock();
+        while records.len() < count {
+            if self.changed.wait_until(&mut records, deadline).timed_out() {
+                break;
+            }
+        }
+        match records.len() >= count {
+            true => Ok(records[..count].to_vec()),
+            false => Err(records.clone()),
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    mem::discriminant,
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
+    time::Duration,
+};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    services::{
+        entity::{Name, Object, Point, ToPoint},
+        service::{Service, ServiceError},
+        services::Services,
+        subscription::SubscriptionCriteria,
+    },
+    sync::{channel::{self, Receiver, RecvTimeoutError, Sender}, Handles, Mutex},
+};
+use super::test_records::{TestRecord, TestRecords};
+///
+/// ### Records received points and asserts on them, used in the integration tests
+/// - Exposes named links, returned by `get_link`, and / or subscribes on the another services
+/// - Every received [Point] is recorded with the arrival time and the source
+/// - Waiting assertions return as soon as condition is met,
+///   so tests do not need to sleep for the arbitrary time
+///
+/// **Example**
+/// ```ignore
+/// let test = Arc::new(TestService::new("App", "Test", &["in-queue"]));
+/// services.insert(test.clone());
+/// test.subscribe_on(&services, "/App/MultiQueue", &[])?;
+/// ...
+/// test.wait_value("/App/Drive/Speed", 100.0f64, Duration::from_millis(500))?;
+/// test.assert_none(Duration::from_millis(100), |point| point.status() != Status::Ok)?;
+/// test.assert_order(&["/App/Drive/Start", "/App/Drive/Speed"])?;
+/// ```
+pub struct TestService {
+    dbg: Dbg,
+    name: Name,
+    links: Vec<(String, Sender<Point>)>,
+    subscriptions: Mutex<Vec<(String, Vec<SubscriptionCriteria>)>>,
+    records: Arc<TestRecords>,
+    handles: Handles<()>,
+    exit: Arc<AtomicBool>,
+}
+//
+//
+impl TestService {
+    const RECV_TIMEOUT: Duration = Duration::from_millis(10);
+    ///
+    /// Returns [TestService] new instance
+    /// - `links` - names of the incoming links, returned by `get_link`
+    ///
+    /// Points are recorded from the moment of creation, `run` is not required
+    pub fn new(parent: impl Into<String>, name: impl Into<String>, links: &[&str]) -> Self {
+        let name = Name::new(parent, name);
+        let dbg = Dbg::new(name.parent(), name.me());
+        let records = Arc::new(TestRecords::new());
+        let handles = Handles::new(&dbg);
+        let exit = Arc::new(AtomicBool::new(false));
+        let links = links.iter().map(|link| {
+            let (send, recv) = channel::unbounded();
+            handles.push(Self::record(&dbg, link, recv, records.clone(), exit.clone()));
+            (link.to_string(), send)
+        }).collect();
+        Self {
+            dbg,
+            name,
+            links,
+            subscriptions: Mutex::new(vec![]),
+            records,
+            handles,
+            exit,
+        }
+    }
+    ///
+    /// Subscribes on the `service`, received points will be recorded
+    /// - `points` - criterias of the subscription, empty for the all points
+    pub fn subscribe_on(&self, services: &Services, service: &str, points: &[SubscriptionCriteria]) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "subscribe_on");
+        let (_, recv) = services.try_subscribe(service, &self.name.join(), points)
+            .map_err(|err| error.pass_with(format!("Subscription on '{}' failed", service), err.to_string()))?;
+        self.subscriptions.lock().push((service.to_owned(), points.to_vec()));
+        self.handles.push(Self::record(&self.dbg, service, recv, self.records.clone(), self.exit.clone()));
+        Ok(())
+    }
+    ///
+    /// Cancels all subscriptions made by `subscribe_on`
+    pub fn unsubscribe_all(&self, services: &Services) {
+        for (service, points) in self.subscriptions.lock().drain(..) {
+            if let Err(err) = services.try_unsubscribe(&service, &self.name.join(), &points) {
+                log::warn!("{}.unsubscribe_all | Unsubscribe from '{}' error: {}", self.dbg, service, err);
+            }
+        }
+    }
+    ///
+    /// Returns all received records in the arrival order
+    pub fn records(&self) -> Vec<TestRecord> {
+        self.records.all()
+    }
+    ///
+    /// Returns all received points in the arrival order
+    pub fn received(&self) -> Vec<Point> {
+        self.records.all().into_iter().map(|record| record.point).collect()
+    }
+    ///
+    /// Returns the number of the received points
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+    ///
+    /// Returns `true` if nothing received
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+    ///
+    /// Forgets all received points
+    pub fn clear(&self) {
+        self.records.clear()
+    }
+    ///
+    /// Returns the first received record matching the `filter`, waits for it up to `timeout`
+    pub fn wait_for(&self, timeout: Duration, filter: impl Fn(&Point) -> bool) -> Result<TestRecord, Error> {
+        self.records.wait_for(0, timeout, filter)
+            .ok_or_else(|| Error::new(&self.dbg, "wait_for").err(format!("No matching point received within {:?}, received: {}", timeout, self.records.len())))
+    }
+    ///
+    /// Returns the first received point with the `name`, waits for it up to `timeout`
+    pub fn wait_point(&self, name: &str, timeout: Duration) -> Result<TestRecord, Error> {
+        self.records.wait_for(0, timeout, |point| point.name() == name)
+            .ok_or_else(|| Error::new(&self.dbg, "wait_point").err(format!("Point '{}' not received within {:?}", name, timeout)))
+    }
+    ///
+    /// Returns the first received point with the `name` and the `value`, waits for it up to `timeout`
+    /// - The type of the point must be the same as the type of the `value`
+    pub fn wait_value<T: ToPoint>(&self, name: &str, value: T, timeout: Duration) -> Result<TestRecord, Error> {
+        let target = value.to_point(0, name);
+        self.records.wait_for(0, timeout, |point| {
+            point.name() == name && discriminant(point) == discriminant(&target) && point.cmp_value(&target)
+        }).ok_or_else(|| {
+            let last = self.records.all().into_iter().rev().find(|record| record.point.name() == name).map(|record| record.point.value());
+            Error::new(&self.dbg, "wait_value").err(format!(
+                "Point '{}' = {:?} not received within {:?}, last received value: {:?}", name, target.value(), timeout, last,
+            ))
+        })
+    }
+    ///
+    /// Returns the first `count` received records, waits for them up to `timeout`
+    pub fn wait_count(&self, count: usize, timeout: Duration) -> Result<Vec<TestRecord>, Error> {
+        self.records.wait_count(count, timeout).map_err(|records| {
+            Error::new(&self.dbg, "wait_count").err(format!("Expected {} points within {:?}, received: {}", count, timeout, records.len()))
+        })
+    }
+    ///
+    /// Returns an error if any point matching the `filter` is received during `duration`, starting from now
+    pub fn assert_none(&self, duration: Duration, filter: impl Fn(&Point) -> bool) -> Result<(), Error> {
+        match self.records.wait_for(self.records.len(), duration, filter) {
+            Some(record) => Err(Error::new(&self.dbg, "assert_none").err(format!("Unexpected point received from '{}': {:?}", record.source, record.point))),
+            None => Ok(()),
+        }
+    }
+    ///
+    /// Returns an error if the points with the `names` are not received or first received not in the given order
+    pub fn assert_order(&self, names: &[&str]) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "assert_order");
+        let records = self.records.all();
+        let mut positions = vec![];
+        for name in names {
+            match records.iter().position(|record| record.point.name() == *name) {
+                Some(position) => positions.push(position),
+                None => return Err(error.err(format!("Point '{}' not received", name))),
+            }
+        }
+        match positions.windows(2).position(|pair| pair[0] > pair[1]) {
+            Some(index) => Err(error.err(format!("Point '{}' received before '{}'", names[index + 1], names[index]))),
+            None => Ok(()),
+        }
+    }
+    ///
+    /// Returns the thread recording the points received by `recv`
+    fn record(dbg: &Dbg, source: &str, recv: Receiver<Point>, records: Arc<TestRecords>, exit: Arc<AtomicBool>) -> std::thread::JoinHandle<()> {
+        let dbg = dbg.clone();
+        let source = source.to_owned();
+        std::thread::Builder::new().name(format!("{}.record({})", dbg, source)).spawn(move || {
+            log::debug!("{}.record | Recording from '{}'...", dbg, source);
+            while !exit.load(Ordering::SeqCst) {
+                match recv.recv_timeout(Self::RECV_TIMEOUT) {
+                    Ok(point) => {
+                        log::trace!("{}.record | '{}': {:?}", dbg, source, point);
+                        records.push(&source, point);
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(err) => {
+                        log::debug!("{}.record | '{}' closed: {:?}", dbg, source, err);
+                        break;
+                    }
+                }
+            }
+            log::debug!("{}.record | Recording from '{}' - exit", dbg, source);
+        }).unwrap_or_else(|err| panic!("{}.record | Start failed: {}", dbg, err))
+    }
+}
+//
+//
+impl Object for TestService {
+    fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+//
+//
+impl std::fmt::Debug for TestService {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("TestService")
+            .field("id", &self.dbg)
+            .field("received", &self.records.len())
+            .finish()
+    }
+}
+//
+//
+impl Service for TestService {
+    //
+    //
+    fn try_get_link(&self, name: &str) -> Result<Sender<Point>, ServiceError> {
+        match self.links.iter().find(|(link, _)| link == name) {
+            Some((_, send)) => Ok(send.clone()),
+            None => Err(ServiceError::link_not_found(self.name.join(), name)),
+        }
+    }
+    //
+    //
+    fn in_queues(&self) -> Vec<String> {
+        self.links.iter().map(|(link, _)| link.clone()).collect()
+    }
+    //
+    //
+    fn subscribed_to(&self) -> Vec<String> {
+        self.subscriptions.lock().iter().map(|(service, _)| service.clone()).collect()
+    }
+    //
+    //
+    fn run(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    //
+    //
+    fn is_finished(&self) -> bool {
+        self.handles.is_finished()
+    }
+    //
+    //
+    fn wait(&self) -> Result<(), Error> {
+        self.handles.wait()
+    }
+    //
+    //
+    fn exit(&self) {
+        self.exit.store(true, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,11 @@
+//!
+//! # Live topology of the services
+//! 
+//! - Services, their incoming queues and `send-to` links
+//! - Subscriptions registered on the services
+//! - Points owned by the services
+//! - Exported as JSON or Graphviz DOT
+//! 
+mod topology;
+
+pub use topology::*;
@@ -0,0 +1,171 @@
+use serde::Serialize;
+use sal_core::error::Error;
+use crate::services::service::ServiceState;
+///
+/// Subscription registered on the service
+/// - `receiver` - the name of the receiver
+/// - `broadcast` - receiver gets all points
+/// - `destinations` - subscribed points in the format "Cot:point name", empty for broadcast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TopologySubscription {
+    pub receiver: String,
+    pub broadcast: bool,
+    pub destinations: Vec<String>,
+}
+//
+//
+impl TopologySubscription {
+    ///
+    /// Returns broadcast [TopologySubscription]
+    pub fn broadcast(receiver: impl Into<String>) -> Self {
+        Self { receiver: receiver.into(), broadcast: true, destinations: vec![] }
+    }
+    ///
+    /// Returns multicast [TopologySubscription]
+    pub fn multicast(receiver: impl Into<String>, destinations: Vec<String>) -> Self {
+        Self { receiver: receiver.into(), broadcast: false, destinations }
+    }
+}
+///
+/// Node of the [Topology] - single service
+/// - `name` - full name of the service
+/// - `state` - last known lifecycle state
+/// - `in_queues` - incoming queues, configured by `in queue`
+/// - `send_to` - links to the incoming queues of another services, configured by `send-to`
+/// - `subscribed_to` - services subscribed on, configured by `subscribe`
+/// - `subscriptions` - subscriptions of the receivers registered on the service
+/// - `points` - names of the points owned by the service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TopologyService {
+    pub name: String,
+    pub state: Option<ServiceState>,
+    pub in_queues: Vec<String>,
+    pub send_to: Vec<String>,
+    pub subscribed_to: Vec<String>,
+    pub subscriptions: Vec<TopologySubscription>,
+    pub points: Vec<String>,
+}
+///
+/// Snapshot of the live graph of the services, returned by [Services::topology](crate::services::Services::topology)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Topology {
+    pub services: Vec<TopologyService>,
+}
+//
+//
+impl Topology {
+    ///
+    /// Returns the service by its name
+    pub fn service(&self, name: &str) -> Option<&TopologyService> {
+        self.services.iter().find(|service| service.name == name)
+    }
+    ///
+    /// Returns the name of the service owning the point
+    pub fn owner(&self, point: &str) -> Option<&str> {
+        self.services
+            .iter()
+            .find(|service| service.points.iter().any(|p| p == point))
+            .map(|service| service.name.as_str())
+    }
+    ///
+    /// Returns the topology as pretty printed JSON
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| Error::new("Topology", "to_json").err(err.to_string()))
+    }
+    ///
+    /// Returns the topology as Graphviz DOT
+    /// - Service is a record node with its incoming queues as ports
+    /// - `send-to` link is a solid edge to the queue port
+    /// - Subscription is a dashed edge from the service to the receiver,
+    ///   labeled by `*` for broadcast or by the number of the subscribed points
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph Services {\n    rankdir=LR;\n    node [shape=record];\n");
+        for service in &self.services {
+            let queues: Vec<String> = service.in_queues
+                .iter()
+                .map(|queue| format!("<{}> {}", Self::port(queue), Self::escape(queue)))
+                .collect();
+            let state = service.state.map(|state| format!("\\n{}", state)).unwrap_or_default();
+            let points = match service.points.len() {
+                0 => String::new(),
+                len => format!("\\npoints: {}", len),
+            };
+            let label = if queues.is_empty() {
+                format!("{}{}{}", Self::escape(&service.name), state, points)
+            } else {
+                format!("{{{}{}{}|{{{}}}}}", Self::escape(&service.name), state, points, queues.join("|"))
+            };
+            dot.push_str(&format!("    {} [label=\"{}\"];\n", Self::id(&service.name), label));
+        }
+        for service in &self.services {
+            for link in &service.send_to {
+                dot.push_str(&format!("    {} -> {};\n", Self::id(&service.name), self.target(link)));
+            }
+            for subscription in &service.subscriptions {
+                let label = if subscription.broadcast {
+                    "*".to_owned()
+                } else {
+                    subscription.destinations.len().to_string()
+                };
+                dot.push_str(&format!(
+                    "    {} -> {} [style=dashed, label=\"{}\"];\n",
+                    Self::id(&service.name), self.target(&subscription.receiver), label,
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+    ///
+    /// Returns the DOT edge target for the service name or link name (Service.link),
+    /// pointing to the queue port if the link refers to the known incoming queue
+    fn target(&self, name: &str) -> String {
+        if let Some((service, queue)) = name.rsplit_once('.') {
+            if self.service(service).is_some_and(|s| s.in_queues.iter().any(|q| q == queue)) {
+                return format!("{}:{}", Self::id(service), Self::port(queue));
+            }
+        }
+        Self::id(name)
+    }
+    ///
+    /// Returns the DOT port name for the queue
+    fn port(queue: &str) -> String {
+        queue.chars().map(|c| if c.is_alphanumeric() { c } else { '_' }).collect()
+    }
+    ///
+    /// Returns the DOT quoted id, `"` and `\` are escaped, line breaks are replaced by `\n`
+    fn id(value: &str) -> String {
+        let mut id = String::with_capacity(value.len() + 2);
+        id.push('"');
+        for c in value.chars() {
+            match c {
+                '"' | '\\' => {
+                    id.push('\\');
+                    id.push(c);
+                }
+                '\n' => id.push_str("\\n"),
+                '\r' => {}
+                _ => id.push(c),
+            }
+        }
+        id.push('"');
+        id
+    }
+    ///
+    /// Escapes DOT record label special characters, line breaks are replaced by `\n`
+    fn escape(value: &str) -> String {
+        value.chars().fold(String::new(), |mut escaped, c| {
+            match c {
+                '{' | '}' | '|' | '<' | '>' | '"' | '\\' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                '\n' => escaped.push_str("\\n"),
+                '\r' => {}
+                _ => escaped.push(c),
+            }
+            escaped
+        })
+    }
+}
@@ -2,6 +2,12 @@ pub type Sender<T> = kanal::Sender<T>;
 pub type Receiver<T> = kanal::Receiver<T>;
 pub type RecvTimeoutError = kanal::ReceiveErrorTimeout;
 pub type SendError = kanal::SendError;
+pub type ReceiveError = kanal::ReceiveError;
+///
+/// Async counterparts, sharing the same channel with the sync ones,
+/// so the link can be used from the async runtime without a bridging thread
+pub type AsyncSender<T> = kanal::AsyncSender<T>;
+pub type AsyncReceiver<T> = kanal::AsyncReceiver<T>;
 
 ///
 /// Creates a new sync bounded channel with the requested buffer size,
@@ -15,3 +21,35 @@ pub fn bounded<T>(size: usize) -> (Sender<T>, Receiver<T>) {
 pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
     kanal::unbounded()
 }
+///
+/// Creates a new async bounded channel with the requested buffer size,
+/// and returns AsyncSender and AsyncReceiver of the channel for type T,
+pub fn bounded_async<T>(size: usize) -> (AsyncSender<T>, AsyncReceiver<T>) {
+    kanal::bounded_async(size)
+}
+///
+/// Creates a new async unbounded channel,
+/// and returns AsyncSender and AsyncReceiver of the channel for type T,
+pub fn unbounded_async<T>() -> (AsyncSender<T>, AsyncReceiver<T>) {
+    kanal::unbounded_async()
+}
+///
+/// Returns async Sender of the same channel, the sync one is consumed
+pub fn to_async_sender<T>(send: Sender<T>) -> AsyncSender<T> {
+    send.to_async()
+}
+///
+/// Returns async Receiver of the same channel, the sync one is consumed
+pub fn to_async_receiver<T>(recv: Receiver<T>) -> AsyncReceiver<T> {
+    recv.to_async()
+}
+///
+/// Returns sync Sender of the same channel, the async one is consumed
+pub fn to_sync_sender<T>(send: AsyncSender<T>) -> Sender<T> {
+    send.to_sync()
+}
+///
+/// Returns sync Receiver of the same channel, the async one is consumed
+pub fn to_sync_receiver<T>(recv: AsyncReceiver<T>) -> Receiver<T> {
+    recv.to_sync()
+}
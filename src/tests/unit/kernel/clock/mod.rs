@@ -0,0 +1 @@
+mod virtual_clock_test;
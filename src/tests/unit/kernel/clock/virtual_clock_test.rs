@@ -0,0 +1,116 @@
+#[cfg(test)]
+
+mod virtual_clock {
+    use std::{sync::{Arc, Once}, time::{Duration, Instant}};
+    use chrono::{TimeZone, Utc};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        kernel::clock::{Clock, GlobalClock, VirtualClock},
+        services::entity::PointHlr,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing time is changed only by `advance`, sleeping thread is woken up when the time is advanced enough
+    #[test]
+    fn manual() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("virtual_clock_manual");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let origin = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(origin));
+        let start = clock.now();
+        std::thread::sleep(Duration::from_millis(20));
+        let result = (clock.elapsed(start), clock.utc());
+        let target = (Duration::ZERO, origin);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        clock.advance(Duration::from_secs(3600));
+        let result = (clock.elapsed(start), clock.utc());
+        let target = (Duration::from_secs(3600), origin + chrono::Duration::hours(1));
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let sleeper = {
+            let clock = clock.clone();
+            std::thread::spawn(move || clock.sleep(Duration::from_secs(1)))
+        };
+        let result = clock.wait_sleeping(1, Duration::from_secs(3));
+        assert!(result, "\nresult: {:?}\ntarget: {:?}", result, true);
+        clock.advance(Duration::from_millis(500));
+        std::thread::sleep(Duration::from_millis(50));
+        let result = sleeper.is_finished();
+        assert!(!result, "\nresult: {:?}\ntarget: {:?}", result, false);
+        clock.advance(Duration::from_millis(500));
+        sleeper.join().unwrap();
+        let result = clock.sleeping();
+        assert!(result == 0, "\nresult: {:?}\ntarget: {:?}", result, 0);
+        test_duration.exit();
+    }
+    ///
+    /// Testing `sleep` of the auto clock advances the time and returns immediately
+    #[test]
+    fn auto() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("virtual_clock_auto");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let origin = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let clock = VirtualClock::auto(origin);
+        let time = Instant::now();
+        for _ in 0..24 {
+            clock.sleep(Duration::from_secs(3600));
+        }
+        let result = clock.utc();
+        let target = origin + chrono::Duration::days(1);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        assert!(time.elapsed() < Duration::from_secs(1), "\nresult: {:?}\ntarget: < 1 s", time.elapsed());
+        test_duration.exit();
+    }
+    ///
+    /// Testing point constructors take the timestamp from the clock installed for the thread
+    #[test]
+    fn global() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("virtual_clock_global");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let origin = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(origin));
+        let guard = GlobalClock::local(clock.clone());
+        clock.advance(Duration::from_secs(90));
+        let target = origin + chrono::Duration::seconds(90);
+        let result = PointHlr::new_int(0, "/App/Drive/Speed", 10).timestamp;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = GlobalClock::utc();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        // Another threads are not affected
+        let result = std::thread::spawn(|| PointHlr::new_int(0, "/App/Drive/Speed", 10).timestamp).join().unwrap();
+        assert!(result > target, "\nresult: {:?}\ntarget: > {:?}", result, target);
+        drop(guard);
+        let result = PointHlr::new_int(0, "/App/Drive/Speed", 10).timestamp;
+        assert!(result > target, "\nresult: {:?}\ntarget: > {:?}", result, target);
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,137 @@
+#[cfg(test)]
+
+mod metrics {
+    use std::{sync::{atomic::{AtomicUsize, Ordering}, Arc, Once}, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::kernel::metrics::{Histogram, Metrics};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing counters and gauges are shared by the name and labels, and rendered in the Prometheus text format
+    #[test]
+    fn counter_gauge() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("metrics_counter_gauge");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let metrics = Metrics::new();
+        let counter = metrics.counter("test_points_total", "Points received", &[("service", "/App/Task"), ("kind", "in")]);
+        counter.inc();
+        counter.add(2);
+        let same = metrics.counter("test_points_total", "Points received", &[("kind", "in"), ("service", "/App/Task")]);
+        same.inc();
+        let result = counter.get();
+        let target = 4;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let gauge = metrics.gauge("test_depth", "Queue depth", &[]);
+        gauge.set(5.0);
+        gauge.dec();
+        let result = gauge.get();
+        let target = 4.0;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let mismatch = metrics.gauge("test_points_total", "Points received", &[]);
+        mismatch.set(10.0);
+        let result = metrics.render();
+        let target = [
+            "# HELP test_depth Queue depth",
+            "# TYPE test_depth gauge",
+            "test_depth 4",
+            "# HELP test_points_total Points received",
+            "# TYPE test_points_total counter",
+            "test_points_total{kind=\"in\",service=\"/App/Task\"} 4",
+            "",
+        ].join("\n");
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = metrics.counter("test_escape", "Escaped", &[("path", "a\"b\\c\nd")]);
+        result.inc();
+        let result = metrics.render().contains("test_escape{path=\"a\\\"b\\\\c\\nd\"} 1");
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing histogram buckets are cumulative and rendered with `+Inf`, `_sum` and `_count`
+    #[test]
+    fn histogram() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("metrics_histogram");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let metrics = Metrics::new();
+        let histogram = metrics.histogram("test_exec_seconds", "Execution time", &[("cycle", "Task")], &[0.25, 1.0]);
+        histogram.observe(0.25);
+        histogram.observe(0.5);
+        histogram.observe(2.0);
+        histogram.observe_duration(Duration::from_millis(125));
+        let result = histogram.buckets();
+        let target = vec![(0.25, 2), (1.0, 3)];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = histogram.count();
+        let target = 4;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = metrics.render();
+        let target = [
+            "# HELP test_exec_seconds Execution time",
+            "# TYPE test_exec_seconds histogram",
+            "test_exec_seconds_bucket{cycle=\"Task\",le=\"0.25\"} 2",
+            "test_exec_seconds_bucket{cycle=\"Task\",le=\"1\"} 3",
+            "test_exec_seconds_bucket{cycle=\"Task\",le=\"+Inf\"} 4",
+            "test_exec_seconds_sum{cycle=\"Task\"} 2.875",
+            "test_exec_seconds_count{cycle=\"Task\"} 4",
+            "",
+        ].join("\n");
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = Histogram::new(&[]).buckets();
+        let target = vec![];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing calculated gauge is removed when its source is dropped
+    #[test]
+    fn gauge_fn() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("metrics_gauge_fn");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let metrics = Metrics::new();
+        let size = Arc::new(AtomicUsize::new(3));
+        let size_ref = Arc::downgrade(&size);
+        metrics.gauge_fn("test_workers", "Workers", &[("pool", "Pool")], move || {
+            size_ref.upgrade().map(|size| size.load(Ordering::SeqCst) as f64)
+        });
+        size.store(5, Ordering::SeqCst);
+        let result = metrics.render().contains("test_workers{pool=\"Pool\"} 5\n");
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        drop(size);
+        metrics.render();
+        let result = metrics.render();
+        let target = "";
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+}
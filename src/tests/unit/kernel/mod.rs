@@ -1 +1,4 @@
-mod state;
\ No newline at end of file
+mod clock;
+mod metrics;
+mod realtime;
+mod state;
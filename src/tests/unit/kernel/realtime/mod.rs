@@ -0,0 +1 @@
+mod real_time_test;
@@ -0,0 +1,45 @@
+#[cfg(test)]
+
+mod real_time {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::kernel::realtime::RealTime;
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing invalid priority and CPU are rejected, permitted requests are applied to the calling thread only
+    #[test]
+    fn invalid() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("real_time_invalid");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        for priority in [-1, 0, 100] {
+            let result = RealTime::fifo(priority);
+            assert!(result.is_err(), "priority {} \nresult: {:?}\ntarget: Err", priority, result);
+        }
+        let result = RealTime::pin(usize::MAX);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        // Depends on the permissions of the test environment, the thread is finished right after the call
+        let result = std::thread::spawn(|| RealTime::fifo(1)).join().unwrap();
+        log::debug!("{}.invalid | SCHED_FIFO(1): {:?}", dbg, result);
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,179 @@
+#[cfg(test)]
+
+mod cache_service {
+    use std::{str::FromStr, sync::{Arc, Once}, time::{Duration, Instant}};
+    use chrono::Utc;
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        conf::{ConfTree, ServicesConf},
+        entity::{Cot, Point, PointHlr, Status},
+        CacheConf, CacheService, LinkName, MultiQueue, MultiQueueConf, Service, Services, SubscriptionCriteria,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns [CacheConf] parsed from the yaml containing single service node
+    fn conf(yaml: &str) -> CacheConf {
+        let conf = ConfTree::new_root(serde_yaml::from_str(yaml).unwrap()).next().unwrap();
+        CacheConf::new("App", conf).unwrap()
+    }
+    ///
+    /// Returns [Services] with running [MultiQueue]
+    fn services(dbg: &Dbg, conf: ConfTree) -> (Arc<Services>, Arc<MultiQueue>) {
+        let services = Arc::new(Services::new("App", ServicesConf::new(dbg, conf), None));
+        let multi_queue = Arc::new(MultiQueue::new(
+            MultiQueueConf::from_yaml("App", &serde_yaml::from_str(r#"
+                service MultiQueue:
+                    in queue in-queue:
+                        max-length: 10000
+            "#).unwrap()),
+            services.clone(),
+            None,
+        ));
+        services.insert(multi_queue.clone());
+        multi_queue.run().unwrap();
+        (services, multi_queue)
+    }
+    ///
+    /// Returns Int point
+    fn int(name: &str, value: i64, status: Status, cot: Cot) -> Point {
+        Point::Int(PointHlr::new(0, name, value, status, cot, Utc::now()))
+    }
+    ///
+    /// Returns (name, value, status, cot) of the points
+    fn values(points: &[Point]) -> Vec<(String, i64, Status, Cot)> {
+        points.iter().map(|point| (point.name(), point.as_int().value, point.status(), point.cot())).collect()
+    }
+    ///
+    /// Waits until `cond` is true
+    fn wait(timeout: Duration, cond: impl Fn() -> bool) -> bool {
+        let time = Instant::now();
+        while time.elapsed() < timeout {
+            if cond() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        cond()
+    }
+    ///
+    /// Testing points received from the `MultiQueue` are cached and returned by `gi` and queries
+    #[test]
+    fn gi() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("cache_service_gi");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let (services, multi_queue) = services(&dbg, ConfTree::empty());
+        let cache = Arc::new(CacheService::new(conf(r#"
+            service CacheService Cache:
+                subscribe: MultiQueue
+        "#), services.clone(), None));
+        services.insert(cache.clone());
+        let result = cache.subscribed_to();
+        let target = vec!["/App/MultiQueue".to_owned()];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        cache.run().unwrap();
+        let send = services.get_link(&LinkName::from_str("/App/MultiQueue.in-queue").unwrap()).unwrap();
+        let test_data = [
+            int("/App/Drive/Speed", 1, Status::Ok, Cot::Inf),
+            int("/App/Drive/State", 3, Status::Invalid, Cot::Inf),
+            int("/App/Drive/Speed", 10, Status::Ok, Cot::Act),
+            int("/App/Drive/Speed", 2, Status::Ok, Cot::Inf),
+        ];
+        for point in test_data {
+            send.send(point).unwrap();
+        }
+        assert!(
+            wait(Duration::from_secs(3), || cache.get("/App/Drive/Speed", Cot::Inf).map(|point| point.as_int().value) == Some(2)),
+            "\nresult: {:?}\ntarget: {:?}", cache.get("/App/Drive/Speed", Cot::Inf), 2,
+        );
+        let result = values(&cache.try_gi("/App/Receiver", &[]).unwrap().wait().unwrap());
+        let target = vec![
+            ("/App/Drive/Speed".to_owned(), 10, Status::Ok, Cot::Act),
+            ("/App/Drive/Speed".to_owned(), 2, Status::Ok, Cot::Inf),
+            ("/App/Drive/State".to_owned(), 3, Status::Invalid, Cot::Inf),
+        ];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = values(&cache.gi("/App/Receiver", &[SubscriptionCriteria::new("/App/Drive/Speed", Cot::Inf)]).wait().unwrap());
+        let target = vec![("/App/Drive/Speed".to_owned(), 2, Status::Ok, Cot::Inf)];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = values(&cache.by_name("/App/Drive/State"));
+        let target = vec![("/App/Drive/State".to_owned(), 3, Status::Invalid, Cot::Inf)];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = cache.by_prefix("/App/Drive/").len();
+        assert!(result == 3, "\nresult: {:?}\ntarget: {:?}", result, 3);
+        let result = cache.by_status(Status::Invalid).len();
+        assert!(result == 1, "\nresult: {:?}\ntarget: {:?}", result, 1);
+        cache.exit();
+        multi_queue.exit();
+        cache.wait().unwrap();
+        multi_queue.wait().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing cached points survive the restart if `retain` enabled
+    #[test]
+    fn retain() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("cache_service_retain");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = std::env::temp_dir().join("sal-sync-cache-service-retain");
+        let _ = std::fs::remove_dir_all(&dir);
+        let services_conf = || ConfTree::new_root(serde_yaml::from_str(&format!("retain:\n    path: {}", dir.display())).unwrap());
+        let cache_conf = r#"
+            service CacheService Cache:
+                subscribe: MultiQueue
+                retain: true
+                retain-cycle: 50 ms
+        "#;
+        let (services, multi_queue) = services(&dbg, services_conf());
+        let cache = Arc::new(CacheService::new(conf(cache_conf), services.clone(), None));
+        services.insert(cache.clone());
+        cache.run().unwrap();
+        let send = services.get_link(&LinkName::from_str("/App/MultiQueue.in-queue").unwrap()).unwrap();
+        send.send(int("/App/Drive/Speed", 5, Status::Ok, Cot::Inf)).unwrap();
+        send.send(int("/App/Drive/State", 7, Status::Obsolete, Cot::Inf)).unwrap();
+        assert!(wait(Duration::from_secs(3), || cache.len() == 2), "\nresult: {:?}\ntarget: {:?}", cache.len(), 2);
+        let path = dir.join("cache").join("App.Cache.json");
+        assert!(wait(Duration::from_secs(3), || path.exists()), "\nresult: {:?}\ntarget: exists", path);
+        cache.exit();
+        multi_queue.exit();
+        cache.wait().unwrap();
+        multi_queue.wait().unwrap();
+        // Restarted with the same config, retained points are available before any point received
+        let (services, multi_queue) = self::services(&dbg, services_conf());
+        let cache = CacheService::new(conf(cache_conf), services.clone(), None);
+        let result = values(&cache.gi("/App/Receiver", &[]).wait().unwrap());
+        let target = vec![
+            ("/App/Drive/Speed".to_owned(), 5, Status::Ok, Cot::Inf),
+            ("/App/Drive/State".to_owned(), 7, Status::Obsolete, Cot::Inf),
+        ];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        multi_queue.exit();
+        multi_queue.wait().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        test_duration.exit();
+    }
+}
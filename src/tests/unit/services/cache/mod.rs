@@ -0,0 +1,2 @@
+mod cache_service_test;
+mod point_cache_test;
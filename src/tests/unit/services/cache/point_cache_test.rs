@@ -0,0 +1,138 @@
+#[cfg(test)]
+
+mod point_cache {
+    use std::{sync::Once, time::Duration};
+    use chrono::Utc;
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        entity::{Cot, Point, PointHlr, Status},
+        PointCache, SubscriptionCriteria,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns Int point
+    fn int(name: &str, value: i64, status: Status, cot: Cot) -> Point {
+        Point::Int(PointHlr::new(0, name, value, status, cot, Utc::now()))
+    }
+    ///
+    /// Returns (name, value, status, cot) of the points
+    fn values(points: &[Point]) -> Vec<(String, i64, Status, Cot)> {
+        points.iter().map(|point| (point.name(), point.as_int().value, point.status(), point.cot())).collect()
+    }
+    ///
+    /// Returns [PointCache] filled with the test points
+    fn cache(dbg: &Dbg) -> PointCache {
+        let cache = PointCache::new(dbg);
+        let test_data = [
+            int("/App/Drive/Speed", 1, Status::Ok, Cot::Inf),
+            int("/App/Drive/Speed", 2, Status::Ok, Cot::Inf),
+            int("/App/Drive/Speed", 10, Status::Ok, Cot::Act),
+            int("/App/Drive/State", 3, Status::Invalid, Cot::Inf),
+            int("/App/Pump/State", 4, Status::Ok, Cot::Inf),
+            int("/App/Pump/State", 5, Status::Obsolete, Cot::Inf),
+        ];
+        for point in test_data {
+            cache.insert(point);
+        }
+        cache
+    }
+    ///
+    /// Testing the latest point per name and cot is kept and queried
+    #[test]
+    fn query() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("point_cache_query");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let cache = cache(&dbg);
+        let result = cache.len();
+        let target = 4;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = cache.get("/App/Drive/Speed", Cot::Inf).map(|point| point.as_int().value);
+        let target = Some(2);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = cache.get("/App/Drive/Speed", Cot::Req);
+        assert!(result.is_none(), "\nresult: {:?}\ntarget: None", result);
+        let test_data = [
+            (00, values(&cache.name("/App/Drive/Speed")), vec![
+                ("/App/Drive/Speed".to_owned(), 10, Status::Ok, Cot::Act),
+                ("/App/Drive/Speed".to_owned(), 2, Status::Ok, Cot::Inf),
+            ]),
+            (01, values(&cache.name("/App/Drive")), vec![]),
+            (02, values(&cache.prefix("/App/Drive/")), vec![
+                ("/App/Drive/Speed".to_owned(), 10, Status::Ok, Cot::Act),
+                ("/App/Drive/Speed".to_owned(), 2, Status::Ok, Cot::Inf),
+                ("/App/Drive/State".to_owned(), 3, Status::Invalid, Cot::Inf),
+            ]),
+            (03, values(&cache.status(Status::Ok)), vec![
+                ("/App/Drive/Speed".to_owned(), 10, Status::Ok, Cot::Act),
+                ("/App/Drive/Speed".to_owned(), 2, Status::Ok, Cot::Inf),
+            ]),
+            (04, values(&cache.status(Status::Obsolete)), vec![
+                ("/App/Pump/State".to_owned(), 5, Status::Obsolete, Cot::Inf),
+            ]),
+            (05, values(&cache.gi(&[])), values(&cache.all())),
+            (06, values(&cache.gi(&[SubscriptionCriteria::new("/App/Drive/Speed", Cot::All)])), values(&cache.name("/App/Drive/Speed"))),
+            (07, values(&cache.gi(&[
+                SubscriptionCriteria::new("/App/Drive/Speed", Cot::Act),
+                SubscriptionCriteria::new("/App/Pump/State", Cot::Inf),
+                SubscriptionCriteria::new("/App/Pump/State", Cot::Act),
+            ])), vec![
+                ("/App/Drive/Speed".to_owned(), 10, Status::Ok, Cot::Act),
+                ("/App/Pump/State".to_owned(), 5, Status::Obsolete, Cot::Inf),
+            ]),
+        ];
+        for (step, result, target) in test_data {
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing stored points are loaded back
+    #[test]
+    fn store_load() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("point_cache_store_load");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let path = std::env::temp_dir().join("sal-sync-point-cache").join("store_load.json");
+        let _ = std::fs::remove_file(&path);
+        let loaded = PointCache::new(&dbg);
+        let result = loaded.load(&path).unwrap();
+        assert!(result == 0, "\nresult: {:?}\ntarget: {:?}", result, 0);
+        let cache = cache(&dbg);
+        cache.store(&path).unwrap();
+        let result = loaded.load(&path).unwrap();
+        let target = cache.len();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = values(&loaded.all());
+        let target = values(&cache.all());
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        std::fs::write(&path, "not a json").unwrap();
+        let result = loaded.load(&path);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        std::fs::remove_file(&path).unwrap();
+        test_duration.exit();
+    }
+}
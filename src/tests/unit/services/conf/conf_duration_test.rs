@@ -1,5 +1,5 @@
 #[cfg(test)]
-use std::{sync::Once, str::FromStr};
+use std::{sync::Once, str::FromStr, time::Duration};
 use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
 use crate::services::{conf::{ConfDuration, ConfDurationUnit}, task::functions::FnConfKeywd};
 ///
@@ -46,6 +46,35 @@ fn test_create_valid() {
         assert_eq!(conf_duration, target);
     }
 }
+///
+///
+#[test]
+fn test_to_duration() {
+    DebugSession::init(LogLevel::Info, Backtrace::Short);
+    init_once();
+    init_each();
+    println!("test_to_duration");
+    let test_data = vec![
+        ("111 ns"   , Duration::from_nanos(111)),
+        ("12  us"   , Duration::from_micros(12)),
+        ("10ms"     , Duration::from_millis(10)),
+        ("5   s"    , Duration::from_secs(5)),
+        ("3"        , Duration::from_secs(3)),
+        ("1 m"      , Duration::from_secs(60)),
+        ("7m"       , Duration::from_secs(420)),
+        ("1 h"      , Duration::from_secs(3600)),
+        ("9h"       , Duration::from_secs(32400)),
+        ("504 h"    , Duration::from_secs(1_814_400)),
+    ];
+    for (value, target) in test_data {
+        let result = ConfDuration::from_str(value).unwrap().to_duration();
+        log::debug!("value: {:?}   |   duration: {:?}   |   target: {:?}", value, result, target);
+        assert!(result == target, "value: {:?} \nresult: {:?}\ntarget: {:?}", value, result, target);
+    }
+    let result = ConfDuration::new(u64::MAX, ConfDurationUnit::Hours).to_duration();
+    let target = Duration::from_secs(u64::MAX);
+    assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+}
 
 #[test]
 fn test_create_invalid() {
@@ -0,0 +1,145 @@
+#[cfg(test)]
+
+mod conf_loader {
+    use std::{fs, path::{Path, PathBuf}, sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::conf::{ConfLoader, ConfTree};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Writes the `files` into the new directory `name`, returns the directory
+    fn files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join("sal-sync-conf-loader").join(name);
+        let _ = fs::remove_dir_all(&dir);
+        for (path, content) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+    ///
+    /// Returns the file name of the `path`
+    fn file_name(path: Option<&Path>) -> Option<String> {
+        path.and_then(|path| path.file_name()).map(|name| name.to_string_lossy().into_owned())
+    }
+    ///
+    /// Testing includes are resolved relative to the including file, variables are expanded, sources are recorded
+    #[test]
+    fn load() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_loader_load");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = files("load", &[
+            ("app.yaml", r#"
+                name: ${SAL_SYNC_TEST_UNSET_NAME:-App}
+                port: ${SAL_SYNC_TEST_UNSET_PORT:-8080}
+                address: ${HOST}:${SAL_SYNC_TEST_UNSET_PORT:-8080}
+                literal: $${HOST}
+                include: services/task.yaml
+                service MultiQueue:
+                    include:
+                        - queues/in.yaml
+            "#.replace("\n                ", "\n").as_str()),
+            ("services/task.yaml", "service Task ${TASK}:\n    cycle: ${CYCLE}\n    include: task-in.yaml\n"),
+            ("services/task-in.yaml", "in queue recv-queue:\n    max-length: 100\n"),
+            ("queues/in.yaml", "in queue in-queue:\n    max-length: 10000\n"),
+        ]);
+        let (result, sources) = ConfLoader::new(&dbg)
+            .with_vars([("HOST", "127.0.0.1"), ("TASK", "Task1"), ("CYCLE", "10 ms")])
+            .load(dir.join("app.yaml"))
+            .unwrap();
+        let target = ConfTree::new_root(serde_yaml::from_str(r#"
+            name: App
+            port: 8080
+            address: 127.0.0.1:8080
+            literal: ${HOST}
+            service Task Task1:
+                cycle: 10 ms
+                in queue recv-queue:
+                    max-length: 100
+            service MultiQueue:
+                in queue in-queue:
+                    max-length: 10000
+        "#).unwrap());
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let test_data = [
+            (01, "", Some("app.yaml")),
+            (02, "name", Some("app.yaml")),
+            (03, "service Task Task1", Some("task.yaml")),
+            (04, "service Task Task1/cycle", Some("task.yaml")),
+            (05, "service Task Task1/in queue recv-queue/max-length", Some("task-in.yaml")),
+            (06, "service MultiQueue", Some("app.yaml")),
+            (07, "service MultiQueue/in queue in-queue/max-length", Some("in.yaml")),
+            (08, "service MultiQueue/in queue in-queue/unknown/deep", Some("in.yaml")),
+        ];
+        for (step, path, target) in test_data {
+            let result = file_name(sources.file(path));
+            let target = target.map(|target| target.to_owned());
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = sources.files().len();
+        let target = 4;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing include cycles, duplicated keys, missing files and variables are errors
+    #[test]
+    fn errors() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_loader_errors");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = files("errors", &[
+            ("cycle-a.yaml", "a: 1\ninclude: cycle-b.yaml\n"),
+            ("cycle-b.yaml", "b: 1\ninclude: cycle-a.yaml\n"),
+            ("duplicated.yaml", "a: 1\ninclude: duplicated-inc.yaml\n"),
+            ("duplicated-inc.yaml", "a: 2\n"),
+            ("missing-file.yaml", "include: not-exists.yaml\n"),
+            ("missing-var.yaml", "a: ${SAL_SYNC_TEST_UNSET_VAR}\n"),
+            ("unclosed.yaml", "a: ${SAL_SYNC_TEST_UNSET_VAR\n"),
+            ("not-mapping.yaml", "include: list.yaml\n"),
+            ("list.yaml", "- 1\n- 2\n"),
+        ]);
+        let test_data = [
+            (01, "cycle-a.yaml", "cycle"),
+            (02, "duplicated.yaml", "Duplicated key 'a'"),
+            (03, "missing-file.yaml", "not-exists.yaml"),
+            (04, "missing-var.yaml", "SAL_SYNC_TEST_UNSET_VAR"),
+            (05, "unclosed.yaml", "unclosed"),
+            (06, "not-mapping.yaml", "must contain mapping"),
+        ];
+        for (step, file, target) in test_data {
+            let result = ConfLoader::new(&dbg).load(dir.join(file));
+            log::debug!("{}.errors | step {}: {:?}", dbg, step, result);
+            let result = match result {
+                Ok(_) => String::new(),
+                Err(err) => format!("{:?}", err),
+            };
+            assert!(result.contains(target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+}
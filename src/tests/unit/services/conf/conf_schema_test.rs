@@ -0,0 +1,214 @@
+#[cfg(test)]
+
+mod conf_schema {
+    use std::{fs, path::PathBuf, sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::conf::{ConfLoader, ConfSchema, ConfTree};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Writes the `files` into the new directory `name`, returns the directory
+    fn files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join("sal-sync-conf-schema").join(name);
+        let _ = fs::remove_dir_all(&dir);
+        for (path, content) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+    ///
+    /// Testing valid configurations have no violations
+    #[test]
+    fn valid() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_schema_valid");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (1, r#"
+                name: App
+                retain:
+                    path: assets/retain/
+                    point:
+                        path: point/id.json
+                service MultiQueue:
+                    in queue in-queue:
+                        max-length: 10000
+                    send-to: [Task1.in-queue]
+                service Task Task1:
+                    cycle: 10 ms
+                    in queue recv-queue:
+                        max-length: 100
+                    subscribe: MultiQueue
+                    fn Add:
+                        input1: const 1
+                service TcpClient Client:
+                    address: 127.0.0.1:8080
+                    send-to: MultiQueue.in-queue
+                    diagnosis:
+                        point Connection:
+                            type: 'Int'
+                            history: rw
+                            alarm: 5
+                            address:
+                                offset: 0
+                                bit: 1
+                            filters:
+                                threshold: 0.5
+                            comment: Connection status
+            "#),
+            (2, ""),
+        ];
+        for (step, conf) in test_data {
+            let result = ConfSchema::app().validate_str(conf);
+            let target: Vec<String> = vec![];
+            let result: Vec<String> = result.iter().map(|violation| violation.to_string()).collect();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the service specific keys of the points and the aliases are allowed
+    #[test]
+    fn producer() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_schema_producer");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf = r#"
+            service MultiQueue:
+                in queue in-queue:
+                    max-length: 10000
+            service ProducerService Producer:
+                cycle: 10 ms
+                send-to: /App/MultiQueue.in-queue
+                point Drive.Speed:
+                    type: 'Double'
+                    history: r
+                    generator: ramp
+                    to: 100
+                point Drive.State:
+                    Type: 'Int'
+                    generator: const
+                    value: 1
+                point Drive.Cmd:
+                    type: 'Int'
+                    history: w
+                    cot: Act
+                    status: Ok
+                    generator: const
+                    value: 5
+            service HistoryService History:
+                subscribe:
+                    /App/MultiQueue:
+                        {cot: Inf, history: r}: []
+                        {cot: Act, history: w}: []
+                path: assets/history/
+                segment: 1 m
+                flush: 50 ms
+        "#;
+        let result: Vec<String> = ConfSchema::app().validate_str(conf).iter().map(|violation| violation.to_string()).collect();
+        let target: Vec<String> = vec![];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result: Vec<String> = ConfSchema::point().validate_str("Type: Integer").iter().map(|violation| violation.to_string()).collect();
+        let target = 1;
+        assert!(result.len() == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing every violation is returned with its key path, line and column
+    #[test]
+    fn violations() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_schema_violations");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf = [
+            "name: App",
+            "service Task Task1:",
+            "    cycle: 10 mss",
+            "    in queue recv-queue:",
+            "        max-len: 100",
+            "    point Drive.Speed:",
+            "        type: Integer",
+            "        alarm: 20",
+            "retain:",
+            "    path: 42",
+            "1: one",
+        ].join("\n");
+        let result: Vec<(String, Option<usize>, Option<usize>)> = ConfSchema::app().validate_str(&conf).into_iter()
+            .map(|violation| {
+                log::debug!("{}", violation);
+                (violation.path, violation.line, violation.column)
+            })
+            .collect();
+        let target = vec![
+            ("service Task Task1/cycle".to_owned(), Some(3), Some(5)),
+            ("service Task Task1/in queue recv-queue/max-len".to_owned(), Some(5), Some(9)),
+            ("service Task Task1/in queue recv-queue".to_owned(), Some(4), Some(5)),
+            ("service Task Task1/point Drive.Speed/type".to_owned(), Some(7), Some(9)),
+            ("service Task Task1/point Drive.Speed/alarm".to_owned(), Some(8), Some(9)),
+            ("retain/path".to_owned(), Some(10), Some(5)),
+            ("1".to_owned(), Some(11), Some(1)),
+        ];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = ConfSchema::queue().validate(&ConfTree::new("in queue recv-queue", serde_yaml::from_str("max-length: ten").unwrap()));
+        let result: Vec<String> = result.iter().map(|violation| violation.to_string()).collect();
+        let target = vec!["'in queue recv-queue/max-length' - Int expected, but found: 'ten'".to_owned()];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = ConfSchema::app().validate_str("service Task Task1:\n    cycle: [10 ms\n");
+        assert!(result.len() == 1, "\nresult: {:?}\ntarget: {:?}", result.len(), 1);
+        assert!(result[0].line.is_some(), "\nresult: {:?}\ntarget: {:?}", result[0].line, "Some(line)");
+        test_duration.exit();
+    }
+    ///
+    /// Testing violations of the loaded configuration are located in the source files
+    #[test]
+    fn sources() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_schema_sources");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = files("sources", &[
+            ("app.yaml", "name: App\ninclude: services/task.yaml\n"),
+            ("services/task.yaml", "service Task Task1:\n    cycle: 10 ms\n    in queue recv-queue:\n        max-length: ten\n"),
+        ]);
+        let (conf, sources) = ConfLoader::new(&dbg).load(dir.join("app.yaml")).unwrap();
+        let result = ConfSchema::app().validate_sources(&conf, &sources);
+        let result: Vec<String> = result.iter().map(|violation| violation.to_string()).collect();
+        let file = fs::canonicalize(dir.join("services/task.yaml")).unwrap();
+        let target = vec![
+            format!("{}:4:9: 'service Task Task1/in queue recv-queue/max-length' - Int expected, but found: 'ten'", file.display()),
+        ];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+}
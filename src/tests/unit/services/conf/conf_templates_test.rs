@@ -0,0 +1,187 @@
+#[cfg(test)]
+
+mod conf_templates {
+    use std::{fs, sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        conf::{ConfLoader, ConfTemplates, ConfTree},
+        entity::{PointConfig, PointConfigHistory},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the root [ConfTree] parsed from the yaml `text`
+    fn conf(text: &str) -> ConfTree {
+        ConfTree::new_root(serde_yaml::from_str(text).unwrap())
+    }
+    ///
+    /// Testing templates are instantiated with parameters, defaults and ranges
+    #[test]
+    fn expand() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_templates_expand");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let result = ConfTemplates::new(&dbg).expand(&conf(r#"
+            template Drive:
+                params:
+                    offset: ~
+                    history: r
+                point $(name).Speed:
+                    type: Real
+                    history: $(history)
+                    address:
+                        offset: $(offset)
+                point $(name).Torque:
+                    type: Real
+                    address:
+                        offset: $(offset + 4)
+            service ProfinetClient Ied01:
+                cycle: 10 ms
+                use template Drive as Drive1: {offset: 0}
+                use template Drive as Drive$(i): {i: 2..=3, offset: $(i * 8), history: rw}
+        "#)).unwrap();
+        let target = conf(r#"
+            service ProfinetClient Ied01:
+                cycle: 10 ms
+                point Drive1.Speed: {type: Real, history: r, address: {offset: 0}}
+                point Drive1.Torque: {type: Real, address: {offset: 4}}
+                point Drive2.Speed: {type: Real, history: rw, address: {offset: 16}}
+                point Drive2.Torque: {type: Real, address: {offset: 20}}
+                point Drive3.Speed: {type: Real, history: rw, address: {offset: 24}}
+                point Drive3.Torque: {type: Real, address: {offset: 28}}
+        "#);
+        assert!(result.conf == target.conf, "\nresult: {:#?}\ntarget: {:#?}", result.conf, target.conf);
+        let service = result.at("service ProfinetClient Ied01").unwrap();
+        let result_keys: Vec<String> = service.sub_nodes().unwrap().map(|node| node.key).collect();
+        let target_keys: Vec<String> = target.at("service ProfinetClient Ied01").unwrap().sub_nodes().unwrap().map(|node| node.key).collect();
+        assert!(result_keys == target_keys, "\nresult: {:?}\ntarget: {:?}", result_keys, target_keys);
+        let point = PointConfig::new("/App/Ied01", &service.at("point Drive2.Speed").unwrap());
+        let target = ("/App/Ied01/Drive2.Speed".to_owned(), Some(16), PointConfigHistory::ReadWrite);
+        let result = (point.name.clone(), point.address.as_ref().and_then(|address| address.offset), point.history.clone());
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing templates using other templates
+    #[test]
+    fn nested() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_templates_nested");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let result = ConfTemplates::new(&dbg).expand(&conf(r#"
+            template Valve:
+                point $(name).Open:
+                    type: Bool
+                    address:
+                        bit: $(bit)
+            service Task Station:
+                template Station:
+                    point $(name).Pressure:
+                        type: Real
+                    use template Valve as $(name).Valve$(i): {i: 0..2, bit: $(i)}
+                use template Station as Station1:
+        "#)).unwrap();
+        let target = conf(r#"
+            service Task Station:
+                point Station1.Pressure: {type: Real}
+                point Station1.Valve0.Open: {type: Bool, address: {bit: 0}}
+                point Station1.Valve1.Open: {type: Bool, address: {bit: 1}}
+        "#);
+        assert!(result.conf == target.conf, "\nresult: {:#?}\ntarget: {:#?}", result.conf, target.conf);
+        test_duration.exit();
+    }
+    ///
+    /// Testing invalid templates are reported
+    #[test]
+    fn errors() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_templates_errors");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let drive = "template Drive:\n    params:\n        offset: ~\n    point $(name).Speed:\n        type: Real\n        address: {offset: $(offset)}\n";
+        let test_data = [
+            (01, "use template Valve: {}\n".to_owned(), "template 'Valve' is not defined"),
+            (02, format!("{}use template Drive as Drive1:\n", drive), "parameter 'offset' is required for 'Drive1'"),
+            (03, format!("{}use template Drive as Drive1: {{offset: 0, bits: 16}}\n", drive), "unknown parameter 'bits' for 'Drive1'"),
+            (04, "template A:\n    use template B:\ntemplate B:\n    use template A:\nuse template A:\n".to_owned(), "template cycle: A -> B -> A"),
+            (05, format!("{}use template Drive as Drive1: {{offset: 0}}\nuse template Drive as Drive$(i): {{i: 1..2, offset: 0}}\n", drive), "Duplicated key 'point Drive1.Speed'"),
+            (06, format!("{}use template Drive as Drive1: {{offset: $(1 +)}}\n", drive), "incomplete expression"),
+            (07, "use template:\n".to_owned(), "expected, but found: 'use template'"),
+            (08, format!("{}{}", drive, drive.replace("template Drive:", "template  Drive:")), "template 'Drive' already defined"),
+            (09, format!("{}use template Drive as Drive1: {{offset: $(9223372036854775807 * 2)}}\n", drive), "integer overflow"),
+            (10, format!("{}use template Drive as Drive1: {{offset: $(9223372036854775807 + 1)}}\n", drive), "integer overflow"),
+            (11, format!("{}use template Drive as Drive1: {{offset: $(0 - 9223372036854775807 - 2)}}\n", drive), "integer overflow"),
+            (12, format!("{}use template Drive as Drive$(i): {{i: 0..100000, offset: 0}}\n", drive), "range '0..100000' exceeds 10000 values"),
+            (13, format!("{}use template Drive as Drive$(i)_$(j): {{i: 0..200, j: 0..=100, offset: 0}}\n", drive), "number of the instances exceeds 10000"),
+            (14, concat!(
+                "template Inner:\n    point $(name).Value:\n        type: Int\n",
+                "template Outer:\n    use template Inner as $(name).Inner$(i): {i: 0..200}\n",
+                "use template Outer as Outer$(j): {j: 0..100}\n",
+            ).to_owned(), "total number of the instances exceeds 10000"),
+            (15, format!("{}use template Drive as Drive$(i): {{i: 5..1, offset: 0}}\n", drive), "range '5..1' has no values"),
+            (16, format!("{}use template Drive as Drive$(i): {{i: 3..3, offset: 0}}\n", drive), "range '3..3' has no values"),
+        ];
+        for (step, text, target) in test_data {
+            let result = ConfTemplates::new(&dbg).expand(&conf(&text));
+            log::debug!("{}.errors | step {}: {:?}", dbg, step, result);
+            let result = match result {
+                Ok(_) => String::new(),
+                Err(err) => format!("{:?}", err),
+            };
+            assert!(result.contains(target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the loader expands templates, instances are located at the `use`
+    #[test]
+    fn loader() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_templates_loader");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = std::env::temp_dir().join("sal-sync-conf-templates").join("loader");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("templates.yaml"), "template Valve:\n    point $(name).Open:\n        type: Bool\n").unwrap();
+        fs::write(dir.join("app.yaml"), "include: templates.yaml\nservice Task Station:\n    use template Valve as Valve1:\n").unwrap();
+        let (result, sources) = ConfLoader::new(&dbg).load(dir.join("app.yaml")).unwrap();
+        let target = conf("service Task Station:\n    point Valve1.Open:\n        type: Bool\n");
+        assert!(result.conf == target.conf, "\nresult: {:#?}\ntarget: {:#?}", result.conf, target.conf);
+        let result = (
+            sources.file("service Task Station/point Valve1.Open").and_then(|file| file.file_name()).map(|name| name.to_string_lossy().into_owned()),
+            sources.position("service Task Station/point Valve1.Open"),
+        );
+        let target = (Some("app.yaml".to_owned()), Some((3, 5)));
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(test)]
+
+mod conf_tree_path {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::conf::{ConfPath, ConfPositions, ConfSources, ConfTree};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the config used by the tests
+    fn conf() -> ConfTree {
+        ConfTree::new_root(serde_yaml::from_str(r#"
+            name: App
+            service MultiQueue:
+                in queue in-queue:
+                    max-length: 10000
+            service Task Task1:
+                cycle: 10 ms
+                in queue recv-queue:
+                    max-length: 100
+                fn ToMultiQueue:
+                    in1 point Drive.Speed:
+                        type: 'Real'
+                    in2 point Drive.Torque:
+                        type: 'Real'
+            service Task Task2:
+                cycle: 1 s
+                in queue recv-queue:
+                    max-length: 200
+                fn ToMultiQueue:
+                    in1 point Pump.Pressure:
+                        type: 'Real'
+        "#).unwrap())
+    }
+    ///
+    /// Testing `at`, `get_at` and `duration_at` by the exact and the pattern paths
+    #[test]
+    fn at() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_tree_path_at");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf = conf();
+        let test_data: [(i32, &str, Option<i64>); 6] = [
+            (01, "service Task Task1/in queue recv-queue/max-length", Some(100)),
+            (02, "service  Task   Task2 / in queue recv-queue / max-length", Some(200)),
+            (03, "/service MultiQueue/in queue */max-length/", Some(10000)),
+            (04, "service Task Task1/in queue in-queue/max-length", None),
+            (05, "service Task Task1/cycle", None),
+            (06, "service Task Task3/in queue recv-queue/max-length", None),
+        ];
+        for (step, path, target) in test_data {
+            let result: Option<i64> = conf.get_at(path);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result: Option<String> = conf.get_at("service Task Task1/cycle");
+        let target = Some("10 ms".to_owned());
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = conf.duration_at("service Task Task2/cycle").unwrap();
+        let target = Duration::from_secs(1);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = conf.duration_at("service Task Task3/cycle").is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = conf.at("service Task Task1").map(|node| node.key);
+        let target = Some("service Task Task1".to_owned());
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = conf.at("").map(|node| node.conf == conf.conf);
+        let target = Some(true);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing `select` and `find_all` return the full key paths
+    #[test]
+    fn select() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_tree_path_select");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf = conf();
+        let test_data: [(i32, &str, Vec<&str>); 5] = [
+            (01, "service Task *", vec!["service Task Task1", "service Task Task2"]),
+            (02, "service *", vec!["service MultiQueue"]),
+            (03, "*/in queue *", vec!["service MultiQueue/in queue in-queue", "service Task Task1/in queue recv-queue", "service Task Task2/in queue recv-queue"]),
+            (04, "service Task */fn */* point Drive.*", vec!["service Task Task1/fn ToMultiQueue/in1 point Drive.Speed", "service Task Task1/fn ToMultiQueue/in2 point Drive.Torque"]),
+            (05, "service Unknown *", vec![]),
+        ];
+        for (step, path, target) in test_data {
+            let result: Vec<String> = conf.select(path).into_iter().map(|(path, _)| path).collect();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let test_data: [(i32, &str, Vec<&str>); 3] = [
+            (01, "* point *", vec![
+                "service Task Task1/fn ToMultiQueue/in1 point Drive.Speed",
+                "service Task Task1/fn ToMultiQueue/in2 point Drive.Torque",
+                "service Task Task2/fn ToMultiQueue/in1 point Pump.Pressure",
+            ]),
+            (02, "max-length", vec![
+                "service MultiQueue/in queue in-queue/max-length",
+                "service Task Task1/in queue recv-queue/max-length",
+                "service Task Task2/in queue recv-queue/max-length",
+            ]),
+            (03, "type", vec![
+                "service Task Task1/fn ToMultiQueue/in1 point Drive.Speed/type",
+                "service Task Task1/fn ToMultiQueue/in2 point Drive.Torque/type",
+                "service Task Task2/fn ToMultiQueue/in1 point Pump.Pressure/type",
+            ]),
+        ];
+        for (step, key, target) in test_data {
+            let result: Vec<String> = conf.find_all(key).into_iter().map(|(path, _)| path).collect();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = ConfPath::new("/service Task *//in queue */").len();
+        let target = 2;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing keys containing `/` are escaped in the paths of the [ConfTree], [ConfSources] and [ConfPositions]
+    #[test]
+    fn escaped() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("conf_tree_path_escaped");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let text = r#"
+service TcpServer Server:
+    subscribe:
+        /App/MultiQueue:
+            Inf: []
+        Dir\Queue:
+            Act: []
+"#;
+        let conf = ConfTree::new_root(serde_yaml::from_str(text).unwrap());
+        let test_data: [(i32, &str, Vec<&str>); 2] = [
+            (01, "service TcpServer */subscribe/*", vec![r"service TcpServer Server/subscribe/\/App\/MultiQueue", r"service TcpServer Server/subscribe/Dir\\Queue"]),
+            (02, r"*/subscribe/\/App\/MultiQueue/*", vec![r"service TcpServer Server/subscribe/\/App\/MultiQueue/Inf"]),
+        ];
+        for (step, path, target) in test_data {
+            let result: Vec<String> = conf.select(path).into_iter().map(|(path, _)| path).collect();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = conf.at(r"service TcpServer Server/subscribe/\/App\/MultiQueue/Inf").is_some();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let test_data = [
+            (01, "/App/MultiQueue", r"\/App\/MultiQueue"),
+            (02, r"Dir\Queue", r"Dir\\Queue"),
+            (03, "MultiQueue", "MultiQueue"),
+        ];
+        for (step, key, target) in test_data {
+            let result = ConfPath::escape(key);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = ConfPath::split(&ConfPath::join("subscribe", key));
+            let target = vec!["subscribe".to_owned(), key.to_owned()];
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let test_data = [
+            (01, r"subscribe/\/App\/MultiQueue/", r"subscribe/\/App\/MultiQueue", Some(("subscribe", r"\/App\/MultiQueue"))),
+            (02, r"/Queue\/", r"Queue\/", None),
+            (03, r"Dir\\/", r"Dir\\", None),
+        ];
+        for (step, path, target_trim, target_last) in test_data {
+            let result = ConfPath::trim(path);
+            assert!(result == target_trim, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_trim);
+            let result = ConfPath::split_last(result);
+            assert!(result == target_last, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_last);
+        }
+        let positions = ConfPositions::new(text);
+        let path = r"service TcpServer Server/subscribe/\/App\/MultiQueue/Inf";
+        let result = positions.get(path);
+        let target = Some((5, 13));
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = positions.nearest(&format!("{}/missing", path));
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let mut sources = ConfSources::new();
+        sources.insert("", "app.yaml");
+        sources.insert(r"service TcpServer Server/subscribe/\/App\/MultiQueue", "queue.yaml");
+        sources.insert_position(r"service TcpServer Server/subscribe/\/App\/MultiQueue", (2, 1));
+        let result = sources.file(path).map(|file| file.display().to_string());
+        let target = Some("queue.yaml".to_owned());
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = sources.position(path);
+        let target = Some((2, 1));
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = sources.file(r"service TcpServer Server/subscribe/Dir\\Queue").map(|file| file.display().to_string());
+        let target = Some("app.yaml".to_owned());
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+}
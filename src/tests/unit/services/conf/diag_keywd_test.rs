@@ -35,6 +35,12 @@ mod diag_keywd {
         let test_data = [
             DiagKeywd::Status,
             DiagKeywd::Connection,
+            DiagKeywd::CycleMin,
+            DiagKeywd::CycleAvg,
+            DiagKeywd::CycleMax,
+            DiagKeywd::CycleJitter,
+            DiagKeywd::CycleOverruns,
+            DiagKeywd::CycleOverrunMax,
         ];
         for kewd in DiagKeywd::iter() {
             let result = test_data.contains(&kewd);
@@ -61,6 +67,10 @@ mod diag_keywd {
             ("/App/Service/Connection".to_owned(), DiagKeywd::Connection),
             ("/App/Service/Some.Status".to_owned(), DiagKeywd::Status),
             ("/App/Service/Some.Connection".to_owned(), DiagKeywd::Connection),
+            ("/App/Service/Diag.CycleMax".to_owned(), DiagKeywd::CycleMax),
+            ("/App/Service/Diag.CycleJitter".to_owned(), DiagKeywd::CycleJitter),
+            ("/App/Service/Diag.CycleOverruns".to_owned(), DiagKeywd::CycleOverruns),
+            ("/App/Service/Diag.CycleOverrunMax".to_owned(), DiagKeywd::CycleOverrunMax),
         ]
         .into_iter()
         .chain(
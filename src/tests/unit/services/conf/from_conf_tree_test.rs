@@ -0,0 +1,114 @@
+#[cfg(test)]
+
+mod from_conf_tree {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        collections::FxIndexMap,
+        services::{conf::{ConfInQueue, ConfTree, DiagKeywd, FromConfTree}, entity::{Name, PointConfig}, service::LinkName, subscription::ConfSubscribe},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    crate::conf_struct! {
+        ///
+        /// Config covering all kinds of the fields
+        #[derive(Debug)]
+        struct DriveConf {
+            name: Name = name,
+            rx: ConfInQueue = in_queue,
+            send_to: Vec<LinkName> = send_to,
+            cycle: Option<Duration> = cycle,
+            subscribe: ConfSubscribe = subscribe,
+            diagnosis: FxIndexMap<DiagKeywd, PointConfig> = diagnosis,
+            address: String = required("address"),
+            timeout: Duration = or("timeout", Duration::from_secs(3)),
+            comment: Option<String> = optional("comment"),
+        }
+    }
+    ///
+    /// Testing the fields are read by the keyword keys
+    #[test]
+    fn parse() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("from_conf_tree_parse");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf: serde_yaml::Value = serde_yaml::from_str(r#"
+            cycle: 10 ms
+            address: 127.0.0.1:502
+            in queue in-queue:
+                max-length: 1000
+            send-to: MultiQueue.in-queue
+            subscribe: MultiQueue
+            diagnosis:
+                point Status:
+                    type: 'Int'
+        "#).unwrap();
+        let result = DriveConf::from_conf_tree("App", &ConfTree::new("service Drive Drive1", conf)).unwrap();
+        log::debug!("{:#?}", result);
+        let target = "/App/Drive1";
+        assert!(result.name.join() == target, "\nresult: {:?}\ntarget: {:?}", result.name.join(), target);
+        let target = ConfInQueue { name: "in-queue".to_owned(), max_length: 1000 };
+        assert!(result.rx == target, "\nresult: {:?}\ntarget: {:?}", result.rx, target);
+        let target: Vec<String> = vec!["MultiQueue.in-queue".to_owned()];
+        let send_to: Vec<String> = result.send_to.iter().map(|link| link.name()).collect();
+        assert!(send_to == target, "\nresult: {:?}\ntarget: {:?}", send_to, target);
+        let target = Some(Duration::from_millis(10));
+        assert!(result.cycle == target, "\nresult: {:?}\ntarget: {:?}", result.cycle, target);
+        let target = ConfSubscribe::new(serde_yaml::Value::String("MultiQueue".to_owned()));
+        assert!(result.subscribe == target, "\nresult: {:?}\ntarget: {:?}", result.subscribe, target);
+        let target = 1;
+        assert!(result.diagnosis.len() == target, "\nresult: {:?}\ntarget: {:?}", result.diagnosis.len(), target);
+        let target = "127.0.0.1:502";
+        assert!(result.address == target, "\nresult: {:?}\ntarget: {:?}", result.address, target);
+        let target = Duration::from_secs(3);
+        assert!(result.timeout == target, "\nresult: {:?}\ntarget: {:?}", result.timeout, target);
+        let target = None;
+        assert!(result.comment == target, "\nresult: {:?}\ntarget: {:?}", result.comment, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing the errors name the invalid parameter
+    #[test]
+    fn errors() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("from_conf_tree_errors");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let valid = "in queue in-queue:\n    max-length: 1000\naddress: 127.0.0.1:502\n";
+        let test_data = [
+            (1, "address: 127.0.0.1:502\n".to_owned(), "Parameter 'in queue'"),
+            (2, "in queue in-queue:\n    max-length: 1000\n".to_owned(), "Parameter 'address' - not found"),
+            (3, format!("{}cycle: 10 mss\n", valid), "Parameter 'cycle' - Duration (like '100 ms', '1 s') expected, but found: '10 mss'"),
+            (4, format!("{}send-to: [MultiQueue]\n", valid), "Parameter 'send-to' - item 0: 'Service.link' expected, but found: 'MultiQueue'"),
+            (5, format!("{}timeout: [1 s]\n", valid), "Parameter 'timeout' - Duration expected, but found: list"),
+            (6, format!("{}diagnosis:\n    point Status:\n        type: Integer\n", valid), "Parameter 'diagnosis' - invalid"),
+        ];
+        for (step, conf, target) in test_data {
+            let conf = ConfTree::new("service Drive Drive1", serde_yaml::from_str(&conf).unwrap());
+            let result = format!("{:?}", DriveConf::from_conf_tree("App", &conf).unwrap_err());
+            assert!(result.contains(target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+}
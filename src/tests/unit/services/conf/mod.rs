@@ -1,7 +1,12 @@
 mod fn_config;
 mod conf_duration_test;
 mod conf_keyword_test;
+mod conf_loader_test;
+mod conf_schema_test;
+mod conf_templates_test;
 mod conf_tree_get_test;
 mod conf_tree_keys_test;
+mod conf_tree_path_test;
 mod conf_tree_test;
 mod diag_keywd_test;
+mod from_conf_tree_test;
@@ -2,5 +2,6 @@ mod point_config_address_test;
 mod point_config_test;
 mod point_config_type_test;
 mod point_hlr_test;
+mod point_lines_test;
 mod point_test;
 mod point_tx_id_test;
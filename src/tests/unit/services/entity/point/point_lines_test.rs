@@ -0,0 +1,87 @@
+#[cfg(test)]
+
+mod point_lines {
+    use std::{sync::Once, time::Duration};
+    use chrono::Utc;
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        entity::{Cot, Point, PointHlr, PointLines, Status},
+        types::Bool,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing `PointLines::encode` / `PointLines::decode` round trip
+    #[test]
+    fn round_trip() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("point_lines_round_trip");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let tx_id = 7;
+        let test_data = [
+            (00, Point::Bool(PointHlr::new(tx_id, "/App/Point.Bool", Bool(true), Status::Ok, Cot::Inf, Utc::now()))),
+            (01, Point::Int(PointHlr::new(tx_id, "/App/Point.Int", -123, Status::Invalid, Cot::Act, Utc::now()))),
+            (02, Point::Real(PointHlr::new(tx_id, "/App/Point.Real", 1.5, Status::Obsolete, Cot::ActCon, Utc::now()))),
+            (03, Point::Double(PointHlr::new(tx_id, "/App/Point.Double", -0.125, Status::TimeInvalid, Cot::Inf, Utc::now()))),
+            (04, Point::String(PointHlr::new(tx_id, "/App/Point.String", "multi\nline \"text\"".to_owned(), Status::Ok, Cot::Req, Utc::now()))),
+        ];
+        for (step, target) in test_data {
+            let line = PointLines::encode(&target).unwrap();
+            let lines = line.iter().filter(|b| **b == b'\n').count();
+            assert!(lines == 1 && line.ends_with(b"\n"), "step {} \nresult: {:?}\ntarget: single line", step, String::from_utf8_lossy(&line));
+            let result = PointLines::decode(&line, tx_id).unwrap();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `PointLines::decode` replaces `tx_id` and returns error on invalid lines
+    #[test]
+    fn decode() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("point_lines_decode");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let line = br#"{"type":"Int","value":3,"name":"/App/Point.Int","status":0,"cot":"Inf","timestamp":"2024-02-19T12:16:57.648504907+00:00"}"#;
+        let test_data: [(i32, Vec<u8>, usize); 2] = [
+            (00, [line.as_slice(), &b"\n"[..]].concat(), 11),
+            (01, [line.as_slice(), &b"\r\n"[..]].concat(), 22),
+        ];
+        for (step, line, tx_id) in test_data {
+            let result = PointLines::decode(&line, tx_id).unwrap();
+            let target = Point::Int(PointHlr::new(tx_id, "/App/Point.Int", 3, Status::Ok, Cot::Inf, "2024-02-19T12:16:57.648504907Z".parse().unwrap()));
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let test_data: [(i32, &[u8]); 3] = [
+            (00, b"\n"),
+            (01, b"{\"type\":\"Int\"}\n"),
+            (02, b"not a json\n"),
+        ];
+        for (step, line) in test_data {
+            let result = PointLines::decode(line, 0);
+            assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: Err", step, result);
+        }
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,318 @@
+#[cfg(test)]
+
+mod future_combinators {
+    use std::{
+        pin::pin, sync::{Arc, Once}, task::{Context, Poll, Wake, Waker},
+        thread::{self, Thread}, time::{Duration, Instant},
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use chrono::Utc;
+    use crate::{
+        kernel::clock::{GlobalClock, VirtualClock},
+        services::future::{Future, Sink},
+        sync::channel,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Wakes the thread polling the future
+    struct ThreadWaker(Thread);
+    //
+    //
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+    ///
+    /// Minimal executor, polls the `future` on the current thread until ready
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        let waker: Waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+    ///
+    /// Sends the `value` by the `sink` after the `delay`
+    fn delayed<T: Send + 'static>(sink: Sink<T>, delay: Duration, value: T) {
+        thread::spawn(move || {
+            thread::sleep(delay);
+            sink.add(value);
+        });
+    }
+    ///
+    /// Testing wait_timeout, try_get and failure on the dropped Sink
+    #[test]
+    fn wait_timeout() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("future_wait_timeout");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let (future, sink) = Future::<i32>::new();
+        let result = future.try_get().unwrap();
+        let target = None;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let time = Instant::now();
+        let result = future.wait_timeout(Duration::from_millis(50)).is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = time.elapsed() >= Duration::from_millis(50);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        delayed(sink, Duration::from_millis(50), 12);
+        let result = future.wait_timeout(Duration::from_secs(3)).unwrap();
+        let target = 12;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = future.try_get().is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (future, sink) = Future::<i32>::new();
+        drop(sink);
+        let result = future.wait_timeout(Duration::from_secs(3)).is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = Future::ready(3).try_get().unwrap();
+        let target = Some(3);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing wait_timeout is measured by the GlobalClock
+    #[test]
+    fn wait_timeout_virtual() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("future_wait_timeout_virtual");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let _clock = GlobalClock::local(clock.clone());
+        let (future, _sink) = Future::<i32>::new();
+        let advance = {
+            let clock = clock.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                clock.advance(Duration::from_secs(60));
+            })
+        };
+        let time = Instant::now();
+        let result = future.wait_timeout(Duration::from_secs(60)).is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = time.elapsed() < Duration::from_secs(5);
+        assert!(result == target, "\nresult: {:?}\ntarget: < 5 s", time.elapsed());
+        advance.join().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing deprecated Sink::new sends the value into the channel
+    #[test]
+    #[allow(deprecated)]
+    fn sink_new() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("future_sink_new");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let (send, recv) = channel::unbounded();
+        let sink = Sink::new(send);
+        let result = sink.is_cancelled();
+        let target = false;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        delayed(sink, Duration::from_millis(10), 12);
+        let result = recv.recv_timeout(Duration::from_secs(3)).unwrap();
+        let target = 12;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        // Dropped without value, nothing is sent
+        let (send, recv) = channel::unbounded::<i32>();
+        drop(Sink::new(send));
+        let result = recv.recv_timeout(Duration::from_millis(50)).is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing cancellation is observed by the Sink and propagated through the combinators
+    #[test]
+    fn cancel() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("future_cancel");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let (future, sink) = Future::<i32>::new();
+        let result = sink.is_cancelled();
+        let target = false;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = future.cancel();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = sink.is_cancelled();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        sink.add(1);
+        let result = future.wait().is_err();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (source, sink) = Future::<i32>::new();
+        let mapped = source.map(|value| value * 2);
+        mapped.cancel();
+        let result = sink.is_cancelled();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (sources, sinks): (Vec<_>, Vec<_>) = (0..3).map(|_| Future::<i32>::new()).unzip();
+        let joined = Future::join_all(sources);
+        joined.cancel();
+        let result: Vec<bool> = sinks.iter().map(|sink| sink.is_cancelled()).collect();
+        let target = vec![true, true, true];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (future, _sink) = Future::<i32>::new();
+        future.cancel();
+        let result = future.cancel();
+        let target = false;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing map and and_then are not blocking and chain the values
+    #[test]
+    fn map_and_then() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("future_map_and_then");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let (future, sink) = Future::<i32>::new();
+        let time = Instant::now();
+        let future = future
+            .map(|value| value + 1)
+            .and_then(|value| {
+                let (future, sink) = Future::new();
+                delayed(sink, Duration::from_millis(20), format!("value: {}", value * 10));
+                future
+            });
+        let result = time.elapsed() < Duration::from_millis(20);
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        delayed(sink, Duration::from_millis(20), 4);
+        let result = future.wait_timeout(Duration::from_secs(3)).unwrap();
+        let target = "value: 50".to_owned();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (future, sink) = Future::<i32>::new();
+        let future = future.map(|value| value + 1);
+        sink.fail("source failed");
+        let result = future.wait().is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing join_all and select with the deadline
+    #[test]
+    fn join_select() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("future_join_select");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let futures: Vec<Future<u64>> = [30, 10, 20].into_iter().map(|millis| {
+            let (future, sink) = Future::new();
+            delayed(sink, Duration::from_millis(millis), millis);
+            future
+        }).collect();
+        let result = Future::join_all(futures).wait_timeout(Duration::from_secs(3)).unwrap();
+        let target = vec![30, 10, 20];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (slow, _slow_sink) = Future::<u64>::new();
+        let joined = Future::join_all(vec![Future::ready(1), slow]);
+        let result = joined.wait_timeout(Duration::from_millis(50)).is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (failed, failed_sink) = Future::<u64>::new();
+        let (pending, pending_sink) = Future::<u64>::new();
+        let joined = Future::join_all(vec![pending, failed]);
+        drop(failed_sink);
+        let result = joined.wait_timeout(Duration::from_secs(3)).is_err();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = pending_sink.is_cancelled();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (sources, sinks): (Vec<_>, Vec<_>) = (0..3).map(|_| Future::<u64>::new()).unzip();
+        let selected = Future::select(sources);
+        let mut sinks = sinks.into_iter();
+        let first = sinks.next().unwrap();
+        let second = sinks.next().unwrap();
+        let third = sinks.next().unwrap();
+        drop(first);
+        delayed(third, Duration::from_millis(10), 3);
+        let result = selected.wait_timeout(Duration::from_secs(3)).unwrap();
+        let target = (2, 3);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = second.is_cancelled();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (sources, sinks): (Vec<_>, Vec<_>) = (0..2).map(|_| Future::<u64>::new()).unzip();
+        let selected = Future::select(sources);
+        drop(sinks);
+        let result = selected.wait_timeout(Duration::from_secs(3)).is_err();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing the Future can be awaited from the async code
+    #[test]
+    fn await_() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("future_await");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let (first, first_sink) = Future::<i32>::new();
+        let (second, second_sink) = Future::<i32>::new();
+        delayed(first_sink, Duration::from_millis(20), 20);
+        delayed(second_sink, Duration::from_millis(40), 22);
+        let result = block_on(async move {
+            let first = first.await?;
+            let second = second.await?;
+            Ok::<_, sal_core::error::Error>(first + second)
+        }).unwrap();
+        let target = 42;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let (future, sink) = Future::<i32>::new();
+        drop(sink);
+        let result = block_on(future).is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+}
@@ -1,2 +1,3 @@
+mod future_combinators_test;
 mod future_spawn_test;
 mod future_test;
@@ -0,0 +1,147 @@
+#[cfg(test)]
+
+mod history_service {
+    use std::{sync::{Arc, Once}, time::Duration};
+    use chrono::Utc;
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        conf::{ConfTree, ServicesConf},
+        entity::Cot,
+        HistoryConf, HistoryService, MultiQueue, MultiQueueConf, ProducerConf, ProducerService, Service, Services,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the first node of the yaml
+    fn conf(yaml: &str) -> ConfTree {
+        ConfTree::new_root(serde_yaml::from_str(yaml).unwrap()).next().unwrap()
+    }
+    ///
+    /// Testing only points flagged with `history` in the subscribed direction are stored
+    #[test]
+    fn history() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("history_service_history");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = std::env::temp_dir().join("sal-sync-history-service");
+        let _ = std::fs::remove_dir_all(&dir);
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let multi_queue = Arc::new(MultiQueue::new(
+            MultiQueueConf::from_yaml("App", &serde_yaml::from_str(r#"
+                service MultiQueue:
+                    in queue in-queue:
+                        max-length: 10000
+            "#).unwrap()),
+            services.clone(),
+            None,
+        ));
+        services.insert(multi_queue.clone());
+        let producer = Arc::new(ProducerService::new(ProducerConf::new("App", conf(r#"
+            service ProducerService Producer:
+                cycle: 10 ms
+                send-to: /App/MultiQueue.in-queue
+                point Drive.Speed:
+                    type: 'Double'
+                    history: r
+                    generator: ramp
+                    to: 100
+                point Drive.State:
+                    type: 'Int'
+                    generator: const
+                    value: 1
+                point Drive.Cmd:
+                    type: 'Int'
+                    history: w
+                    cot: Act
+                    generator: const
+                    value: 5
+                point Drive.Echo:
+                    type: 'Int'
+                    history: w
+                    generator: const
+                    value: 7
+                point Drive.Torque:
+                    type: 'Int'
+                    history: rw
+                    generator: const
+                    value: 3
+                point Drive.Mode:
+                    type: 'Int'
+                    history: rw
+                    cot: Act
+                    generator: const
+                    value: 2
+        "#)).unwrap(), services.clone(), None));
+        services.insert(producer.clone());
+        let history = Arc::new(HistoryService::new(HistoryConf::new("App", conf(&format!(r#"
+            service HistoryService History:
+                subscribe:
+                    MultiQueue:
+                        {{cot: Inf, history: r}}: []
+                        {{cot: Act, history: w}}: []
+                path: {}
+                segment: 1 m
+                flush: 50 ms
+        "#, dir.display()))).unwrap(), services.clone(), None));
+        services.insert(history.clone());
+        let result = history.subscribed_to();
+        let target = vec!["/App/MultiQueue".to_owned()];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let from = Utc::now();
+        multi_queue.run().unwrap();
+        history.run().unwrap();
+        producer.run().unwrap();
+        std::thread::sleep(Duration::from_millis(300));
+        producer.exit();
+        producer.wait().unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        let to = Utc::now();
+        // Read history, Cot::Inf
+        let speed = history.range("/App/Producer/Drive.Speed", from, to).unwrap();
+        assert!(!speed.is_empty(), "\nresult: {:?}\ntarget: not empty", speed);
+        for (step, point) in speed.iter().enumerate() {
+            let result = (point.cot(), point.as_double().value);
+            let target = (Cot::Inf, step as f64);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        // Write history, Cot::Act
+        let cmd = history.range("/App/Producer/Drive.Cmd", from, to).unwrap();
+        assert!(!cmd.is_empty(), "\nresult: {:?}\ntarget: not empty", cmd);
+        // ReadWrite history, both directions
+        for name in ["/App/Producer/Drive.Torque", "/App/Producer/Drive.Mode"] {
+            let result = history.range(name, from, to).unwrap();
+            assert!(!result.is_empty(), "{} \nresult: {:?}\ntarget: not empty", name, result);
+        }
+        // No history, or history in the another direction
+        for name in ["/App/Producer/Drive.State", "/App/Producer/Drive.Echo"] {
+            let result = history.range(name, from, to).unwrap();
+            assert!(result.is_empty(), "{} \nresult: {:?}\ntarget: empty", name, result);
+        }
+        let result = history.segments().unwrap().len();
+        assert!((1..=2).contains(&result), "\nresult: {:?}\ntarget: 1..=2", result);
+        history.exit();
+        multi_queue.exit();
+        history.wait().unwrap();
+        multi_queue.wait().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+
+mod history_store {
+    use std::{fs::OpenOptions, io::Write, path::PathBuf, sync::Once, time::Duration};
+    use chrono::{DateTime, TimeZone, Utc};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        entity::{Cot, Point, PointHlr, Status},
+        HistoryStore,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns empty directory for the test
+    fn dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("sal-sync-history-store").join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+    ///
+    /// Returns the time `secs` after the 2025-01-01 00:00:00
+    fn time(secs: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() + chrono::Duration::seconds(secs)
+    }
+    ///
+    /// Returns Double point
+    fn double(name: &str, value: f64, timestamp: DateTime<Utc>) -> Point {
+        Point::Double(PointHlr::new(0, name, value, Status::Ok, Cot::Inf, timestamp))
+    }
+    ///
+    /// Returns (name, value, timestamp) of the points
+    fn values(points: &[Point]) -> Vec<(String, f64, DateTime<Utc>)> {
+        points.iter().map(|point| (point.name(), point.as_double().value, point.timestamp())).collect()
+    }
+    ///
+    /// Testing points are partitioned into the segments and queried by the name and time window
+    #[test]
+    fn range() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("history_store_range");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = dir("range");
+        let mut store = HistoryStore::new(&dbg, &dir, Duration::from_secs(60), None, None);
+        // Two flushes into the same segments, out of order timestamps
+        for (secs, value) in [(0, 0.0), (30, 0.5), (150, 2.5), (61, 1.0)] {
+            store.append(&double("/App/Drive/Speed", value, time(secs))).unwrap();
+            store.append(&double("/App/Drive/Torque", -value, time(secs))).unwrap();
+        }
+        store.flush().unwrap();
+        for (secs, value) in [(59, 0.9), (179, 2.9)] {
+            store.append(&double("/App/Drive/Speed", value, time(secs))).unwrap();
+        }
+        let test_data = [
+            (00, "/App/Drive/Speed", time(0), time(200), vec![(0, 0.0), (30, 0.5), (59, 0.9), (61, 1.0), (150, 2.5), (179, 2.9)]),
+            (01, "/App/Drive/Speed", time(30), time(61), vec![(30, 0.5), (59, 0.9), (61, 1.0)]),
+            (02, "/App/Drive/Speed", time(62), time(149), vec![]),
+            (03, "/App/Drive/Torque", time(100), time(1000), vec![(150, -2.5)]),
+            (04, "/App/Drive/State", time(0), time(1000), vec![]),
+        ];
+        for (step, name, from, to, target) in test_data {
+            let result = values(&store.range(name, from, to).unwrap());
+            let target: Vec<_> = target.into_iter().map(|(secs, value)| (name.to_owned(), value, time(secs))).collect();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result: Vec<_> = store.segments().unwrap().into_iter().map(|segment| segment.start).collect();
+        let target = vec![time(0), time(60), time(120)];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        std::fs::remove_dir_all(&dir).unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing segments are removed by the retention and the max size
+    #[test]
+    fn cleanup() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("history_store_cleanup");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = dir("cleanup");
+        let mut store = HistoryStore::new(&dbg, &dir, Duration::from_secs(60), Some(Duration::from_secs(120)), None);
+        for minute in 0..5 {
+            store.append(&double("/App/Drive/Speed", minute as f64, time(minute * 60))).unwrap();
+        }
+        store.flush().unwrap();
+        // Segments ended before 05:00 - 02:00 = 03:00 are removed: 00:00, 01:00, 02:00
+        let result = store.cleanup(time(300)).unwrap();
+        assert!(result == 3, "\nresult: {:?}\ntarget: {:?}", result, 3);
+        let result: Vec<_> = store.segments().unwrap().into_iter().map(|segment| segment.start).collect();
+        let target = vec![time(180), time(240)];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let segments = store.segments().unwrap();
+        let store = HistoryStore::new(&dbg, &dir, Duration::from_secs(60), None, Some(segments[1].size));
+        let result = store.cleanup(time(300)).unwrap();
+        assert!(result == 1, "\nresult: {:?}\ntarget: {:?}", result, 1);
+        let result: Vec<_> = store.segments().unwrap().into_iter().map(|segment| segment.start).collect();
+        let target = vec![time(240)];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        // The latest segment is kept, even if exceeds
+        let store = HistoryStore::new(&dbg, &dir, Duration::from_secs(60), None, Some(0));
+        let result = store.cleanup(time(300)).unwrap();
+        assert!(result == 0, "\nresult: {:?}\ntarget: {:?}", result, 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing very large segment and retention do not overflow, nothing is removed
+    #[test]
+    fn cleanup_huge() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("history_store_cleanup_huge");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = dir("cleanup_huge");
+        let test_data = [
+            (00, Duration::from_secs(60), Duration::MAX),
+            (01, Duration::from_secs(60), Duration::from_millis(i64::MAX as u64 + 1)),
+            (02, Duration::MAX, Duration::from_secs(120)),
+            (03, Duration::MAX, Duration::MAX),
+        ];
+        for (step, segment, retention) in test_data {
+            let mut store = HistoryStore::new(&dbg, &dir, segment, Some(retention), None);
+            for minute in 0..5 {
+                store.append(&double("/App/Drive/Speed", minute as f64, time(minute * 60))).unwrap();
+            }
+            store.flush().unwrap();
+            let result = store.cleanup(time(300)).unwrap();
+            assert!(result == 0, "step {} \nresult: {:?}\ntarget: {:?}", step, result, 0);
+            let result = values(&store.range("/App/Drive/Speed", time(0), time(300)).unwrap()).len();
+            assert!(result == 5, "step {} \nresult: {:?}\ntarget: {:?}", step, result, 5);
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing points written before the damaged tail of the segment are readable
+    #[test]
+    fn damaged() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("history_store_damaged");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = dir("damaged");
+        let mut store = HistoryStore::new(&dbg, &dir, Duration::from_secs(60), None, None);
+        store.append(&double("/App/Drive/Speed", 1.0, time(1))).unwrap();
+        store.flush().unwrap();
+        let path = store.segments().unwrap()[0].path.clone();
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(&[0x1f, 0x8b, 0x08, 0x00, 0x01]).unwrap();
+        let result = values(&store.range("/App/Drive/Speed", time(0), time(60)).unwrap());
+        let target = vec![("/App/Drive/Speed".to_owned(), 1.0, time(1))];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        std::fs::remove_dir_all(&dir).unwrap();
+        test_duration.exit();
+    }
+}
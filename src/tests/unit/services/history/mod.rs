@@ -0,0 +1,2 @@
+mod history_service_test;
+mod history_store_test;
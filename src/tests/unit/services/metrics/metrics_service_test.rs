@@ -0,0 +1,79 @@
+#[cfg(test)]
+
+mod metrics_service {
+    use std::{io::{Read, Write}, net::{SocketAddr, TcpStream}, sync::{Arc, Once}, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        kernel::metrics::Metrics,
+        services::{conf::{ConfTree, ServicesConf}, MetricsConf, MetricsService, Service, Services},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the reply of the `GET path` request
+    fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(3))).unwrap();
+        stream.write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes()).unwrap();
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).unwrap();
+        reply
+    }
+    ///
+    /// Testing metrics are served over HTTP on the localhost
+    #[test]
+    fn endpoint() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("metrics_service_endpoint");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let conf = ConfTree::new_root(serde_yaml::from_str(r#"
+            service MetricsService Metrics:
+                address: 127.0.0.1:0
+        "#).unwrap()).next().unwrap();
+        let service = MetricsService::new(MetricsConf::new("App", conf).unwrap(), services, None);
+        let result = service.local_addr();
+        let target = None;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        Metrics::global().counter("test_metrics_service_total", "Test of the MetricsService", &[("test", "endpoint")]).add(7);
+        service.run().unwrap();
+        let address = service.local_addr().unwrap();
+        for path in ["/metrics", "/"] {
+            let reply = get(address, path);
+            log::debug!("{}.endpoint | reply: {}", dbg, reply);
+            let result = reply.starts_with("HTTP/1.1 200 OK\r\n");
+            let target = true;
+            assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+            let result = reply.contains("Content-Type: text/plain; version=0.0.4");
+            let target = true;
+            assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+            let result = reply.contains("# TYPE test_metrics_service_total counter\ntest_metrics_service_total{test=\"endpoint\"} 7\n");
+            let target = true;
+            assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        }
+        let result = get(address, "/unknown").starts_with("HTTP/1.1 404 Not Found\r\n");
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        service.exit();
+        service.wait().unwrap();
+        test_duration.exit();
+    }
+}
@@ -0,0 +1 @@
+mod metrics_service_test;
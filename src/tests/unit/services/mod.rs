@@ -1,10 +1,19 @@
+mod cache;
 mod conf;
 mod entity;
 mod future;
+mod history;
+mod metrics;
 mod multi_queue;
+mod producer;
+mod registry;
 mod retain;
 mod service;
 mod services;
 mod subscription;
+mod supervisor;
 mod task;
+mod tcp;
+mod test_service;
+mod topology;
 mod types;
@@ -5,7 +5,7 @@ use sal_core::{dbg::Dbg, error::Error};
 use testing::entities::test_value::Value;
 use crate::{services::{
     entity::{Name, Object, Point, ToPoint}, LinkName, Service, Services
-}, sync::{channel::Sender, RwLock}};
+}, sync::RwLock};
 ///
 ///
 pub struct MockSendService {
@@ -67,15 +67,6 @@ impl Debug for MockSendService {
 //
 //
 impl Service for MockSendService {
-    //
-    //
-    fn get_link(&self, _name: &str) -> Sender<Point> {
-        panic!("{}.get_link | Does not support get_link", self.id())
-        // match self.rxSend.get(name) {
-        //     Some(send) => send.clone(),
-        //     None => panic!("{}.run | link '{:?}' - not found", self.id, name),
-        // }
-    }
     //
     //
     fn run(&self) -> Result<(), Error> {
@@ -0,0 +1,230 @@
+#[cfg(test)]
+
+mod generator {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{conf::ConfTree, Generator, GeneratorValue};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the point node parsed from the yaml
+    fn conf(yaml: &str) -> ConfTree {
+        ConfTree::new_root(serde_yaml::from_str(yaml).unwrap()).next().unwrap()
+    }
+    ///
+    /// Returns first `count` values of the generator
+    fn values(generator: &mut Generator, count: u64, cycle: Duration) -> Vec<GeneratorValue> {
+        (0..count).filter_map(|step| generator.next(step, cycle)).collect()
+    }
+    ///
+    /// Returns numbers rounded to 6 digits
+    fn numbers(values: &[f64]) -> Vec<GeneratorValue> {
+        values.iter().map(|value| GeneratorValue::Number(*value)).collect()
+    }
+    ///
+    /// Returns values with numbers rounded to 6 digits
+    fn rounded(values: Vec<GeneratorValue>) -> Vec<GeneratorValue> {
+        values.into_iter().map(|value| match value {
+            GeneratorValue::Number(value) => GeneratorValue::Number((value * 1e6).round() / 1e6),
+            value => value,
+        }).collect()
+    }
+    ///
+    /// Testing const, ramp, sine, square generators
+    #[test]
+    fn generators() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("generator_generators");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let cycle = Duration::from_millis(250);
+        let test_data = [
+            (00, r#"
+                point Const:
+                    type: 'Int'
+                    generator: const
+                    value: 3
+            "#, numbers(&[3.0, 3.0, 3.0])),
+            (01, r#"
+                point Const:
+                    type: 'Bool'
+                    generator: const
+                    value: true
+            "#, numbers(&[1.0, 1.0])),
+            (02, r#"
+                point Const:
+                    type: 'String'
+                    generator: const
+                    value: text
+            "#, vec![GeneratorValue::String("text".to_owned())]),
+            (03, r#"
+                point Ramp:
+                    type: 'Int'
+                    generator: ramp
+                    to: 3
+            "#, numbers(&[0.0, 1.0, 2.0, 3.0, 0.0, 1.0])),
+            (04, r#"
+                point Ramp:
+                    type: 'Real'
+                    generator: ramp
+                    from: 1.0
+                    to: -0.5
+                    step: -0.5
+            "#, numbers(&[1.0, 0.5, 0.0, -0.5, 1.0])),
+            (05, r#"
+                point Sine:
+                    type: 'Real'
+                    generator: sine
+                    amplitude: 2.0
+                    offset: 1.0
+                    period: 1 s
+            "#, numbers(&[1.0, 3.0, 1.0, -1.0, 1.0])),
+            (06, r#"
+                point Sine:
+                    type: 'Real'
+                    generator: sine
+                    period: 1 s
+                    phase: 90
+            "#, numbers(&[1.0, 0.0, -1.0, 0.0, 1.0])),
+            (07, r#"
+                point Square:
+                    type: 'Bool'
+                    generator: square
+                    period: 1 s
+            "#, numbers(&[1.0, 1.0, 0.0, 0.0, 1.0, 1.0])),
+            (08, r#"
+                point Square:
+                    type: 'Int'
+                    generator: square
+                    low: -5
+                    high: 5
+                    period: 1 s
+                    duty: 0.25
+            "#, numbers(&[5.0, -5.0, -5.0, -5.0, 5.0])),
+        ];
+        for (step, conf, target) in test_data {
+            let mut generator = Generator::new(&dbg, &self::conf(conf)).unwrap();
+            let result = rounded(values(&mut generator, target.len() as u64, cycle));
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing random walk is limited and repeatable with the same seed
+    #[test]
+    fn random_walk() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("generator_random_walk");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let cycle = Duration::from_millis(100);
+        let conf = conf(r#"
+            point Random:
+                type: 'Double'
+                generator: random-walk
+                start: 5.0
+                step: 1.0
+                min: 0.0
+                max: 10.0
+                seed: 42
+        "#);
+        let result = values(&mut Generator::new(&dbg, &conf).unwrap(), 1000, cycle);
+        let target = values(&mut Generator::new(&dbg, &conf).unwrap(), 1000, cycle);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let first = result.first().cloned();
+        assert!(first == Some(GeneratorValue::Number(5.0)), "\nresult: {:?}\ntarget: {:?}", first, 5.0);
+        for (step, pair) in result.windows(2).enumerate() {
+            match pair {
+                [GeneratorValue::Number(prev), GeneratorValue::Number(next)] => {
+                    assert!((0.0..=10.0).contains(next), "step {} \nresult: {:?}\ntarget: 0.0..=10.0", step, next);
+                    assert!((next - prev).abs() <= 1.0, "step {} \nresult: {:?}\ntarget: step <= 1.0", step, next - prev);
+                }
+                _ => panic!("step {} \nresult: {:?}\ntarget: Number", step, pair),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing replay from the CSV file, with and without repeat
+    #[test]
+    fn csv() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("generator_csv");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let path = std::env::temp_dir().join("sal-sync-generator-csv.csv");
+        std::fs::write(&path, "Time, Speed, State\n0, 1.5, Run\n1, 2.5, Run\n\n2, 3.5, Stop\n").unwrap();
+        let cycle = Duration::from_millis(100);
+        let test_data = [
+            (00, "Speed", true, 5, numbers(&[1.5, 2.5, 3.5, 1.5, 2.5])),
+            (01, "Speed", false, 5, numbers(&[1.5, 2.5, 3.5])),
+            (02, "State", false, 5, ["Run", "Run", "Stop"].into_iter().map(|v| GeneratorValue::String(v.to_owned())).collect()),
+        ];
+        for (step, column, repeat, count, target) in test_data {
+            let conf = conf(&format!(r#"
+                point Csv:
+                    type: 'Real'
+                    generator: csv
+                    path: {}
+                    column: {}
+                    repeat: {}
+            "#, path.display(), column, repeat));
+            let result = values(&mut Generator::new(&dbg, &conf).unwrap(), count, cycle);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        std::fs::remove_file(&path).unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing invalid generator configs are rejected
+    #[test]
+    fn new_error() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("generator_new_error");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (00, "point A:\n  type: 'Int'"),
+            (01, "point A:\n  type: 'Int'\n  generator: triangle"),
+            (02, "point A:\n  type: 'Int'\n  generator: const"),
+            (03, "point A:\n  type: 'Int'\n  generator: ramp\n  to: 10\n  step: -1"),
+            (04, "point A:\n  type: 'Int'\n  generator: sine\n  period: 0 ms"),
+            (05, "point A:\n  type: 'Int'\n  generator: square\n  period: 1 s\n  duty: 1.5"),
+            (06, "point A:\n  type: 'Int'\n  generator: csv\n  path: /not/existing.csv\n  column: A"),
+            (07, "point A:\n  type: 'Int'\n  generator: random-walk\n  step: -1"),
+            (08, "point A:\n  type: 'Int'\n  generator: random-walk\n  step: .inf"),
+            (09, "point A:\n  type: 'Int'\n  generator: random-walk\n  min: 10\n  max: -10"),
+        ];
+        for (step, conf) in test_data {
+            let result = Generator::new(&dbg, &self::conf(conf));
+            assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: Err", step, result);
+        }
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,2 @@
+mod generator_test;
+mod producer_service_test;
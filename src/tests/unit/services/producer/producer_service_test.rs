@@ -0,0 +1,169 @@
+#[cfg(test)]
+
+mod producer_service {
+    use std::{str::FromStr, sync::{Arc, Once}, time::Duration};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        services::{
+            conf::{ConfTree, ServicesConf},
+            entity::{Cot, Name, Object, Point, PointTxId, Status},
+            LinkName, ProducerConf, ProducerService, Service, ServiceError, Services,
+        },
+        sync::channel::{self, Receiver, Sender},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns [ProducerConf] parsed from the yaml containing single service node
+    fn conf(yaml: &str) -> Result<ProducerConf, Error> {
+        let conf = ConfTree::new_root(serde_yaml::from_str(yaml).unwrap()).next().unwrap();
+        ProducerConf::new("App", conf)
+    }
+    ///
+    /// Testing generated points are sent to the `send-to` with configured cot and status
+    #[test]
+    fn run() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("producer_service_run");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let (receiver, recv) = ReceiverMok::new("App", "Receiver");
+        services.insert(Arc::new(receiver));
+        let producer = Arc::new(ProducerService::new(conf(r#"
+            service ProducerService Producer:
+                cycle: 10 ms
+                send-to: /App/Receiver.in-queue
+                cot: Act
+                point Drive.State:
+                    type: 'Int'
+                    generator: ramp
+                    to: 2
+                point Drive.Run:
+                    type: 'Bool'
+                    status: Invalid
+                    generator: square
+                    period: 40 ms
+                point Drive.Mode:
+                    type: 'String'
+                    cot: Inf
+                    generator: const
+                    value: Auto
+        "#).unwrap(), services.clone(), None));
+        services.insert(producer.clone());
+        let result = producer.send_to();
+        let target = vec![LinkName::from_str("/App/Receiver.in-queue").unwrap()];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result: Vec<String> = producer.points().into_iter().map(|point| point.name).collect();
+        let target = vec!["/App/Producer/Drive.State", "/App/Producer/Drive.Run", "/App/Producer/Drive.Mode"];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        producer.run().unwrap();
+        let result = producer.run();
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let tx_id = PointTxId::from_str("/App/Producer");
+        let target_states = [0, 1, 2, 0, 1, 2];
+        let target_runs = [true, true, false, false, true, true];
+        for step in 0..target_states.len() {
+            let points: Vec<Point> = (0..3).map(|_| recv.recv_timeout(Duration::from_secs(3)).unwrap()).collect();
+            for point in &points {
+                assert!(point.tx_id() == tx_id, "step {} \nresult: {:?}\ntarget: {:?}", step, point.tx_id(), tx_id);
+            }
+            let (state, run, mode) = (&points[0], &points[1], &points[2]);
+            let result = (state.name(), state.as_int().value, state.cot(), state.status());
+            let target = ("/App/Producer/Drive.State".to_owned(), target_states[step], Cot::Act, Status::Ok);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = (run.name(), run.as_bool().value.0, run.cot(), run.status());
+            let target = ("/App/Producer/Drive.Run".to_owned(), target_runs[step], Cot::Act, Status::Invalid);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = (mode.name(), mode.as_string().value, mode.cot(), mode.status());
+            let target = ("/App/Producer/Drive.Mode".to_owned(), "Auto".to_owned(), Cot::Inf, Status::Ok);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        producer.exit();
+        producer.wait().unwrap();
+        let result = producer.steps();
+        assert!(result >= target_states.len() as u64, "\nresult: {:?}\ntarget: >= {:?}", result, target_states.len());
+        test_duration.exit();
+    }
+    ///
+    /// Testing invalid configs are rejected
+    #[test]
+    fn conf_error() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("producer_service_conf_error");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (00, "service ProducerService Producer:\n  send-to: Receiver.in-queue"),
+            (01, "service ProducerService Producer:\n  cycle: 0 ms\n  send-to: Receiver.in-queue"),
+            (02, "service ProducerService Producer:\n  cycle: 10 ms"),
+            (03, "service ProducerService Producer:\n  cycle: 10 ms\n  send-to: Receiver.in-queue\n  status: Wrong"),
+            (04, "service ProducerService Producer:\n  cycle: 10 ms\n  send-to: Receiver.in-queue\n  point A:\n    type: 'Int'"),
+            (05, "service ProducerService Producer:\n  cycle: 10 ms\n  send-to: Receiver.in-queue\n  point A:\n    type: 'Json'\n    generator: const\n    value: 1"),
+        ];
+        for (step, yaml) in test_data {
+            let result = conf(yaml);
+            assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: Err", step, result);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Used for testing only, forwards points received by `in-queue` into the test
+    struct ReceiverMok {
+        name: Name,
+        send: Sender<Point>,
+    }
+    impl ReceiverMok {
+        fn new(parent: &str, name: &str) -> (Self, Receiver<Point>) {
+            let (send, recv) = channel::unbounded();
+            (Self { name: Name::new(parent, name), send }, recv)
+        }
+    }
+    impl Object for ReceiverMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ReceiverMok {
+        fn try_get_link(&self, name: &str) -> Result<Sender<Point>, ServiceError> {
+            match name {
+                "in-queue" => Ok(self.send.clone()),
+                _ => Err(ServiceError::link_not_found(self.name.join(), name)),
+            }
+        }
+        fn run(&self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn is_finished(&self) -> bool {
+            false
+        }
+        fn exit(&self) {}
+    }
+    impl std::fmt::Debug for ReceiverMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ReceiverMok")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+}
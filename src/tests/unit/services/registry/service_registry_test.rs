@@ -0,0 +1,131 @@
+#[cfg(test)]
+
+mod service_registry {
+    use std::{sync::{Arc, Once}, time::Duration};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        services::{conf::{ConfTree, ServicesConf}, entity::{Name, Object}, Service, ServiceRegistry, Services},
+        thread_pool::Scheduler,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Factory of the [ServiceMok], fails if `fail: true`
+    fn mok(conf: ConfTree, services: Arc<Services>, _scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        if conf.as_bool("fail").unwrap_or(false) {
+            return Err(Error::new("ServiceMok", "new").err("Failed by config"));
+        }
+        let me = conf.sufix_or(conf.name()?);
+        Ok(Arc::new(ServiceMok { name: Name::new(services.name().parent(), me) }))
+    }
+    ///
+    /// Testing `ServiceRegistry::load` builds all known services
+    #[test]
+    fn load() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("service_registry_load");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf: serde_yaml::Value = serde_yaml::from_str(r#"
+            name: App
+            description: Testing registry
+            service Mok Mok1:
+                cycle: 10 ms
+            service MultiQueue:
+                in queue in-queue:
+                    max-length: 10000
+                send-to:
+                    - /App/Mok1.in-queue
+            task Mok Task1:
+                cycle: 10 ms
+        "#).unwrap();
+        let conf = ConfTree::new_root(conf);
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let mut registry = ServiceRegistry::new(&dbg);
+        registry.register("Mok", mok);
+        let result = registry.load(&conf, &services, None).unwrap();
+        let target = vec!["/App/Mok1", "/App/MultiQueue", "/App/Task1"];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        for name in target {
+            assert!(services.get(name).is_some(), "\nresult: {:?}\ntarget: {:?}", None::<()>, name);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `ServiceRegistry::load` reports all unknown and failed services in the single error
+    #[test]
+    fn load_error() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("service_registry_load_error");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf: serde_yaml::Value = serde_yaml::from_str(r#"
+            service Mok Mok1:
+                cycle: 10 ms
+            service TcpClinet Ied01:
+                cycle: 10 ms
+            service Mok Mok2:
+                fail: true
+            service ProfinetClient Ied02:
+                cycle: 10 ms
+        "#).unwrap();
+        let conf = ConfTree::new_root(conf);
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        services.insert(Arc::new(ServiceMok { name: Name::new("App", "Mok1") }));
+        let mut registry = ServiceRegistry::empty(&dbg);
+        registry.register("Mok", mok);
+        let result = registry.load(&conf, &services, None);
+        log::debug!("{} | result: {:?}", dbg, result);
+        let err = result.unwrap_err().to_string();
+        for target in ["service Mok Mok1", "TcpClinet", "Failed by config", "ProfinetClient"] {
+            assert!(err.contains(target), "\nresult: {:?}\ntarget: {:?}", err, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Used for testing only
+    struct ServiceMok {
+        name: Name,
+    }
+    impl Object for ServiceMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ServiceMok {
+        fn run(&self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn is_finished(&self) -> bool {
+            false
+        }
+        fn exit(&self) {}
+    }
+    impl std::fmt::Debug for ServiceMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ServiceMok")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+}
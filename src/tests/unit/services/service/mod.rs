@@ -1,3 +1,4 @@
 mod link_name_test;
 mod service_cycle_test;
+mod service_deps_test;
 mod service_test;
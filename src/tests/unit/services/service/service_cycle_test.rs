@@ -1,11 +1,19 @@
 mod service_cycle {
     #[cfg(test)]
     use log::{info, warn, debug};
-    use std::{sync::Once, time::{Duration, Instant}};
+    use std::{sync::{Arc, Once}, time::{Duration, Instant}};
+    use chrono::Utc;
     use rand::Rng;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
     use crate::{
-        math::AproxEq, services::ServiceCycle
+        collections::FxIndexMap,
+        kernel::clock::{Clock, RealClock, VirtualClock},
+        math::AproxEq,
+        services::{
+            conf::{ConfTree, DiagKeywd},
+            entity::PointConfig,
+            ServiceCycle, ServiceCycleMode, ServiceCycleStats,
+        },
     };
     ///
     ///
@@ -101,4 +109,128 @@ mod service_cycle {
             assert!(errors < errors_allowed, "to much errors ({}), a few errors will be ok, but not more then 5% ({}) of test cycles", errors, errors_allowed);
         }
     }
+    ///
+    /// Testing an hour of the cyclic operation simulated on the virtual clock
+    #[test]
+    fn virtual_clock() {
+        DebugSession::init(LogLevel::Info, Backtrace::Short);
+        init_once();
+        init_each();
+        let clock = Arc::new(VirtualClock::auto(Utc::now()));
+        let mut cycle = ServiceCycle::with_clock("test", Duration::from_millis(100), clock.clone());
+        let start = clock.now();
+        let time = Instant::now();
+        for step in 0..36_000u32 {
+            cycle.start();
+            // Load of the cycle, every 1000-th overruns the interval
+            clock.advance(Duration::from_millis(if step % 1000 == 0 { 150 } else { 30 }));
+            cycle.wait();
+        }
+        let result = clock.elapsed(start);
+        let target = Duration::from_millis(36 * 150 + (36_000 - 36) * 100);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        assert!(time.elapsed() < Duration::from_secs(5), "\nresult: {:?}\ntarget: < 5 s", time.elapsed());
+    }
+    ///
+    /// Runs cycles with the execution time `loads` and the `overhead` between `wait` and `start`
+    ///
+    /// Returns the total time and the statistics
+    fn simulate(mode: ServiceCycleMode, loads: &[u64], overhead: u64) -> (Duration, ServiceCycleStats) {
+        let clock = Arc::new(VirtualClock::auto(Utc::now()));
+        let mut cycle = ServiceCycle::with_clock("test", Duration::from_millis(100), clock.clone()).with_mode(mode);
+        let start = clock.now();
+        for load in loads {
+            cycle.start();
+            clock.advance(Duration::from_millis(*load));
+            cycle.wait();
+            clock.advance(Duration::from_millis(overhead));
+        }
+        (clock.elapsed(start) - Duration::from_millis(overhead), cycle.stats())
+    }
+    ///
+    /// Testing relative and drift-free scheduling, statistics of the cycles
+    #[test]
+    fn modes() {
+        DebugSession::init(LogLevel::Info, Backtrace::Short);
+        init_once();
+        init_each();
+        let ms = Duration::from_millis;
+        // Relative cycles drift by the overhead, absolute are not
+        let (result, stats) = simulate(ServiceCycleMode::Relative, &[30; 10], 1);
+        let target = ms(10 * 100 + 9);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        assert!(stats.jitter_max == ms(1), "\nresult: {:?}\ntarget: {:?}", stats.jitter_max, ms(1));
+        let (result, stats) = simulate(ServiceCycleMode::Absolute, &[30; 10], 1);
+        let target = ms(10 * 100);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        assert!(stats.jitter_max == ms(1), "\nresult: {:?}\ntarget: {:?}", stats.jitter_max, ms(1));
+        // Missed cycles executed without waiting until the schedule is caught up
+        let (result, stats) = simulate(ServiceCycleMode::Absolute, &[250, 30, 30, 30, 30], 0);
+        let target = (ms(500), 3, ms(150), 0);
+        let result = (result, stats.overruns, stats.overrun_max, stats.skipped);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        // Missed cycles skipped
+        let (result, stats) = simulate(ServiceCycleMode::AbsoluteSkip, &[250, 30, 30], 0);
+        let target = (ms(500), 1, ms(150), 2);
+        let result = (result, stats.overruns, stats.overrun_max, stats.skipped);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let target = ServiceCycleStats {
+            cycles: 3,
+            min: ms(30),
+            avg: ms(310) / 3,
+            max: ms(250),
+            jitter_avg: Duration::ZERO,
+            jitter_max: Duration::ZERO,
+            overruns: 1,
+            overrun_max: ms(150),
+            skipped: 2,
+        };
+        assert!(stats == target, "\nresult: {:?}\ntarget: {:?}", stats, target);
+    }
+    ///
+    /// Testing statistics are reported by the configured diagnosis points
+    #[test]
+    fn diagnosis() {
+        DebugSession::init(LogLevel::Info, Backtrace::Short);
+        init_once();
+        init_each();
+        let (_, stats) = simulate(ServiceCycleMode::AbsoluteSkip, &[250, 30, 30], 0);
+        let mut diagnosis = FxIndexMap::default();
+        for name in ["Diag.CycleMax", "Diag.CycleOverruns", "Diag.Status"] {
+            let conf = ConfTree::new(name, serde_yaml::from_str("type: 'Int'").unwrap());
+            diagnosis.insert(DiagKeywd::new(name), PointConfig::new("/App/Task", &conf));
+        }
+        let result: Vec<_> = stats.to_points(0, &diagnosis).into_iter().map(|point| (point.name(), point.as_int().value)).collect();
+        let target = vec![("/App/Task/Diag.CycleMax".to_owned(), 250_000), ("/App/Task/Diag.CycleOverruns".to_owned(), 1)];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+    }
+    ///
+    /// Testing sub-millisecond cycles with the hybrid sleep
+    /// - Real-time limits depend on the host load, so exceeding them is only logged
+    #[test]
+    fn spin() {
+        DebugSession::init(LogLevel::Info, Backtrace::Short);
+        init_once();
+        init_each();
+        let interval = Duration::from_micros(250);
+        let cycles = 400u32;
+        let mut cycle = ServiceCycle::with_clock("test", interval, Arc::new(RealClock))
+            .with_mode(ServiceCycleMode::Absolute)
+            .with_spin(Duration::from_micros(150));
+        let time = Instant::now();
+        for _ in 0..cycles {
+            cycle.start();
+            cycle.wait();
+        }
+        let elapsed = time.elapsed();
+        let stats = cycle.stats();
+        println!("spin | elapsed: {:?}, stats: {}", elapsed, stats);
+        assert!(elapsed >= interval * cycles, "\nresult: {:?}\ntarget: >= {:?}", elapsed, interval * cycles);
+        if elapsed >= interval * cycles * 2 {
+            log::warn!("spin | Elapsed exceeded \nresult: {:?}\ntarget: < {:?}", elapsed, interval * cycles * 2);
+        }
+        if stats.jitter_avg >= Duration::from_micros(100) {
+            log::warn!("spin | Jitter exceeded \nresult: {:?}\ntarget: < 100 us", stats.jitter_avg);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,163 @@
+#[cfg(test)]
+
+mod service_deps {
+    use std::{str::FromStr, sync::{Arc, Once}, time::Duration};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        services::{conf::{ConfTree, ServicesConf}, entity::{Name, Object}, LinkName, Service, ServiceDeps, Services},
+        sync::Mutex,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns services by (name, send-to, subscribe) definitions
+    fn services(log: &Arc<Mutex<Vec<String>>>, defs: &[(&str, &[&str], &[&str])]) -> Vec<(String, Arc<dyn Service>)> {
+        defs.iter().map(|(name, send_to, subscribe)| {
+            let service: Arc<dyn Service> = Arc::new(ServiceMok::new(name, send_to, subscribe, log.clone()));
+            (service.name().join(), service)
+        }).collect()
+    }
+    ///
+    /// Testing `ServiceDeps::order`
+    #[test]
+    fn order() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("service_deps_order");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let log = Arc::new(Mutex::new(vec![]));
+        let test_data: [(i32, &[(&str, &[&str], &[&str])], Vec<&str>); 4] = [
+            (00, &[("/App/A", &[], &[])], vec!["/App/A"]),
+            (01, &[("/App/Client", &["/App/MultiQueue.in-queue"], &[]), ("/App/MultiQueue", &[], &[])], vec!["/App/MultiQueue", "/App/Client"]),
+            (02, &[("/App/Task", &["/App/MultiQueue.in-queue"], &["/App/MultiQueue"]), ("/App/MultiQueue", &["/App/Api.in-queue"], &[]), ("/App/Api", &[], &[])], vec!["/App/Api", "/App/MultiQueue", "/App/Task"]),
+            (03, &[("/App/A", &["/App/A.in-queue"], &[]), ("/App/B", &[], &["/App/A"])], vec!["/App/A", "/App/B"]),
+        ];
+        for (step, defs, target) in test_data {
+            let deps = ServiceDeps::new(&dbg, &services(&log, defs));
+            let result = deps.order().unwrap();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `ServiceDeps::order` returns error on cycles and not registered services
+    #[test]
+    fn order_error() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("service_deps_order_error");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let log = Arc::new(Mutex::new(vec![]));
+        let test_data: [(i32, &[(&str, &[&str], &[&str])]); 3] = [
+            (00, &[("/App/A", &["/App/B.in-queue"], &[]), ("/App/B", &["/App/A.in-queue"], &[])]),
+            (01, &[("/App/A", &[], &["/App/C"]), ("/App/B", &[], &["/App/A"]), ("/App/C", &["/App/B.in-queue"], &[])]),
+            (02, &[("/App/A", &["/App/NotExists.in-queue"], &[])]),
+        ];
+        for (step, defs) in test_data {
+            let deps = ServiceDeps::new(&dbg, &services(&log, defs));
+            let result = deps.order();
+            log::debug!("step {} | result: {:?}", step, result);
+            assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: Err", step, result);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `Services::run_all` / `Services::exit_all`
+    #[test]
+    fn run_all() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("service_deps_run_all");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let log = Arc::new(Mutex::new(vec![]));
+        let services = Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None);
+        for (_, service) in self::services(&log, &[
+            ("/App/Task", &["/App/MultiQueue.in-queue"], &["/App/MultiQueue"]),
+            ("/App/MultiQueue", &["/App/Api.in-queue"], &[]),
+            ("/App/Api", &[], &[]),
+        ]) {
+            services.insert(service);
+        }
+        services.run_all(Duration::from_secs(1)).unwrap();
+        services.exit_all().unwrap();
+        let result = log.lock().clone();
+        let target = vec![
+            "run /App/Api", "run /App/MultiQueue", "run /App/Task",
+            "exit /App/Task", "exit /App/MultiQueue", "exit /App/Api",
+        ];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Used for testing only, records `run` and `exit` calls
+    struct ServiceMok {
+        name: Name,
+        send_to: Vec<LinkName>,
+        subscribe: Vec<String>,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+    impl ServiceMok {
+        fn new(name: &str, send_to: &[&str], subscribe: &[&str], log: Arc<Mutex<Vec<String>>>) -> Self {
+            Self {
+                name: Name::new("", name),
+                send_to: send_to.iter().map(|link| LinkName::from_str(link).unwrap()).collect(),
+                subscribe: subscribe.iter().map(|s| s.to_string()).collect(),
+                log,
+            }
+        }
+    }
+    impl Object for ServiceMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ServiceMok {
+        fn send_to(&self) -> Vec<LinkName> {
+            self.send_to.clone()
+        }
+        fn subscribed_to(&self) -> Vec<String> {
+            self.subscribe.clone()
+        }
+        fn run(&self) -> Result<(), Error> {
+            self.log.lock().push(format!("run {}", self.name));
+            Ok(())
+        }
+        // `wait` is not implemented, `exit_all` reports it as can not be joined
+        fn is_finished(&self) -> bool {
+            false
+        }
+        fn exit(&self) {
+            self.log.lock().push(format!("exit {}", self.name));
+        }
+    }
+    impl std::fmt::Debug for ServiceMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ServiceMok")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+}
@@ -6,7 +6,7 @@ mod trait_service {
     use std::{sync::Once, time::Duration};
     use testing::stuff::max_test_duration::TestDuration;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
-    use crate::services::{entity::{Name, Object}, Service};
+    use crate::services::{entity::{Name, Object}, Service, ServiceError};
     ///
     ///
     static INIT: Once = Once::new();
@@ -133,6 +133,34 @@ mod trait_service {
         test_duration.exit();
     }
     ///
+    /// Testing trait Service fallible methods returns `ServiceError::Unsupported` by default
+    #[test]
+    fn unsupported() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("unsupported");
+        debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let service_test = ServiceTest { name: Name::new(&dbg, "ServiceTest") };
+        let service = service_test.name().join();
+        let test_data = [
+            (00, "get_link", service_test.try_get_link("").map(|_| ())),
+            (01, "subscribe", service_test.try_subscribe("", &[]).map(|_| ())),
+            (02, "extend_subscription", service_test.try_extend_subscription("", &[])),
+            (03, "unsubscribe", service_test.try_unsubscribe("", &[])),
+            (04, "gi", service_test.try_gi("", &[]).map(|_| ())),
+        ];
+        for (step, method, result) in test_data {
+            let target = Err(ServiceError::unsupported(&service, method));
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = service_test.wait();
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        test_duration.exit();
+    }
+    ///
     /// 
     struct ServiceTest {
         name: Name,
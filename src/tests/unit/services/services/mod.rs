@@ -1,2 +1,8 @@
 // mod services_points_test;
+mod services_async_test;
+mod services_error_test;
+mod services_points_request_test;
+mod services_reconfigure_test;
+mod services_shutdown_test;
+mod services_state_test;
 mod services_test;
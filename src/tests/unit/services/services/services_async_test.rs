@@ -0,0 +1,102 @@
+#[cfg(test)]
+
+mod services_async {
+    use std::{
+        pin::pin, str::FromStr, sync::{Arc, Once}, task::{Context, Poll, Wake, Waker},
+        thread::{self, Thread}, time::Duration,
+    };
+    use chrono::Utc;
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        conf::{ConfTree, ServicesConf},
+        entity::{Cot, Point, PointHlr, Status},
+        LinkName, MultiQueue, MultiQueueConf, Service, Services, SubscriptionCriteria,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Wakes the thread polling the future
+    struct ThreadWaker(Thread);
+    //
+    //
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+    ///
+    /// Minimal executor, polls the `future` on the current thread until ready
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        let waker: Waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+    ///
+    /// Testing points are sent into the `MultiQueue` and received by the subscription from the async code
+    #[test]
+    fn link_subscribe() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("services_async_link_subscribe");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let multi_queue = Arc::new(MultiQueue::new(
+            MultiQueueConf::from_yaml("App", &serde_yaml::from_str(r#"
+                service MultiQueue:
+                    in queue in-queue:
+                        max-length: 10000
+            "#).unwrap()),
+            services.clone(),
+            None,
+        ));
+        services.insert(multi_queue.clone());
+        let (_, recv) = services.try_subscribe_async(
+            "/App/MultiQueue",
+            "/App/AsyncReceiver",
+            &[SubscriptionCriteria::new("/App/Drive/Speed", Cot::Inf)],
+        ).unwrap();
+        multi_queue.run().unwrap();
+        let send = services.get_link_async(&LinkName::from_str("/App/MultiQueue.in-queue").unwrap()).unwrap();
+        let test_data = [11, 12, 13];
+        let result = block_on(async move {
+            let mut values = vec![];
+            for value in test_data {
+                send.send(Point::Int(PointHlr::new(0, "/App/Drive/Speed", value, Status::Ok, Cot::Inf, Utc::now()))).await.unwrap();
+                let point = recv.recv().await.unwrap();
+                values.push(point.as_int().value);
+            }
+            values
+        });
+        let target = test_data.to_vec();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = services.try_subscribe_async("/App/Unknown", "/App/AsyncReceiver", &[]).is_err();
+        let target = true;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        multi_queue.exit();
+        multi_queue.wait().unwrap();
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,210 @@
+#[cfg(test)]
+
+mod services_error {
+    use std::{str::FromStr, sync::{Arc, Once}, time::Duration};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        services::{
+            conf::{ConfTree, ServicesConf}, entity::{Name, Object, Point}, future::Future,
+            LinkName, Service, ServiceError, Services, SubscriptionCriteria,
+        },
+        sync::channel::{self, Receiver, Sender},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing `Services::try_insert` / `Services::try_get`
+    #[test]
+    fn try_insert() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test-Services-try_insert");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None);
+        let result = services.try_insert(Arc::new(ServiceMok::new("/App/Mok", false)));
+        assert!(result == Ok(()), "\nresult: {:?}\ntarget: {:?}", result, Ok::<(), ServiceError>(()));
+        let result = services.try_insert(Arc::new(ServiceMok::new("/App/Mok", false)));
+        let target = Err(ServiceError::duplicate("/App/Mok"));
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = services.try_get("/App/Mok").map(|service| service.name().join());
+        assert!(result == Ok("/App/Mok".to_owned()), "\nresult: {:?}\ntarget: {:?}", result, "/App/Mok");
+        let result = services.try_get("/App/Mock").map(|service| service.name().join());
+        let target = Err(ServiceError::not_found("/App/Mock"));
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing `Services::try_get_link` / `Services::try_subscribe` / `Services::try_gi`
+    #[test]
+    fn try_get_link() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test-Services-try_get_link");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None);
+        services.insert(Arc::new(ServiceMok::new("/App/Linked", true)));
+        services.insert(Arc::new(ServiceMok::new("/App/NotLinked", false)));
+        let test_data = [
+            (00, "/App/Linked.in-queue", Ok(())),
+            (01, "/App/Linked.out-queue", Err(ServiceError::link_not_found("/App/Linked", "out-queue"))),
+            (02, "/App/NotLinked.in-queue", Err(ServiceError::unsupported("/App/NotLinked", "get_link"))),
+            (03, "/App/Typo.in-queue", Err(ServiceError::not_found("/App/Typo"))),
+        ];
+        for (step, link, target) in test_data {
+            let link = LinkName::from_str(link).unwrap();
+            let result = services.try_get_link(&link).map(|_| ());
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            // `get_link` never panics, `Unsupported` is returned as an error as well
+            let result = services.get_link(&link);
+            assert!(result.is_ok() == target.is_ok(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let test_data = [
+            (00, "/App/NotLinked", Err(ServiceError::unsupported("/App/NotLinked", "subscribe"))),
+            (01, "/App/Typo", Err(ServiceError::not_found("/App/Typo"))),
+        ];
+        for (step, service, target) in test_data {
+            let result = services.try_subscribe(service, "/App/Receiver", &[]).map(|_| ());
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = services.try_extend_subscription(service, "/App/Receiver", &[]);
+            assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: Err", step, result);
+            let result = services.try_unsubscribe(service, "/App/Receiver", &[]);
+            assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: Err", step, result);
+        }
+        let test_data = [
+            (00, "/App/NotLinked", Err(ServiceError::unsupported("/App/NotLinked", "gi"))),
+            (01, "/App/Typo", Err(ServiceError::not_found("/App/Typo"))),
+        ];
+        for (step, service, target) in test_data {
+            let result = services.try_gi(service, &[]).map(|_| ());
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `Services::try_*` fall back to the panicking methods of the service implementing only them
+    #[test]
+    fn fallback() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test-Services-fallback");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None);
+        services.insert(Arc::new(LegacyMok { name: Name::new("", "/App/Legacy") }));
+        let link = LinkName::from_str("/App/Legacy.in-queue").unwrap();
+        let result = services.try_get_link(&link).map(|_| ());
+        assert!(result == Ok(()), "\nresult: {:?}\ntarget: {:?}", result, Ok::<(), ServiceError>(()));
+        let result = services.get_link(&link).map(|_| ());
+        assert!(result.is_ok(), "\nresult: {:?}\ntarget: Ok", result);
+        let result = services.try_subscribe("/App/Legacy", "/App/Receiver", &[]).map(|_| ());
+        assert!(result == Ok(()), "\nresult: {:?}\ntarget: {:?}", result, Ok::<(), ServiceError>(()));
+        let result = services.try_gi("/App/Legacy", &[]).map(|_| ());
+        assert!(result == Ok(()), "\nresult: {:?}\ntarget: {:?}", result, Ok::<(), ServiceError>(()));
+        // Not implemented at all, the panic of the default method is returned as `Unsupported`
+        let result = services.try_extend_subscription("/App/Legacy", "/App/Receiver", &[]);
+        let target = Err(ServiceError::unsupported("/App/Legacy", "extend_subscription"));
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Used for testing only, implements the panicking methods only
+    struct LegacyMok {
+        name: Name,
+    }
+    impl Object for LegacyMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for LegacyMok {
+        fn get_link(&self, _name: &str) -> Sender<Point> {
+            channel::unbounded().0
+        }
+        fn subscribe(&self, _receiver_name: &str, _points: &[SubscriptionCriteria]) -> (Sender<Point>, Receiver<Point>) {
+            channel::unbounded()
+        }
+        fn gi(&self, _receiver_name: &str, _points: &[SubscriptionCriteria]) -> Future<Vec<Point>> {
+            let (future, sink) = Future::new();
+            sink.add(vec![]);
+            future
+        }
+        fn run(&self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn is_finished(&self) -> bool {
+            false
+        }
+        fn exit(&self) {}
+    }
+    impl std::fmt::Debug for LegacyMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("LegacyMok")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+    ///
+    /// Used for testing only, has `in-queue` link if `linked`
+    struct ServiceMok {
+        name: Name,
+        link: Option<Sender<Point>>,
+    }
+    impl ServiceMok {
+        fn new(name: &str, linked: bool) -> Self {
+            Self {
+                name: Name::new("", name),
+                link: if linked { Some(channel::unbounded().0) } else { None },
+            }
+        }
+    }
+    impl Object for ServiceMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ServiceMok {
+        fn try_get_link(&self, name: &str) -> Result<Sender<Point>, ServiceError> {
+            match (&self.link, name) {
+                (Some(link), "in-queue") => Ok(link.clone()),
+                (Some(_), _) => Err(ServiceError::link_not_found(self.name.join(), name)),
+                (None, _) => Err(ServiceError::unsupported(self.name.join(), "get_link")),
+            }
+        }
+        fn run(&self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn is_finished(&self) -> bool {
+            false
+        }
+        fn exit(&self) {}
+    }
+    impl std::fmt::Debug for ServiceMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ServiceMok")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+}
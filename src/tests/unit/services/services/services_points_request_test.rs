@@ -0,0 +1,173 @@
+#[cfg(test)]
+
+mod services_points_request {
+    use std::{collections::HashSet, sync::{Arc, Once}, time::{Duration, Instant}};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        conf::{ConfTree, ServicesConf},
+        entity::Cot,
+        PointsRequest, ProducerConf, ProducerService, Service, Services, SubscriptionCriteria,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the producer `name` with the points `Drive.Speed` and `Drive.State`
+    fn producer(name: &str, services: &Arc<Services>) -> Arc<ProducerService> {
+        let conf = ConfTree::new_root(serde_yaml::from_str(&format!(r#"
+            service ProducerService {}:
+                cycle: 100 ms
+                send-to: /App/MultiQueue.in-queue
+                point Drive.Speed:
+                    type: 'Double'
+                    generator: const
+                    value: 1
+                point Drive.State:
+                    type: 'Int'
+                    generator: const
+                    value: 1
+        "#, name)).unwrap()).next().unwrap();
+        Arc::new(ProducerService::new(ProducerConf::new("App", conf).unwrap(), services.clone(), None))
+    }
+    ///
+    /// Returns [Services] retaining point id's in the `dir`
+    fn retained(dbg: &Dbg, dir: &std::path::Path) -> Arc<Services> {
+        let _ = std::fs::remove_dir_all(dir);
+        Arc::new(Services::new("App", ServicesConf::new(dbg, ConfTree::new_root(serde_yaml::from_str(&format!(r#"
+            retain:
+                path: {}
+                point:
+                    path: point/id.json
+        "#, dir.display())).unwrap())), None))
+    }
+    ///
+    /// Returns sorted names of the requested points
+    fn names(services: &Services, requester: &str, request: PointsRequest) -> Vec<String> {
+        let mut names: Vec<String> = services.request_points(requester, request).wait().unwrap().into_iter().map(|point| point.name).collect();
+        names.sort();
+        names
+    }
+    ///
+    /// Testing requests of the all points, points of the service and points matching the criteria
+    #[test]
+    fn kinds() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("services_points_request_kinds");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = std::env::temp_dir().join("sal-sync-services-points-request-kinds");
+        let services = retained(&dbg, &dir);
+        services.insert(producer("ProducerA", &services));
+        services.insert(producer("ProducerB", &services));
+        services.run().unwrap();
+        let test_data = [
+            (00, "/App/ProducerA", PointsRequest::All, vec!["/App/ProducerB/Drive.Speed", "/App/ProducerB/Drive.State"]),
+            (01, "App/ProducerA/Fn", PointsRequest::All, vec!["/App/ProducerB/Drive.Speed", "/App/ProducerB/Drive.State"]),
+            (02, "/App/Task", PointsRequest::All, vec![
+                "/App/ProducerA/Drive.Speed", "/App/ProducerA/Drive.State", "/App/ProducerB/Drive.Speed", "/App/ProducerB/Drive.State",
+            ]),
+            (03, "/App/Task", PointsRequest::Service("/App/ProducerB".to_owned()), vec!["/App/ProducerB/Drive.Speed", "/App/ProducerB/Drive.State"]),
+            (04, "/App/Task", PointsRequest::Service("/App/Producer".to_owned()), vec![]),
+            (05, "/App/ProducerA", PointsRequest::Criteria(vec![
+                SubscriptionCriteria::new("/App/ProducerA/Drive.Speed", Cot::Inf),
+                SubscriptionCriteria::new("/App/ProducerB/Drive.State", Cot::Act),
+            ]), vec!["/App/ProducerA/Drive.Speed", "/App/ProducerB/Drive.State"]),
+            // `/App/Producer` is the prefix of the name, but not the owner of `/App/ProducerA` and `/App/ProducerB`
+            (06, "/App/Producer", PointsRequest::All, vec![
+                "/App/ProducerA/Drive.Speed", "/App/ProducerA/Drive.State", "/App/ProducerB/Drive.Speed", "/App/ProducerB/Drive.State",
+            ]),
+            (07, "/App/ProducerA/", PointsRequest::All, vec!["/App/ProducerB/Drive.Speed", "/App/ProducerB/Drive.State"]),
+        ];
+        for (step, requester, request, target) in test_data {
+            let result = names(&services, requester, request);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        services.exit();
+        services.wait().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing nothing is returned if `retain.point` is not configured
+    #[test]
+    fn not_retained() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("services_points_request_not_retained");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        services.insert(producer("ProducerA", &services));
+        services.run().unwrap();
+        let test_data = [
+            (00, PointsRequest::All),
+            (01, PointsRequest::Service("/App/ProducerA".to_owned())),
+            (02, PointsRequest::Criteria(vec![SubscriptionCriteria::new("/App/ProducerA/Drive.Speed", Cot::Inf)])),
+        ];
+        for (step, request) in test_data {
+            let result = names(&services, "/App/Task", request.clone());
+            assert!(result.is_empty(), "step {} \nresult: {:?}\ntarget: empty", step, result);
+            let result = services.point_ids("/App/Task", request).wait().unwrap();
+            assert!(result.is_empty(), "step {} \nresult: {:?}\ntarget: empty", step, result);
+        }
+        services.exit();
+        services.wait().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing retained id's are returned and many pending requests are served at once
+    #[test]
+    fn retained() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("services_points_request_retained");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let dir = std::env::temp_dir().join("sal-sync-services-points-request");
+        let services = retained(&dbg, &dir);
+        services.insert(producer("ProducerA", &services));
+        services.insert(producer("ProducerB", &services));
+        let time = Instant::now();
+        // Requested before the run, served all at once
+        let requests: Vec<_> = (0..200).map(|_| services.point_ids("/App/Task", PointsRequest::All)).collect();
+        services.run().unwrap();
+        let results: Vec<_> = requests.into_iter().map(|request| request.wait().unwrap()).collect();
+        assert!(time.elapsed() < Duration::from_secs(2), "\nresult: {:?}\ntarget: < 2 s", time.elapsed());
+        let ids = &results[0];
+        let result: HashSet<usize> = ids.values().copied().collect();
+        let target: HashSet<usize> = (0..4).collect();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        for result in &results {
+            assert!(result == ids, "\nresult: {:?}\ntarget: {:?}", result, ids);
+        }
+        let result = services.request_points("/App/Task", PointsRequest::Service("/App/ProducerA".to_owned())).wait().unwrap();
+        for point in result {
+            let target = ids.get(&point.name).copied();
+            assert!(Some(point.id) == target, "{} \nresult: {:?}\ntarget: {:?}", point.name, point.id, target);
+        }
+        services.exit();
+        services.wait().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,268 @@
+#[cfg(test)]
+
+mod services_reconfigure {
+    use std::{sync::{atomic::{AtomicUsize, Ordering}, Arc, Mutex, Once}, time::Duration};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        services::{
+            conf::{ConfTree, ConfTreeGet, ServicesConf}, entity::{Name, Object, Point},
+            MultiQueue, MultiQueueConf, ProducerConf, ProducerService,
+            Reconfigured, Service, ServiceError, ServiceRegistry, Services, SubscriptionCriteria, TestService,
+        },
+        sync::channel::{self, Receiver, Sender},
+        thread_pool::Scheduler,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// Calls of the [ServiceMok] methods
+    static LOG: Mutex<Vec<String>> = Mutex::new(vec![]);
+    ///
+    /// Number of the built [ServiceMok] instances
+    static INSTANCES: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Factory of the [ServiceMok]
+    fn mok(conf: ConfTree, services: Arc<Services>, _scheduler: Option<Scheduler>) -> Result<Arc<dyn Service>, Error> {
+        let me = conf.sufix_or(conf.name()?);
+        let reconfigurable: Option<bool> = ConfTreeGet::<bool>::get(&conf, "reconfigurable");
+        Ok(Arc::new(ServiceMok {
+            name: Name::new(services.name().parent(), me),
+            instance: INSTANCES.fetch_add(1, Ordering::SeqCst),
+            reconfigurable: reconfigurable.unwrap_or(false),
+        }))
+    }
+    ///
+    /// Returns root [ConfTree] of three services
+    fn conf(a: u64, b: u64, c: u64) -> ConfTree {
+        let conf = format!(r#"
+            service Mok A:
+                reconfigurable: true
+                threshold: {a}
+            service Mok B:
+                threshold: {b}
+            service Mok C:
+                threshold: {c}
+        "#);
+        ConfTree::new_root(serde_yaml::from_str(&conf).unwrap())
+    }
+    ///
+    /// Testing `Services::reconfigure` through `ServiceRegistry::reconfigure`
+    #[test]
+    fn reconfigure() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test-Services-reconfigure");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let mut registry = ServiceRegistry::empty(&dbg);
+        registry.register("Mok", mok);
+        registry.load(&conf(1, 1, 1), &services, None).unwrap();
+        let (_, _recv) = services.subscribe("/App/B", "/App/Receiver", &[]);
+        LOG.lock().unwrap().clear();
+        let c = services.get("/App/C").unwrap();
+        let result = registry.reconfigure(&conf(2, 2, 1), &services, None).unwrap();
+        let target = vec![
+            ("/App/A".to_owned(), Reconfigured::Applied),
+            ("/App/B".to_owned(), Reconfigured::Restarted),
+            ("/App/C".to_owned(), Reconfigured::Unchanged),
+        ];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = LOG.lock().unwrap().clone();
+        let target = vec!["reconfigure 0 threshold: 2", "exit 1", "resubscribe 3 /App/Receiver", "run 3"];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = Arc::ptr_eq(&c, &services.get("/App/C").unwrap());
+        assert!(result, "\nresult: {:?}\ntarget: {:?}", result, true);
+        let result = services.conf("/App/B").map(|conf| conf.as_i64("threshold").unwrap());
+        assert!(result == Some(2), "\nresult: {:?}\ntarget: {:?}", result, Some(2));
+        let result = registry.reconfigure(&conf(2, 2, 1), &services, None).unwrap();
+        assert!(result.iter().all(|(_, r)| *r == Reconfigured::Unchanged), "\nresult: {:?}\ntarget: all Unchanged", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing restarted MultiQueue keeps receiving from the `send-to` of the producer and sending to the subscriber
+    #[test]
+    fn restart() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test-Services-reconfigure-restart");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let name = "/App/MultiQueue";
+        let point = "/App/Producer/Drive.Step";
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let multi_queue_conf = |max_length: usize| {
+            let conf = format!("service MultiQueue:\n    in queue in-queue:\n        max-length: {}\n", max_length);
+            ConfTree::new_root(serde_yaml::from_str(&conf).unwrap()).next().unwrap()
+        };
+        let build = |conf: ConfTree| -> Result<Arc<dyn Service>, Error> {
+            Ok(Arc::new(MultiQueue::new(MultiQueueConf::new("App", conf), services.clone(), None)))
+        };
+        let multi_queue = build(multi_queue_conf(10000)).unwrap();
+        services.insert(multi_queue.clone());
+        services.set_conf(name, multi_queue_conf(10000));
+        let producer = Arc::new(ProducerService::new(ProducerConf::new("App", ConfTree::new_root(serde_yaml::from_str(r#"
+            service ProducerService Producer:
+                cycle: 10 ms
+                send-to: /App/MultiQueue.in-queue
+                point Drive.Step:
+                    type: 'Int'
+                    generator: ramp
+                    to: 1000
+        "#).unwrap()).next().unwrap()).unwrap(), services.clone(), None));
+        services.insert(producer.clone());
+        let test = Arc::new(TestService::new("App", "Test", &[]));
+        services.insert(test.clone());
+        test.subscribe_on(&services, name, &[]).unwrap();
+        multi_queue.run().unwrap();
+        producer.run().unwrap();
+        test.wait_point(point, Duration::from_secs(1)).unwrap();
+        let result = services.reconfigure(name, multi_queue_conf(20000), build).unwrap();
+        assert!(result == Reconfigured::Restarted, "\nresult: {:?}\ntarget: {:?}", result, Reconfigured::Restarted);
+        let restarted = services.get(name).unwrap();
+        let result = Arc::ptr_eq(&multi_queue, &restarted);
+        assert!(!result, "\nresult: {:?}\ntarget: {:?}", result, false);
+        test.clear();
+        let result = test.wait_count(10, Duration::from_secs(3));
+        assert!(result.is_ok(), "\nresult: {:?}\ntarget: 10 points received after restart", result);
+        producer.exit();
+        producer.wait().unwrap();
+        restarted.exit();
+        restarted.wait().unwrap();
+        test.exit();
+        test_duration.exit();
+    }
+    ///
+    /// Testing restarted service keeps receiving from the `send-to` of the MultiQueue
+    #[test]
+    fn restart_send_to() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test-Services-reconfigure-restart_send_to");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let name = "/App/Test";
+        let point = "/App/Producer/Drive.Step";
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let test_conf = |threshold: usize| {
+            let conf = format!("service Test:\n    threshold: {}\n", threshold);
+            ConfTree::new_root(serde_yaml::from_str(&conf).unwrap()).next().unwrap()
+        };
+        let test = Arc::new(TestService::new("App", "Test", &["in-queue"]));
+        services.insert(test.clone());
+        services.set_conf(name, test_conf(1));
+        let multi_queue = Arc::new(MultiQueue::new(MultiQueueConf::new("App", ConfTree::new_root(serde_yaml::from_str(r#"
+            service MultiQueue:
+                in queue in-queue:
+                    max-length: 10000
+                send-to:
+                    - /App/Test.in-queue
+        "#).unwrap()).next().unwrap()), services.clone(), None));
+        services.insert(multi_queue.clone());
+        let producer = Arc::new(ProducerService::new(ProducerConf::new("App", ConfTree::new_root(serde_yaml::from_str(r#"
+            service ProducerService Producer:
+                cycle: 10 ms
+                send-to: /App/MultiQueue.in-queue
+                point Drive.Step:
+                    type: 'Int'
+                    generator: ramp
+                    to: 1000
+        "#).unwrap()).next().unwrap()).unwrap(), services.clone(), None));
+        services.insert(producer.clone());
+        multi_queue.run().unwrap();
+        producer.run().unwrap();
+        test.wait_point(point, Duration::from_secs(1)).unwrap();
+        let restarted = Arc::new(TestService::new("App", "Test", &["in-queue"]));
+        let build = |_conf: ConfTree| -> Result<Arc<dyn Service>, Error> {
+            Ok(restarted.clone())
+        };
+        let result = services.reconfigure(name, test_conf(2), build).unwrap();
+        assert!(result == Reconfigured::Restarted, "\nresult: {:?}\ntarget: {:?}", result, Reconfigured::Restarted);
+        let result = services.get(name).map(|service| service.name().join());
+        assert!(result == Some(name.to_owned()), "\nresult: {:?}\ntarget: {:?}", result, Some(name));
+        restarted.wait_point(point, Duration::from_secs(1)).unwrap();
+        let result = restarted.wait_count(10, Duration::from_secs(3));
+        assert!(result.is_ok(), "\nresult: {:?}\ntarget: 10 points received after restart", result);
+        producer.exit();
+        producer.wait().unwrap();
+        multi_queue.exit();
+        multi_queue.wait().unwrap();
+        restarted.exit();
+        test_duration.exit();
+    }
+    ///
+    /// Used for testing only
+    struct ServiceMok {
+        name: Name,
+        instance: usize,
+        reconfigurable: bool,
+    }
+    impl ServiceMok {
+        fn log(&self, message: String) {
+            LOG.lock().unwrap().push(message);
+        }
+    }
+    impl Object for ServiceMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ServiceMok {
+        fn subscribe(&self, _receiver_name: &str, _points: &[SubscriptionCriteria]) -> (Sender<Point>, Receiver<Point>) {
+            channel::unbounded()
+        }
+        fn resubscribe(&self, receiver_name: &str, _points: &[SubscriptionCriteria], _send: Sender<Point>) -> Result<(), Error> {
+            self.log(format!("resubscribe {} {}", self.instance, receiver_name));
+            Ok(())
+        }
+        fn reconfigure(&self, conf: &ConfTree) -> Result<(), ServiceError> {
+            if self.reconfigurable {
+                self.log(format!("reconfigure {} threshold: {}", self.instance, conf.as_i64("threshold").unwrap()));
+                Ok(())
+            } else {
+                Err(ServiceError::unsupported(self.name.join(), "reconfigure"))
+            }
+        }
+        fn run(&self) -> Result<(), Error> {
+            self.log(format!("run {}", self.instance));
+            Ok(())
+        }
+        fn wait(&self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn is_finished(&self) -> bool {
+            false
+        }
+        fn exit(&self) {
+            self.log(format!("exit {}", self.instance));
+        }
+    }
+    impl std::fmt::Debug for ServiceMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ServiceMok")
+                .field("name", &self.name)
+                .field("instance", &self.instance)
+                .finish()
+        }
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+
+mod services_shutdown {
+    use std::{sync::{atomic::{AtomicBool, Ordering}, Arc, Once}, time::Duration};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{conf::{ConfTree, ServicesConf}, entity::{Name, Object}, RestartPolicy, Service, ServiceError, ServiceState, Services};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing `Services::shutdown` reports stopped, failed, unjoined and hung services
+    #[test]
+    fn shutdown() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test-Services-shutdown");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None));
+        services.insert(Arc::new(ServiceMok::new("/App/Stopping", Behavior::Stop)));
+        services.insert(Arc::new(ServiceMok::new("/App/Failing", Behavior::Fail)));
+        services.insert(Arc::new(ServiceMok::new("/App/Hung", Behavior::Hang)));
+        services.insert(Arc::new(ServiceMok::new("/App/Unjoinable", Behavior::Unjoinable)));
+        services.run().unwrap();
+        let deadline = Duration::from_millis(300);
+        let report = services.shutdown(deadline);
+        log::debug!("{} | report: {}", dbg, report);
+        assert!(!report.is_ok(), "\nresult: {:?}\ntarget: {:?}", report.is_ok(), false);
+        assert!(report.stopped == vec!["/App/Stopping"], "\nresult: {:?}\ntarget: {:?}", report.stopped, vec!["/App/Stopping"]);
+        let failed: Vec<&str> = report.failed.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(failed == vec!["/App/Failing"], "\nresult: {:?}\ntarget: {:?}", failed, vec!["/App/Failing"]);
+        assert!(report.unjoined == vec!["/App/Unjoinable"], "\nresult: {:?}\ntarget: {:?}", report.unjoined, vec!["/App/Unjoinable"]);
+        assert!(report.timed_out == vec!["/App/Hung"], "\nresult: {:?}\ntarget: {:?}", report.timed_out, vec!["/App/Hung"]);
+        assert!(report.elapsed >= deadline, "\nresult: {:?}\ntarget: >= {:?}", report.elapsed, deadline);
+        assert!(report.elapsed < deadline * 2, "\nresult: {:?}\ntarget: < {:?}", report.elapsed, deadline * 2);
+        let result = services.state("/App/Stopping");
+        assert!(result == Some(ServiceState::Stopped), "\nresult: {:?}\ntarget: {:?}", result, Some(ServiceState::Stopped));
+        let result = services.state("/App/Failing");
+        assert!(result == Some(ServiceState::Failed), "\nresult: {:?}\ntarget: {:?}", result, Some(ServiceState::Failed));
+        let result = services.state("/App/Unjoinable");
+        assert!(result == Some(ServiceState::Stopped), "\nresult: {:?}\ntarget: {:?}", result, Some(ServiceState::Stopped));
+        let result = services.state("/App/Hung");
+        assert!(result == Some(ServiceState::Stopping), "\nresult: {:?}\ntarget: {:?}", result, Some(ServiceState::Stopping));
+        test_duration.exit();
+    }
+    ///
+    /// Testing `Services::shutdown` waits slow stopping supervised service through its watcher
+    #[test]
+    fn supervised() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test-Services-shutdown-supervised");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let name = "/App/Slow";
+        // ServiceMok stops in 200 ms after exit
+        let test_data = [
+            (1, Duration::from_millis(100), (vec![], vec![name]), ServiceState::Stopping),
+            (2, Duration::from_millis(1000), (vec![name], vec![]), ServiceState::Stopped),
+        ];
+        for (step, deadline, target, target_state) in test_data {
+            let services = Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None);
+            services.insert(Arc::new(ServiceMok::new(name, Behavior::Slow)));
+            services.run_all(Duration::from_secs(1)).unwrap();
+            services.supervise(name, RestartPolicy::Never, move || -> Result<Arc<dyn Service>, Error> {
+                Ok(Arc::new(ServiceMok::new(name, Behavior::Slow)))
+            }).unwrap();
+            let report = services.shutdown(deadline);
+            log::debug!("{} | step {} report: {}", dbg, step, report);
+            let result: (Vec<&str>, Vec<&str>) = (
+                report.stopped.iter().map(|name| name.as_str()).collect(),
+                report.timed_out.iter().map(|name| name.as_str()).collect(),
+            );
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            assert!(report.failed.is_empty(), "step {} \nresult: {:?}\ntarget: {:?}", step, report.failed, Vec::<(String, String)>::new());
+            let result = services.state(name);
+            assert!(result == Some(target_state), "step {} \nresult: {:?}\ntarget: {:?}", step, result, Some(target_state));
+            let result = services.restarts(name);
+            assert!(result.is_none(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, None::<usize>);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Reaction of the [ServiceMok] on exit
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Behavior {
+        Stop,
+        Fail,
+        Hang,
+        Slow,
+        Unjoinable,
+    }
+    ///
+    /// Used for testing only
+    /// - Like `Handles::wait`, only the first waiter is blocked, next ones are returned immediately
+    struct ServiceMok {
+        name: Name,
+        behavior: Behavior,
+        exit: AtomicBool,
+        waited: AtomicBool,
+    }
+    impl ServiceMok {
+        fn new(name: &str, behavior: Behavior) -> Self {
+            Self {
+                name: Name::new("", name),
+                behavior,
+                exit: AtomicBool::new(false),
+                waited: AtomicBool::new(false),
+            }
+        }
+    }
+    impl Object for ServiceMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ServiceMok {
+        fn run(&self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn wait(&self) -> Result<(), Error> {
+            // Like the default `Service::wait`
+            if self.behavior == Behavior::Unjoinable {
+                return Err(ServiceError::unsupported(self.name.join(), "wait").into());
+            }
+            if self.waited.swap(true, Ordering::SeqCst) {
+                return Ok(());
+            }
+            loop {
+                if self.behavior != Behavior::Hang && self.exit.load(Ordering::SeqCst) {
+                    return match self.behavior {
+                        Behavior::Fail => Err(Error::new("ServiceMok", "wait").err("Stopped with error")),
+                        Behavior::Slow => {
+                            std::thread::sleep(Duration::from_millis(200));
+                            Ok(())
+                        }
+                        _ => Ok(()),
+                    };
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+        fn is_finished(&self) -> bool {
+            self.behavior == Behavior::Unjoinable && self.exit.load(Ordering::SeqCst)
+        }
+        fn exit(&self) {
+            self.exit.store(true, Ordering::SeqCst);
+        }
+    }
+    impl std::fmt::Debug for ServiceMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ServiceMok")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+}
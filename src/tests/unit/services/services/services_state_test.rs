@@ -0,0 +1,111 @@
+#[cfg(test)]
+
+mod services_state {
+    use std::{sync::{Arc, Once}, time::Duration};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        services::{conf::{ConfTree, ServicesConf}, entity::{Name, Object}, Service, ServiceState, Services},
+        sync::RwLock,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing `Services` tracks the lifecycle state transitions of the services
+    #[test]
+    fn transitions() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test-Services-state");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let observer = services.subscribe_states("test-observer");
+        let service = Arc::new(ServiceMok::new(&dbg, ServiceState::Starting));
+        let service_name = service.name().join();
+        services.insert(service.clone());
+        services.run().unwrap();
+        let test_data = [
+            (0, ServiceState::Running),
+            (1, ServiceState::Degraded),
+            (2, ServiceState::Running),
+            (3, ServiceState::Stopping),
+            (4, ServiceState::Stopped),
+        ];
+        let event = observer.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(event.prev.is_none(), "\nresult: {:?}\ntarget: {:?}", event.prev, None::<ServiceState>);
+        assert!(event.state == ServiceState::Starting, "\nresult: {:?}\ntarget: {:?}", event.state, ServiceState::Starting);
+        let mut prev = ServiceState::Starting;
+        for (step, target) in test_data {
+            service.set_state(target);
+            let event = observer.recv_timeout(Duration::from_secs(1)).unwrap();
+            log::debug!("{} | step {}  event: {:?}", dbg, step, event);
+            assert!(event.service == service_name, "step {} \nresult: {:?}\ntarget: {:?}", step, event.service, service_name);
+            assert!(event.prev == Some(prev), "step {} \nresult: {:?}\ntarget: {:?}", step, event.prev, Some(prev));
+            assert!(event.state == target, "step {} \nresult: {:?}\ntarget: {:?}", step, event.state, target);
+            let result = services.state(&service_name);
+            assert!(result == Some(target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, Some(target));
+            prev = target;
+        }
+        services.unsubscribe_states("test-observer");
+        services.exit();
+        services.wait().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Used for testing only, state is set from outside
+    struct ServiceMok {
+        name: Name,
+        state: RwLock<ServiceState>,
+    }
+    impl ServiceMok {
+        fn new(parent: impl Into<String>, state: ServiceState) -> Self {
+            Self {
+                name: Name::new(parent, "ServiceMok"),
+                state: RwLock::new(state),
+            }
+        }
+        fn set_state(&self, state: ServiceState) {
+            *self.state.write() = state;
+        }
+    }
+    impl Object for ServiceMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ServiceMok {
+        fn run(&self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn is_finished(&self) -> bool {
+            self.state.read().is_final()
+        }
+        fn state(&self) -> ServiceState {
+            *self.state.read()
+        }
+        fn exit(&self) {}
+    }
+    impl std::fmt::Debug for ServiceMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ServiceMok")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+}
@@ -62,6 +62,11 @@ mod conf_subscribe {
                 address:
                     offset: 16
                 alarm: 1"#,
+            r#"point Drive.Torque2:
+                type: 'Real'
+                address:
+                    offset: 20
+                history: rw"#,
         ];
         let points = points.map(|conf| {
             let conf = serde_yaml::from_str(conf).unwrap();
@@ -98,6 +103,7 @@ mod conf_subscribe {
                     SubscriptionCriteria::new(Name::new(self_id, "Drive.Current").join(), Cot::Inf),
                     SubscriptionCriteria::new(Name::new(self_id, "Drive.Torque").join(), Cot::Inf),
                     SubscriptionCriteria::new(Name::new(self_id, "Drive.Torque1").join(), Cot::Inf),
+                    SubscriptionCriteria::new(Name::new(self_id, "Drive.Torque2").join(), Cot::Inf),
                 ]))])
             ),
             (
@@ -108,6 +114,7 @@ mod conf_subscribe {
                 "#,
                 HashMap::from([("App/MultiQueue".to_owned(), Some(vec![
                     SubscriptionCriteria::new(Name::new(self_id, "Drive.Current").join(), Cot::All),
+                    SubscriptionCriteria::new(Name::new(self_id, "Drive.Torque2").join(), Cot::All),
                 ]))])
             ),
             (
@@ -128,6 +135,7 @@ mod conf_subscribe {
                 "#,
                 HashMap::from([("MultiQueue".to_owned(), Some(vec![
                     SubscriptionCriteria::new(Name::new(self_id, "Drive.Current").join(), Cot::Inf),
+                    SubscriptionCriteria::new(Name::new(self_id, "Drive.Torque2").join(), Cot::Inf),
                 ]))])
             ),
             (
@@ -0,0 +1,2 @@
+mod restart_policy_test;
+mod supervisor_test;
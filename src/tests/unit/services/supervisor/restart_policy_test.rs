@@ -0,0 +1,139 @@
+#[cfg(test)]
+
+mod restart_policy {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{conf::ConfTree, RestartPolicy};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing `RestartPolicy::new`
+    #[test]
+    fn new() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("restart_policy_new");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (00, r#"
+                cycle: 10 ms
+            "#, Some(RestartPolicy::Never)),
+            (01, r#"
+                restart:
+                    policy: never
+            "#, Some(RestartPolicy::Never)),
+            (02, r#"
+                restart:
+                    policy: always
+            "#, Some(RestartPolicy::Always { backoff: Duration::from_secs(1), max_backoff: Duration::from_secs(30), reset_after: Duration::from_secs(60) })),
+            (03, r#"
+                restart:
+                    policy: always
+                    backoff: 100 ms
+                    max-backoff: 5 s
+                    reset-after: 10 s
+            "#, Some(RestartPolicy::Always { backoff: Duration::from_millis(100), max_backoff: Duration::from_secs(5), reset_after: Duration::from_secs(10) })),
+            (04, r#"
+                restart:
+                    policy: on-failure
+            "#, Some(RestartPolicy::OnFailure { max_retries: 3, backoff: Duration::from_secs(1), max_backoff: Duration::from_secs(30), reset_after: Duration::from_secs(60) })),
+            (05, r#"
+                restart:
+                    policy: on-failure
+                    max-retries: 5
+                    backoff: 10 ms
+            "#, Some(RestartPolicy::OnFailure { max_retries: 5, backoff: Duration::from_millis(10), max_backoff: Duration::from_secs(30), reset_after: Duration::from_secs(60) })),
+            (06, r#"
+                restart:
+                    policy: sometimes
+            "#, None),
+            (07, r#"
+                restart:
+                    policy: always
+                    backoff: soon
+            "#, None),
+            (08, r#"
+                restart:
+                    policy: on-failure
+                    max-backoff: -1 s
+            "#, None),
+            (09, r#"
+                restart:
+                    policy: on-failure
+                    max-retries: many
+            "#, None),
+            (10, r#"
+                restart:
+                    policy: on-failure
+                    max-retries: -3
+            "#, None),
+            (11, r#"
+                restart:
+                    policy: always
+                    reset-after: 1 fortnight
+            "#, None),
+        ];
+        for (step, conf, target) in test_data {
+            let conf: serde_yaml::Value = serde_yaml::from_str(conf).unwrap();
+            let conf = ConfTree::new_root(conf);
+            let result = RestartPolicy::new(&dbg, &conf);
+            log::debug!("step {} | result: {:?}", step, result);
+            match target {
+                Some(target) => {
+                    let result = result.unwrap();
+                    assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+                }
+                None => assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: Err", step, result),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `RestartPolicy::delay`
+    #[test]
+    fn delay() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("restart_policy_delay");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let always = RestartPolicy::Always { backoff: Duration::from_millis(100), max_backoff: Duration::from_millis(500), reset_after: Duration::from_secs(60) };
+        let on_failure = RestartPolicy::OnFailure { max_retries: 2, backoff: Duration::from_millis(100), max_backoff: Duration::from_secs(30), reset_after: Duration::from_secs(60) };
+        let test_data = [
+            (00, &RestartPolicy::Never, 0, true, None),
+            (01, &always, 0, false, Some(Duration::from_millis(100))),
+            (02, &always, 1, true, Some(Duration::from_millis(200))),
+            (03, &always, 2, false, Some(Duration::from_millis(400))),
+            (04, &always, 3, false, Some(Duration::from_millis(500))),
+            (05, &always, 100, false, Some(Duration::from_millis(500))),
+            (06, &on_failure, 0, false, None),
+            (07, &on_failure, 0, true, Some(Duration::from_millis(100))),
+            (08, &on_failure, 1, true, Some(Duration::from_millis(200))),
+            (09, &on_failure, 2, true, None),
+        ];
+        for (step, policy, retries, failed, target) in test_data {
+            let result = policy.delay(retries, failed);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+}
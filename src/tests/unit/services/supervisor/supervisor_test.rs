@@ -0,0 +1,221 @@
+#[cfg(test)]
+
+mod supervisor {
+    use std::{sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc, Once}, time::{Duration, Instant}};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        services::{
+            conf::{ConfTree, ServicesConf}, entity::{Name, Object, Point}, SubscriptionCriteria,
+            RestartPolicy, Service, ServiceState, Services,
+        },
+        sync::{channel::{self, Receiver, Sender}, Mutex},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Waits until `cond` is true or `timeout` elapsed
+    fn wait_for(timeout: Duration, cond: impl Fn() -> bool) -> bool {
+        let time = Instant::now();
+        while time.elapsed() < timeout {
+            if cond() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        cond()
+    }
+    ///
+    /// Testing service failed twice being restarted with `OnFailure` policy and subscriptions restored
+    #[test]
+    fn on_failure() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("supervisor_on_failure");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let name = "/App/Mok";
+        let log = Arc::new(Mutex::new(vec![]));
+        let built = Arc::new(AtomicUsize::new(0));
+        let build = {
+            let log = log.clone();
+            let built = built.clone();
+            move || -> Result<Arc<dyn Service>, Error> {
+                let instance = built.fetch_add(1, Ordering::SeqCst);
+                // first two instances are crashing
+                let fail = if instance < 2 { Some(Duration::from_millis(50)) } else { None };
+                Ok(Arc::new(ServiceMok::new(name, instance, fail, log.clone())))
+            }
+        };
+        let services = Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None);
+        services.insert(build().unwrap());
+        let (_, _recv) = services.subscribe(name, "/App/Receiver", &[]);
+        services.run_all(Duration::from_secs(1)).unwrap();
+        services.supervise(name, RestartPolicy::OnFailure { max_retries: 5, backoff: Duration::from_millis(10), max_backoff: Duration::from_millis(100), reset_after: Duration::from_secs(60) }, build).unwrap();
+        let restarted = wait_for(Duration::from_secs(3), || services.restarts(name) == Some(2));
+        assert!(restarted, "\nresult: {:?}\ntarget: {:?}", services.restarts(name), Some(2));
+        std::thread::sleep(Duration::from_millis(200));
+        let result = services.restarts(name);
+        assert!(result == Some(2), "\nresult: {:?}\ntarget: {:?}", result, Some(2));
+        let result = services.get(name).map(|s| s.state());
+        assert!(result == Some(ServiceState::Running), "\nresult: {:?}\ntarget: {:?}", result, Some(ServiceState::Running));
+        services.exit_all().unwrap();
+        let result = services.all_restarts().len();
+        assert!(result == 0, "\nresult: {:?}\ntarget: {:?}", result, 0);
+        let result = log.lock().clone();
+        let target = vec![
+            "run 0", "resubscribe 1 /App/Receiver", "run 1", "resubscribe 2 /App/Receiver", "run 2", "exit 2",
+        ];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing service finished without error is not restarted with `OnFailure` policy
+    #[test]
+    fn on_failure_finished() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("supervisor_on_failure_finished");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let name = "/App/Mok";
+        let log = Arc::new(Mutex::new(vec![]));
+        let services = Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None);
+        let service: Arc<dyn Service> = Arc::new(ServiceMok::new(name, 0, None, log.clone()));
+        services.insert(service.clone());
+        services.run_all(Duration::from_secs(1)).unwrap();
+        let build = {
+            let log = log.clone();
+            move || -> Result<Arc<dyn Service>, Error> {
+                Ok(Arc::new(ServiceMok::new(name, 1, None, log.clone())))
+            }
+        };
+        services.supervise(name, RestartPolicy::OnFailure { max_retries: 5, backoff: Duration::from_millis(10), max_backoff: Duration::from_millis(100), reset_after: Duration::from_secs(60) }, build).unwrap();
+        service.exit();
+        let stopped = wait_for(Duration::from_secs(3), || services.states().get(name) == Some(&ServiceState::Stopped));
+        assert!(stopped, "\nresult: {:?}\ntarget: {:?}", services.state(name), Some(ServiceState::Stopped));
+        std::thread::sleep(Duration::from_millis(100));
+        let result = services.restarts(name);
+        assert!(result == Some(0), "\nresult: {:?}\ntarget: {:?}", result, Some(0));
+        test_duration.exit();
+    }
+    ///
+    /// Testing retries are reset if the service was running longer than `reset_after` before failed
+    #[test]
+    fn on_failure_reset() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("supervisor_on_failure_reset");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let name = "/App/Mok";
+        let log = Arc::new(Mutex::new(vec![]));
+        let built = Arc::new(AtomicUsize::new(0));
+        let build = {
+            let log = log.clone();
+            let built = built.clone();
+            move || -> Result<Arc<dyn Service>, Error> {
+                let instance = built.fetch_add(1, Ordering::SeqCst);
+                // every instance is crashing after running stable
+                Ok(Arc::new(ServiceMok::new(name, instance, Some(Duration::from_millis(50)), log.clone())))
+            }
+        };
+        let services = Services::new(&dbg, ServicesConf::new(&dbg, ConfTree::empty()), None);
+        services.insert(build().unwrap());
+        services.run_all(Duration::from_secs(1)).unwrap();
+        // Only one retry, but each instance is running longer than `reset_after`
+        services.supervise(name, RestartPolicy::OnFailure { max_retries: 1, backoff: Duration::from_millis(10), max_backoff: Duration::from_millis(100), reset_after: Duration::from_millis(20) }, build).unwrap();
+        let restarted = wait_for(Duration::from_secs(3), || services.restarts(name).is_some_and(|restarts| restarts >= 3));
+        assert!(restarted, "\nresult: {:?}\ntarget: >= {:?}", services.restarts(name), Some(3));
+        services.exit_all().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Used for testing only, crashes after `fail` elapsed
+    struct ServiceMok {
+        name: Name,
+        instance: usize,
+        fail: Option<Duration>,
+        started: Mutex<Option<Instant>>,
+        exit: AtomicBool,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+    impl ServiceMok {
+        fn new(name: &str, instance: usize, fail: Option<Duration>, log: Arc<Mutex<Vec<String>>>) -> Self {
+            Self {
+                name: Name::new("", name),
+                instance,
+                fail,
+                started: Mutex::new(None),
+                exit: AtomicBool::new(false),
+                log,
+            }
+        }
+    }
+    impl Object for ServiceMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ServiceMok {
+        fn subscribe(&self, _receiver_name: &str, _points: &[SubscriptionCriteria]) -> (Sender<Point>, Receiver<Point>) {
+            channel::unbounded()
+        }
+        fn resubscribe(&self, receiver_name: &str, _points: &[SubscriptionCriteria], _send: Sender<Point>) -> Result<(), Error> {
+            self.log.lock().push(format!("resubscribe {} {}", self.instance, receiver_name));
+            Ok(())
+        }
+        fn run(&self) -> Result<(), Error> {
+            self.log.lock().push(format!("run {}", self.instance));
+            *self.started.lock() = Some(Instant::now());
+            Ok(())
+        }
+        fn wait(&self) -> Result<(), Error> {
+            loop {
+                if self.exit.load(Ordering::SeqCst) {
+                    return Ok(());
+                }
+                if let (Some(fail), Some(started)) = (self.fail, *self.started.lock()) {
+                    if started.elapsed() >= fail {
+                        return Err(Error::new("ServiceMok", "wait").err(format!("Instance {} crashed", self.instance)));
+                    }
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+        fn is_finished(&self) -> bool {
+            self.exit.load(Ordering::SeqCst)
+        }
+        fn exit(&self) {
+            self.log.lock().push(format!("exit {}", self.instance));
+            self.exit.store(true, Ordering::SeqCst);
+        }
+    }
+    impl std::fmt::Debug for ServiceMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ServiceMok")
+                .field("name", &self.name)
+                .field("instance", &self.instance)
+                .finish()
+        }
+    }
+}
@@ -0,0 +1 @@
+mod tcp_test;
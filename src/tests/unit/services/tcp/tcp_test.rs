@@ -0,0 +1,309 @@
+#[cfg(test)]
+
+mod tcp {
+    use std::{str::FromStr, sync::{Arc, Once}, time::{Duration, Instant}};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use chrono::Utc;
+    use crate::{
+        kernel::clock::{GlobalClock, VirtualClock},
+        services::{
+            conf::{ConfTree, ServicesConf},
+            entity::{Name, Object, Point, Status},
+            LinkName, MultiQueue, MultiQueueConf, Service, ServiceError, ServiceState, Services,
+            TcpClient, TcpConf, TcpServer,
+        },
+        sync::channel::{self, Receiver, Sender},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns [TcpConf] parsed from the yaml containing single service node
+    fn conf(yaml: &str) -> TcpConf {
+        let conf = ConfTree::new_root(serde_yaml::from_str(yaml).unwrap()).next().unwrap();
+        TcpConf::new("App", conf).unwrap()
+    }
+    ///
+    /// Waits until `cond` is true
+    fn wait(timeout: Duration, cond: impl Fn() -> bool) -> bool {
+        let time = Instant::now();
+        while time.elapsed() < timeout {
+            if cond() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        cond()
+    }
+    ///
+    /// Returns the next point received by the `recv`, skipping points with names not in `names`
+    fn recv(recv: &Receiver<Point>, names: &[&str]) -> Point {
+        loop {
+            let point = recv.recv_timeout(Duration::from_secs(3)).unwrap();
+            if names.contains(&point.name().as_str()) {
+                return point;
+            }
+        }
+    }
+    ///
+    /// Testing points written into the `MultiQueue` on the server side
+    /// are received over loopback on the client side, including reconnect after the server restarted
+    #[test]
+    fn loopback() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("tcp_loopback");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let multi_queue = Arc::new(MultiQueue::new(
+            MultiQueueConf::from_yaml("App", &serde_yaml::from_str(r#"
+                service MultiQueue:
+                    in queue in-queue:
+                        max-length: 10000
+            "#).unwrap()),
+            services.clone(),
+            None,
+        ));
+        services.insert(multi_queue.clone());
+        let (receiver, recv) = ReceiverMok::new("App", "Receiver");
+        services.insert(Arc::new(receiver));
+        let server_conf = r#"
+            service TcpServer Server:
+                address: 127.0.0.1:0
+                subscribe: MultiQueue
+        "#;
+        let server = Arc::new(TcpServer::new(conf(server_conf), services.clone(), None));
+        services.insert(server.clone());
+        multi_queue.run().unwrap();
+        server.run().unwrap();
+        let address = server.local_addr().unwrap();
+        let client = Arc::new(TcpClient::new(conf(&format!(r#"
+            service TcpClient Client:
+                address: {}
+                reconnect: 100 ms
+                max-reconnect: 200 ms
+                send-to: Receiver.in-queue
+                diagnosis:
+                    point Connection:
+                        type: 'Int'
+        "#, address)), services.clone(), None));
+        services.insert(client.clone());
+        let result = client.send_to();
+        let target = vec![LinkName::from_str("/App/Receiver.in-queue").unwrap()];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = server.subscribed_to();
+        let target = vec!["/App/MultiQueue".to_owned()];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        client.run().unwrap();
+        let connection = "/App/Client/Connection";
+        let result = recv(&recv, &[connection]).as_int().value;
+        let target = i64::from(Status::Invalid);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = recv(&recv, &[connection]).as_int().value;
+        let target = i64::from(Status::Ok);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        assert!(wait(Duration::from_secs(3), || server.is_connected()), "\nresult: {:?}\ntarget: {:?}", server.is_connected(), true);
+        let result = client.state();
+        let target = ServiceState::Running;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let send = services.get_link(&LinkName::from_str("/App/MultiQueue.in-queue").unwrap()).unwrap();
+        let test_data = [
+            Point::new(0, "/App/Point.Bool", true),
+            Point::new(0, "/App/Point.Int", 123i64),
+            Point::new(0, "/App/Point.Real", 1.5f32),
+            Point::new(0, "/App/Point.Double", -0.125f64),
+            Point::new(0, "/App/Point.String", "multi\nline"),
+        ];
+        let names: Vec<String> = test_data.iter().map(|point| point.name()).collect();
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        for point in &test_data {
+            send.send(point.clone()).unwrap();
+        }
+        for (step, target) in test_data.iter().enumerate() {
+            let result = recv(&recv, &names);
+            assert!(result.name() == target.name() && result.cmp_value(target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            assert!(result.tx_id() != target.tx_id(), "step {} \nresult: {:?}\ntarget: tx_id of the client", step, result.tx_id());
+        }
+        // Server stopped, client must report the lost connection and reconnect to the restarted one
+        server.exit();
+        server.wait().unwrap();
+        let result = recv(&recv, &[connection]).as_int().value;
+        let target = i64::from(Status::Invalid);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = client.state();
+        let target = ServiceState::Degraded;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let server = Arc::new(TcpServer::new(conf(&format!(r#"
+            service TcpServer Server:
+                address: {}
+                subscribe: MultiQueue
+        "#, address)), services.clone(), None));
+        server.run().unwrap();
+        let result = recv(&recv, &[connection]).as_int().value;
+        let target = i64::from(Status::Ok);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        assert!(wait(Duration::from_secs(3), || server.is_connected()), "\nresult: {:?}\ntarget: {:?}", server.is_connected(), true);
+        send.send(test_data[1].clone()).unwrap();
+        let result = recv(&recv, &names);
+        let target = &test_data[1];
+        assert!(result.name() == target.name() && result.cmp_value(target), "\nresult: {:?}\ntarget: {:?}", result, target);
+        client.exit();
+        server.exit();
+        multi_queue.exit();
+        client.wait().unwrap();
+        server.wait().unwrap();
+        multi_queue.wait().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing `TcpClient` keeps reconnecting while the server is not available
+    #[test]
+    fn reconnect() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("tcp_reconnect");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        // Free port, nobody is listening on it
+        let address = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let (receiver, recv) = ReceiverMok::new("App", "Receiver");
+        services.insert(Arc::new(receiver));
+        let client = Arc::new(TcpClient::new(conf(&format!(r#"
+            service TcpClient Client:
+                address: {}
+                reconnect: 50 ms
+                max-reconnect: 100 ms
+                send-to: Receiver.in-queue
+                diagnosis:
+                    point Connection:
+                        type: 'Int'
+        "#, address)), services.clone(), None));
+        client.run().unwrap();
+        let connection = "/App/Client/Connection";
+        let result = recv(&recv, &[connection]).as_int().value;
+        let target = i64::from(Status::Invalid);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        std::thread::sleep(Duration::from_millis(300));
+        let result = client.state();
+        let target = ServiceState::Degraded;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let listener = std::net::TcpListener::bind(address).unwrap();
+        let (_stream, _) = listener.accept().unwrap();
+        let result = recv(&recv, &[connection]).as_int().value;
+        let target = i64::from(Status::Ok);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        client.exit();
+        client.wait().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing `TcpClient` reconnect delay is measured by the clock of the `Services`
+    #[test]
+    fn reconnect_virtual_clock() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("tcp_reconnect_virtual_clock");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let services = {
+            let _clock = GlobalClock::local(clock.clone());
+            Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None))
+        };
+        // Free port, nobody is listening on it
+        let address = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let (receiver, recv) = ReceiverMok::new("App", "Receiver");
+        services.insert(Arc::new(receiver));
+        let client = Arc::new(TcpClient::new(conf(&format!(r#"
+            service TcpClient Client:
+                address: {}
+                reconnect: 1 h
+                max-reconnect: 1 h
+                send-to: Receiver.in-queue
+                diagnosis:
+                    point Connection:
+                        type: 'Int'
+        "#, address)), services.clone(), None));
+        client.run().unwrap();
+        let connection = "/App/Client/Connection";
+        let result = recv(&recv, &[connection]).as_int().value;
+        let target = i64::from(Status::Invalid);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let listener = std::net::TcpListener::bind(address).unwrap();
+        // The client is waiting for the virtual hour, not reconnected in the real time
+        let result = clock.wait_sleeping(1, Duration::from_secs(1));
+        assert!(result, "\nresult: {:?}\ntarget: {:?}", result, true);
+        std::thread::sleep(Duration::from_millis(300));
+        let result = client.state();
+        let target = ServiceState::Degraded;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        clock.advance(Duration::from_secs(3600));
+        let (_stream, _) = listener.accept().unwrap();
+        let result = recv(&recv, &[connection]).as_int().value;
+        let target = i64::from(Status::Ok);
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        client.exit();
+        client.wait().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Used for testing only, forwards points received by `in-queue` into the test
+    struct ReceiverMok {
+        name: Name,
+        send: Sender<Point>,
+    }
+    impl ReceiverMok {
+        fn new(parent: &str, name: &str) -> (Self, Receiver<Point>) {
+            let (send, recv) = channel::unbounded();
+            (Self { name: Name::new(parent, name), send }, recv)
+        }
+    }
+    impl Object for ReceiverMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ReceiverMok {
+        fn try_get_link(&self, name: &str) -> Result<Sender<Point>, ServiceError> {
+            match name {
+                "in-queue" => Ok(self.send.clone()),
+                _ => Err(ServiceError::link_not_found(self.name.join(), name)),
+            }
+        }
+        fn run(&self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn is_finished(&self) -> bool {
+            false
+        }
+        fn exit(&self) {}
+    }
+    impl std::fmt::Debug for ReceiverMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ReceiverMok")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+}
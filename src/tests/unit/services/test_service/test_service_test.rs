@@ -0,0 +1,138 @@
+#[cfg(test)]
+
+mod test_service {
+    use std::{sync::{Arc, Once}, time::{Duration, Instant}};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        conf::{ConfTree, ServicesConf},
+        entity::{Point, Status},
+        MultiQueue, MultiQueueConf, Service, Services, TestService,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing waiting assertions on the points received by the link
+    #[test]
+    fn link() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test_service_link");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test = TestService::new("App", "Test", &["in-queue"]);
+        let result = test.in_queues();
+        let target = vec!["in-queue".to_owned()];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = test.try_get_link("out-queue");
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result.map(|_| ()));
+        let send = test.get_link("in-queue");
+        let sender = std::thread::spawn(move || {
+            for (name, value) in [("/App/Drive/Start", 1), ("/App/Drive/Speed", 10), ("/App/Drive/Speed", 20)] {
+                std::thread::sleep(Duration::from_millis(20));
+                send.send(Point::new(0, name, value as i64)).unwrap();
+            }
+        });
+        let time = Instant::now();
+        let result = test.wait_value("/App/Drive/Speed", 20i64, Duration::from_secs(3)).map(|record| record.point.as_int().value);
+        assert!(result.as_ref().is_ok_and(|value| *value == 20), "\nresult: {:?}\ntarget: {:?}", result, 20);
+        assert!(time.elapsed() < Duration::from_secs(1), "\nresult: {:?}\ntarget: < 1 s", time.elapsed());
+        sender.join().unwrap();
+        let result = test.wait_count(3, Duration::from_millis(10)).map(|records| records.len());
+        assert!(result.as_ref().is_ok_and(|len| *len == 3), "\nresult: {:?}\ntarget: {:?}", result, 3);
+        let result = test.wait_count(4, Duration::from_millis(50));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let result = test.records().iter().all(|record| record.source == "in-queue");
+        assert!(result, "\nresult: {:?}\ntarget: {:?}", result, true);
+        // Not received values
+        let result = test.wait_value("/App/Drive/Speed", 30i64, Duration::from_millis(50));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let result = test.wait_value("/App/Drive/Speed", 20.0f64, Duration::from_millis(50));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let result = test.wait_point("/App/Drive/Stop", Duration::from_millis(50));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        // Order of the first received
+        let result = test.assert_order(&["/App/Drive/Start", "/App/Drive/Speed"]);
+        assert!(result.is_ok(), "\nresult: {:?}\ntarget: Ok", result);
+        let result = test.assert_order(&["/App/Drive/Speed", "/App/Drive/Start"]);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        let result = test.assert_order(&["/App/Drive/Start", "/App/Drive/Stop"]);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        // Only points received after the call are checked
+        let result = test.assert_none(Duration::from_millis(50), |point| point.name() == "/App/Drive/Speed");
+        assert!(result.is_ok(), "\nresult: {:?}\ntarget: Ok", result);
+        let send = test.get_link("in-queue");
+        let sender = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            send.send(Point::new(0, "/App/Drive/Speed", 0i64)).unwrap();
+        });
+        let time = Instant::now();
+        let result = test.assert_none(Duration::from_secs(3), |point| point.name() == "/App/Drive/Speed");
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        assert!(time.elapsed() < Duration::from_secs(1), "\nresult: {:?}\ntarget: < 1 s", time.elapsed());
+        sender.join().unwrap();
+        test.clear();
+        assert!(test.is_empty(), "\nresult: {:?}\ntarget: {:?}", test.len(), 0);
+        test.exit();
+        test.wait().unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing points received by the subscription on the `MultiQueue`
+    #[test]
+    fn subscribe() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("test_service_subscribe");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let services = Arc::new(Services::new("App", ServicesConf::new(&dbg, ConfTree::empty()), None));
+        let multi_queue = Arc::new(MultiQueue::new(
+            MultiQueueConf::from_yaml("App", &serde_yaml::from_str(r#"
+                service MultiQueue:
+                    in queue in-queue:
+                        max-length: 10000
+            "#).unwrap()),
+            services.clone(),
+            None,
+        ));
+        services.insert(multi_queue.clone());
+        let test = Arc::new(TestService::new("App", "Test", &[]));
+        services.insert(test.clone());
+        multi_queue.run().unwrap();
+        test.subscribe_on(&services, "/App/MultiQueue", &[]).unwrap();
+        let result = test.subscribed_to();
+        let target = vec!["/App/MultiQueue".to_owned()];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let send = multi_queue.get_link("in-queue");
+        send.send(Point::new(0, "/App/Drive/State", "Run")).unwrap();
+        let result = test.wait_value("/App/Drive/State", "Run", Duration::from_secs(3)).map(|record| record.source);
+        let target = "/App/MultiQueue".to_owned();
+        assert!(result.as_ref().is_ok_and(|source| *source == target), "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = test.assert_none(Duration::from_millis(50), |point| point.status() != Status::Ok);
+        assert!(result.is_ok(), "\nresult: {:?}\ntarget: Ok", result);
+        test.unsubscribe_all(&services);
+        test.exit();
+        multi_queue.exit();
+        test.wait().unwrap();
+        multi_queue.wait().unwrap();
+        test_duration.exit();
+    }
+}
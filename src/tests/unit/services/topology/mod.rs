@@ -0,0 +1 @@
+mod topology_test;
@@ -0,0 +1,191 @@
+#[cfg(test)]
+
+mod topology {
+    use std::{str::FromStr, sync::{Arc, Once}, time::Duration};
+    use sal_core::{dbg::Dbg, error::Error};
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::services::{
+        conf::{ConfTree, ServicesConf}, entity::{Name, Object, PointConfig},
+        LinkName, Service, Services, Topology, TopologyService, TopologySubscription,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns [Services] holding:
+    /// - `/App/Ied` owning `Drive.Speed`, sends to `/App/MultiQueue.in-queue`
+    /// - `/App/MultiQueue` with broadcast subscription of `/App/Api` and multicast of `/App/Task`
+    /// - `/App/Task` subscribed on `/App/MultiQueue`
+    fn services(dbg: &Dbg) -> Services {
+        let services = Services::new(dbg, ServicesConf::new(dbg, ConfTree::empty()), None);
+        let ied = Name::new("/App", "Ied");
+        let point: serde_yaml::Value = serde_yaml::from_str(r#"
+            Drive.Speed:
+                type: Real
+        "#).unwrap();
+        services.insert(Arc::new(ServiceMok {
+            name: ied.clone(),
+            in_queues: vec![],
+            send_to: vec![LinkName::from_str("/App/MultiQueue.in-queue").unwrap()],
+            subscribed_to: vec![],
+            subscriptions: vec![],
+            points: vec![PointConfig::from_yaml(&ied, &point)],
+        }));
+        services.insert(Arc::new(ServiceMok {
+            name: Name::new("/App", "MultiQueue"),
+            in_queues: vec!["in-queue".to_owned()],
+            send_to: vec![],
+            subscribed_to: vec![],
+            subscriptions: vec![
+                TopologySubscription::broadcast("/App/Api"),
+                TopologySubscription::multicast("/App/Task", vec!["Inf:/App/Ied/Drive.Speed".to_owned()]),
+            ],
+            points: vec![],
+        }));
+        services.insert(Arc::new(ServiceMok {
+            name: Name::new("/App", "Task"),
+            in_queues: vec!["recv-queue".to_owned()],
+            send_to: vec![],
+            subscribed_to: vec!["/App/MultiQueue".to_owned()],
+            subscriptions: vec![],
+            points: vec![],
+        }));
+        services
+    }
+    ///
+    /// Testing `Services::topology`
+    #[test]
+    fn topology() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("topology");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let topology = services(&dbg).topology();
+        let result: Vec<&str> = topology.services.iter().map(|s| s.name.as_str()).collect();
+        let target = vec!["/App/Ied", "/App/MultiQueue", "/App/Task"];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = topology.service("/App/Ied").unwrap().send_to.clone();
+        let target = vec!["/App/MultiQueue.in-queue"];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = topology.owner("/App/Ied/Drive.Speed");
+        assert!(result == Some("/App/Ied"), "\nresult: {:?}\ntarget: {:?}", result, Some("/App/Ied"));
+        let result = topology.service("/App/MultiQueue").unwrap().subscriptions.iter().filter(|s| s.broadcast).count();
+        assert!(result == 1, "\nresult: {:?}\ntarget: {:?}", result, 1);
+        let json: serde_json::Value = serde_json::from_str(&topology.to_json().unwrap()).unwrap();
+        let result = json["services"][1]["subscriptions"][1]["destinations"][0].as_str();
+        let target = Some("Inf:/App/Ied/Drive.Speed");
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = json["services"][1]["in_queues"][0].as_str();
+        assert!(result == Some("in-queue"), "\nresult: {:?}\ntarget: {:?}", result, Some("in-queue"));
+        let dot = topology.to_dot();
+        log::debug!("{} | dot: \n{}", dbg, dot);
+        for target in [
+            "digraph Services {",
+            r#""/App/Ied" -> "/App/MultiQueue":in_queue;"#,
+            r#""/App/MultiQueue" -> "/App/Api" [style=dashed, label="*"];"#,
+            r#""/App/MultiQueue" -> "/App/Task" [style=dashed, label="1"];"#,
+        ] {
+            assert!(dot.contains(target), "\nresult: {}\ntarget: {:?}", dot, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `Topology::to_dot` escapes the ids and the labels
+    #[test]
+    fn dot_escape() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("topology_dot_escape");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let service = |name: &str, in_queues: &[&str], send_to: &[&str], subscriptions: Vec<TopologySubscription>| TopologyService {
+            name: name.to_owned(),
+            state: None,
+            in_queues: in_queues.iter().map(|s| s.to_string()).collect(),
+            send_to: send_to.iter().map(|s| s.to_string()).collect(),
+            subscribed_to: vec![],
+            subscriptions,
+            points: vec![],
+        };
+        let topology = Topology {
+            services: vec![
+                service(r#"/App/Drive "A""#, &[], &[r#"/App/Queue\1.in|queue"#], vec![]),
+                service(r#"/App/Queue\1"#, &["in|queue"], &[], vec![TopologySubscription::broadcast("/App/Task\n1")]),
+            ],
+        };
+        let dot = topology.to_dot();
+        log::debug!("{} | dot: \n{}", dbg, dot);
+        for target in [
+            r#"    "/App/Drive \"A\"" [label="/App/Drive \"A\""];"#,
+            r#"    "/App/Queue\\1" [label="{/App/Queue\\1|{<in_queue> in\|queue}}"];"#,
+            r#"    "/App/Drive \"A\"" -> "/App/Queue\\1":in_queue;"#,
+            r#"    "/App/Queue\\1" -> "/App/Task\n1" [style=dashed, label="*"];"#,
+        ] {
+            assert!(dot.contains(target), "\nresult: {}\ntarget: {:?}", dot, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Used for testing only
+    struct ServiceMok {
+        name: Name,
+        in_queues: Vec<String>,
+        send_to: Vec<LinkName>,
+        subscribed_to: Vec<String>,
+        subscriptions: Vec<TopologySubscription>,
+        points: Vec<PointConfig>,
+    }
+    impl Object for ServiceMok {
+        fn name(&self) -> Name {
+            self.name.clone()
+        }
+    }
+    impl Service for ServiceMok {
+        fn in_queues(&self) -> Vec<String> {
+            self.in_queues.clone()
+        }
+        fn send_to(&self) -> Vec<LinkName> {
+            self.send_to.clone()
+        }
+        fn subscribed_to(&self) -> Vec<String> {
+            self.subscribed_to.clone()
+        }
+        fn subscriptions(&self) -> Vec<TopologySubscription> {
+            self.subscriptions.clone()
+        }
+        fn points(&self) -> Vec<PointConfig> {
+            self.points.clone()
+        }
+        fn run(&self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn is_finished(&self) -> bool {
+            false
+        }
+        fn exit(&self) {}
+    }
+    impl std::fmt::Debug for ServiceMok {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ServiceMok")
+                .field("name", &self.name)
+                .finish()
+        }
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+
+mod async_channel {
+    use std::{
+        pin::pin, sync::{Arc, Once}, task::{Context, Poll, Wake, Waker},
+        thread::{self, Thread}, time::Duration,
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::sync::channel;
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Wakes the thread polling the future
+    struct ThreadWaker(Thread);
+    //
+    //
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+    ///
+    /// Minimal executor, polls the `future` on the current thread until ready
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        let waker: Waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+    ///
+    /// Testing values sent by the sync side are received by the async side of the same channel and back
+    #[test]
+    fn sync_async() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("async_channel_sync_async");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [1, 2, 3, 4, 5];
+        let (send, recv) = channel::unbounded::<i32>();
+        let recv = channel::to_async_receiver(recv);
+        let h = thread::spawn(move || {
+            for value in test_data {
+                thread::sleep(Duration::from_millis(5));
+                send.send(value).unwrap();
+            }
+        });
+        let result = block_on(async {
+            let mut values = vec![];
+            while let Ok(value) = recv.recv().await {
+                values.push(value);
+            }
+            values
+        });
+        let target = test_data.to_vec();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        h.join().unwrap();
+        let (send, recv) = channel::bounded_async::<i32>(1);
+        let recv = channel::to_sync_receiver(recv);
+        let h = thread::spawn(move || {
+            block_on(async move {
+                for value in test_data {
+                    send.send(value).await.unwrap();
+                }
+            })
+        });
+        let result: Vec<i32> = recv.iter().collect();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        h.join().unwrap();
+        let (send, recv) = channel::unbounded_async::<i32>();
+        let send = channel::to_sync_sender(channel::to_async_sender(channel::to_sync_sender(send)));
+        send.send(7).unwrap();
+        let result = block_on(recv.recv()).unwrap();
+        let target = 7;
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+}
@@ -1,2 +1,3 @@
+mod async_channel_test;
 mod handles_test;
 mod kanal_channel_test;
@@ -1,6 +1,7 @@
 use std::sync::{atomic::{AtomicUsize, Ordering}, Arc, Mutex};
 use coco::Stack;
 use sal_core::{dbg::Dbg, error::Error};
+use crate::kernel::metrics::Metrics;
 use super::{job::Job, scheduler::Scheduler, worker::Worker, JoinHandle};
 ///
 /// Provides ready to execute specified number of threads
@@ -43,6 +44,15 @@ impl ThreadPool {
         let (sender, receiver) = kanal::unbounded();
         let receiver = Arc::new(Mutex::new(receiver));
         let workers = Arc::new(Stack::new());
+        let pool = dbg.to_string();
+        let size_ref = Arc::downgrade(&size);
+        Metrics::global().gauge_fn("sal_thread_pool_workers", "Current number of the ThreadPool workers", &[("pool", &pool)], move || {
+            size_ref.upgrade().map(|size| size.load(Ordering::SeqCst) as f64)
+        });
+        let free_ref = Arc::downgrade(&free);
+        Metrics::global().gauge_fn("sal_thread_pool_free_workers", "Not busy ThreadPool workers", &[("pool", &pool)], move || {
+            free_ref.upgrade().map(|free| free.load(Ordering::SeqCst) as f64)
+        });
         for _ in 0..if capacity.load(Ordering::SeqCst) > 1 { 2 } else { 1 } {
             workers.push(Worker::new(
                 &dbg,
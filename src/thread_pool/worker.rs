@@ -1,6 +1,7 @@
 use std::sync::{atomic::{AtomicUsize, Ordering}, Arc, Mutex};
 use coco::Stack;
 use sal_core::{dbg::Dbg, error::Error};
+use crate::kernel::metrics::Metrics;
 use super::job::Job;
 ///
 /// Picks up code to be executed in the [Worker]’s thread on the `ThreadPool`
@@ -30,6 +31,7 @@ impl Worker {
         let id = size.load(Ordering::SeqCst);
         let dbg = Dbg::new(&parent, format!("Worker({id})"));
         size.fetch_add(1, Ordering::SeqCst);
+        let jobs = Metrics::global().counter("sal_thread_pool_jobs_total", "Jobs executed by the ThreadPool", &[("pool", &parent)]);
         log::debug!("{dbg}.new | Created, capacity: {}, size: {}, free: {}", capacity.load(Ordering::SeqCst), size.load(Ordering::SeqCst), free.load(Ordering::SeqCst));
         let thread = std::thread::spawn(move || loop {
             // let error = Error::new(&dbg, "new");
@@ -67,6 +69,7 @@ impl Worker {
                 Some((job, done)) => {
                     log::debug!("{dbg}.new | Executing job...");
                     free.fetch_sub(1, Ordering::SeqCst);
+                    jobs.inc();
                     let _ = job();
                     if let Err(err) = done.send(()) {
                         log::trace!("{dbg}.new | Send 'Done' error: {:?}", err);